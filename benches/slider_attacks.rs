@@ -0,0 +1,77 @@
+//! Times the slider attack lookups used by the move generator and a perft run on top of them.
+//!
+//! Run with `cargo bench --bench slider_attacks`, adding `RUSTFLAGS="-C target-feature=+bmi2"` to
+//! compare against the build where PEXT is used without any runtime dispatch.
+
+use std::hint::black_box;
+use std::time::Instant;
+use timecat::*;
+
+const NUM_ROUNDS: usize = 200;
+
+fn time<T>(name: &str, num_calls: usize, mut f: impl FnMut() -> T) {
+    let instant = Instant::now();
+    black_box(f());
+    let elapsed = instant.elapsed();
+    println!(
+        "{name:<40} {:>10.2} ms {:>8.2} ns/call",
+        elapsed.as_secs_f64() * 1e3,
+        elapsed.as_nanos() as f64 / num_calls as f64,
+    );
+}
+
+fn main() {
+    let blockers_list = (0..1024u64)
+        .map(|i| {
+            BitBoard::new(i.wrapping_mul(0x9E3779B97F4A7C15) & i.wrapping_mul(0xD1B54A32D192ED03))
+        })
+        .collect::<Vec<_>>();
+    let num_calls = NUM_ROUNDS * ALL_SQUARES.len() * blockers_list.len();
+    println!("Selected method: {}", SliderAttackMethod::get_selected());
+    time("get_queen_moves (selected method)", num_calls, || {
+        let mut result = BitBoard::EMPTY;
+        for _ in 0..NUM_ROUNDS {
+            for &square in &ALL_SQUARES {
+                for &blockers in &blockers_list {
+                    result ^= get_queen_moves(black_box(square), black_box(blockers));
+                }
+            }
+        }
+        result
+    });
+    for method in [SliderAttackMethod::Magic, SliderAttackMethod::Pext] {
+        if !method.is_supported() {
+            continue;
+        }
+        time(
+            &format!("{method} queen moves (explicit)"),
+            num_calls,
+            || {
+                let mut result = BitBoard::EMPTY;
+                for _ in 0..NUM_ROUNDS {
+                    for &square in &ALL_SQUARES {
+                        for &blockers in &blockers_list {
+                            result ^= method
+                                .get_bishop_moves(black_box(square), black_box(blockers))
+                                ^ method.get_rook_moves(black_box(square), black_box(blockers));
+                        }
+                    }
+                }
+                result
+            },
+        );
+    }
+    for (fen, depth) in [
+        (STARTING_POSITION_FEN, 5),
+        (
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            4,
+        ),
+    ] {
+        let position = BoardPosition::from_str(fen).unwrap();
+        let num_nodes = MoveGenerator::perft_test(&position, depth);
+        time(&format!("perft {depth} of {fen:.20}"), num_nodes, || {
+            MoveGenerator::perft_test(&position, depth)
+        });
+    }
+}
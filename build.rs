@@ -139,6 +139,20 @@ mod bitboards_generation {
         moves_bb
     }
 
+    /// Software version of the PEXT instruction, so that the tables do not depend on the CPU building them.
+    fn pext(source: u64, mut mask: u64) -> u64 {
+        let mut result = 0;
+        let mut bit = 0;
+        while mask != 0 {
+            if source & mask & mask.wrapping_neg() != 0 {
+                result |= 1 << bit;
+            }
+            bit += 1;
+            mask &= mask - 1;
+        }
+        result
+    }
+
     fn generate_all_sub_masks_and_moves(
         mask: u64,
         square: u8,
@@ -408,7 +422,6 @@ mod bitboards_generation {
             right_shift: u8,
         }
 
-        #[cfg(target_feature = "bmi2")]
         #[derive(Clone, Copy, Debug, Default)]
         struct BmiMagic {
             blockers_mask: BitBoard,
@@ -453,11 +466,8 @@ mod bitboards_generation {
 
         const NUM_MOVES: usize = 64 * (1 << 12) + 64 * (1 << 9);
 
-        #[cfg(target_feature = "bmi2")]
         let mut bmi_offset = 0;
-        #[cfg(target_feature = "bmi2")]
         let mut bishop_and_rook_bmi_masks = [[BmiMagic::default(); 64]; 2];
-        #[cfg(target_feature = "bmi2")]
         let mut bmi_moves = vec![0; NUM_MOVES];
 
         let mut offset = 0;
@@ -512,14 +522,10 @@ mod bitboards_generation {
                     .unwrap_or(offset);
                 offset = offset.max(magic.offset + num_sub_masks);
 
-                #[cfg(target_feature = "bmi2")]
                 let bmi_magic = &mut bishop_and_rook_bmi_masks[piece_index][square_index];
-                #[cfg(target_feature = "bmi2")]
-                {
-                    bmi_magic.blockers_mask = magic.mask;
-                    bmi_magic.offset = bmi_offset;
-                    bmi_offset += num_sub_masks;
-                }
+                bmi_magic.blockers_mask = magic.mask;
+                bmi_magic.offset = bmi_offset;
+                bmi_offset += num_sub_masks;
 
                 #[allow(clippy::needless_range_loop)]
                 for sub_masks_and_moves_array_index in 0..num_sub_masks {
@@ -531,22 +537,9 @@ mod bitboards_generation {
                     moves[index].0 |= moves_bb;
                     rays_cache_temp[index] |= ray.0;
 
-                    #[cfg(target_feature = "bmi2")]
-                    {
-                        use std::arch::x86_64::_pext_u64;
-                        bmi_moves[bmi_magic.offset
-                            + unsafe {
-                                _pext_u64(
-                                    sub_masks_and_moves_array[sub_masks_and_moves_array_index].0,
-                                    bmi_magic.blockers_mask.0,
-                                ) as usize
-                            }] = unsafe {
-                            _pext_u64(
-                                sub_masks_and_moves_array[sub_masks_and_moves_array_index].1,
-                                ray.0,
-                            ) as u16
-                        };
-                    }
+                    bmi_moves
+                        [bmi_magic.offset + pext(sub_mask, bmi_magic.blockers_mask.0) as usize] =
+                        pext(moves_bb, ray.0) as u16;
                 }
             }
         }
@@ -571,26 +564,26 @@ mod bitboards_generation {
             &moves[0..offset]
         )?;
 
-        #[cfg(target_feature = "bmi2")]
-        {
-            writeln!(file, r##"#[derive(Clone, Copy)]"##)?;
-            writeln!(file, r##"struct BmiMagic {{"##)?;
-            writeln!(file, r##"    blockers_mask: BitBoard,"##)?;
-            writeln!(file, r##"    offset: usize,"##)?;
-            writeln!(file, r##"}}"##)?;
-
-            writeln!(
-                file,
-                r"const BISHOP_AND_ROOK_BMI_MASKS: [[BmiMagic; 64]; 2] = {:#?};",
-                bishop_and_rook_bmi_masks,
-            )?;
-            writeln!(
-                file,
-                r"static BMI_MOVES: [u16; {}] = {:#?};",
-                bmi_offset,
-                &bmi_moves[0..bmi_offset]
-            )?;
-        }
+        writeln!(file, r##"#[cfg(target_arch = "x86_64")]"##)?;
+        writeln!(file, r##"#[derive(Clone, Copy)]"##)?;
+        writeln!(file, r##"struct BmiMagic {{"##)?;
+        writeln!(file, r##"    blockers_mask: BitBoard,"##)?;
+        writeln!(file, r##"    offset: usize,"##)?;
+        writeln!(file, r##"}}"##)?;
+
+        writeln!(file, r##"#[cfg(target_arch = "x86_64")]"##)?;
+        writeln!(
+            file,
+            r"const BISHOP_AND_ROOK_BMI_MASKS: [[BmiMagic; 64]; 2] = {:#?};",
+            bishop_and_rook_bmi_masks,
+        )?;
+        writeln!(file, r##"#[cfg(target_arch = "x86_64")]"##)?;
+        writeln!(
+            file,
+            r"static BMI_MOVES: [u16; {}] = {:#?};",
+            bmi_offset,
+            &bmi_moves[0..bmi_offset]
+        )?;
 
         Ok(())
     }
@@ -35,15 +35,22 @@ impl GameResult {
 #[derive(Clone, Debug)]
 pub struct Board {
     position: BoardPosition,
-    stack: Vec<(BoardPosition, ValidOrNullMove)>,
+    stack: Vec<UndoRecord>,
+    starting_fen: String,
     repetition_table: RepetitionTable,
     #[cfg(feature = "extras")]
     evaluator: Evaluator,
 }
 
 impl Board {
+    #[inline]
     pub fn new() -> Self {
-        BoardPosition::from_str(STARTING_POSITION_FEN)
+        Self::new_variant(Variant::Standard)
+    }
+
+    /// Starting position of the given variant.
+    pub fn new_variant(variant: Variant) -> Self {
+        BoardPosition::from_fen_and_variant(variant.get_starting_fen(), variant)
             .unwrap()
             .into()
     }
@@ -51,24 +58,42 @@ impl Board {
     pub fn shallow_clone(&self) -> Self {
         Self {
             position: self.position.clone(),
+            starting_fen: self.get_fen(),
             repetition_table: self.repetition_table.clone(),
             ..Default::default()
         }
     }
 
+    /// Sets the position from the fen keeping the variant.
+    #[inline]
     pub fn set_fen(&mut self, fen: &str) -> Result<()> {
-        self.position.set_fen(fen)?;
+        self.set_fen_and_variant(fen, self.get_variant())
+    }
+
+    pub fn set_fen_and_variant(&mut self, fen: &str, variant: Variant) -> Result<()> {
+        self.position.set_fen_and_variant(fen, variant)?;
         self.stack.clear();
+        self.starting_fen = self.get_fen();
         self.update_repetition_table();
         Ok(())
     }
 
+    #[inline]
     pub fn from_fen(fen: &str) -> Result<Self> {
-        let mut board = Self::new();
+        Self::from_fen_and_variant(fen, Variant::Standard)
+    }
+
+    pub fn from_fen_and_variant(fen: &str, variant: Variant) -> Result<Self> {
+        let mut board = Self::new_variant(variant);
         board.set_fen(fen)?;
         Ok(board)
     }
 
+    /// Replays the main line of the first game of the PGN.
+    pub fn from_pgn(pgn: &str) -> Result<Self> {
+        PgnGame::from_str(pgn)?.to_board()
+    }
+
     pub fn get_position(&self) -> &BoardPosition {
         &self.position
     }
@@ -84,7 +109,7 @@ impl Board {
     }
 
     pub fn reset(&mut self) {
-        self.set_fen(STARTING_POSITION_FEN).unwrap();
+        self.set_fen(self.get_variant().get_starting_fen()).unwrap();
     }
 
     pub fn clear(&mut self) {
@@ -94,43 +119,55 @@ impl Board {
     pub fn flip_vertical(&mut self) {
         self.position.flip_vertical();
         self.stack.clear();
+        self.starting_fen = self.get_fen();
         self.update_repetition_table();
     }
 
     pub fn flip_vertical_and_flip_turn_unchecked(&mut self) {
         self.position.flip_vertical_and_flip_turn_unchecked();
         self.stack.clear();
+        self.starting_fen = self.get_fen();
         self.update_repetition_table();
     }
 
     pub fn flip_horizontal(&mut self) {
         self.position.flip_horizontal();
         self.stack.clear();
+        self.starting_fen = self.get_fen();
         self.update_repetition_table();
     }
 
     #[inline]
     pub fn to_board_string(&self, use_unicode: bool) -> String {
         self.position.to_board_string(
-            self.stack.last().map_or(Default::default(), |(_, m)| *m),
+            self.stack
+                .last()
+                .map_or(Default::default(), |undo_record| undo_record.get_move()),
             use_unicode,
         )
     }
 
     #[inline]
     pub fn to_unicode_string(&self) -> String {
-        self.position
-            .to_unicode_string(self.stack.last().map_or(Default::default(), |(_, m)| *m))
+        self.position.to_unicode_string(
+            self.stack
+                .last()
+                .map_or(Default::default(), |undo_record| undo_record.get_move()),
+        )
     }
 
     pub fn result(&self) -> GameResult {
-        if self.is_other_draw() {
+        if self.get_variant_status().is_none() && self.is_other_draw() {
             return GameResult::Draw;
         }
         match self.status() {
-            BoardStatus::Checkmate => GameResult::Win(!self.turn()),
-            BoardStatus::Stalemate => GameResult::Draw,
             BoardStatus::Ongoing => GameResult::InProgress,
+            BoardStatus::Stalemate if self.get_variant().is_stalemate_win() => {
+                GameResult::Win(self.turn())
+            }
+            status => status
+                .get_winner(self.turn())
+                .map_or(GameResult::Draw, GameResult::Win),
         }
     }
 
@@ -141,10 +178,12 @@ impl Board {
 
     pub fn update_repetition_table(&mut self) {
         self.repetition_table.clear();
-        for (position, _) in &self.stack {
-            self.repetition_table.insert(position.get_hash())
+        let mut position = self.position.clone();
+        self.repetition_table.insert(position.get_hash());
+        for undo_record in self.stack.iter().rev() {
+            position.unmake_move(undo_record);
+            self.repetition_table.insert(position.get_hash());
         }
-        self.repetition_table.insert(self.get_hash())
     }
 
     #[inline]
@@ -194,26 +233,42 @@ impl Board {
         self.is_other_draw() || self.status() != BoardStatus::Ongoing
     }
 
+    /// Panics if the incremental hashes differ from the ones computed from scratch, checked only when hash verification is on.
+    fn verify_hash(&self) {
+        if !GLOBAL_TIMECAT_STATE.is_verifying_hash() {
+            return;
+        }
+        if let Err(err) = self.position.verify_hash() {
+            panic!(
+                "{err} Moves played: {}",
+                self.get_all_stack_moves().iter().join(" ")
+            );
+        }
+    }
+
     pub fn pop(&mut self) -> ValidOrNullMove {
-        let (position, valid_or_null_move) = self.stack.pop().unwrap();
+        let undo_record = self.stack.pop().unwrap();
         self.repetition_table.remove(self.get_hash());
-        self.position = position;
-        valid_or_null_move
+        self.position.unmake_move(&undo_record);
+        self.verify_hash();
+        undo_record.get_move()
     }
 
     #[inline]
     pub fn get_all_stack_moves(&self) -> Vec<ValidOrNullMove> {
-        self.stack.iter().map(|(_, m)| *m).collect_vec()
+        self.stack.iter().map(UndoRecord::get_move).collect_vec()
     }
 
     #[inline]
     pub fn get_last_stack_move(&self) -> Option<ValidOrNullMove> {
-        self.stack.last().map(|(_, m)| *m)
+        self.stack.last().map(UndoRecord::get_move)
     }
 
     #[inline]
     pub fn stack_contains_null_move(&self) -> bool {
-        self.stack.iter().any(|(_, m)| m.is_null())
+        self.stack
+            .iter()
+            .any(|undo_record| undo_record.get_move().is_null())
     }
 
     #[inline]
@@ -323,27 +378,23 @@ impl Board {
         san_string.trim().to_string()
     }
 
+    #[inline]
     pub fn get_starting_board_fen(&self) -> String {
-        if let Some((position, _)) = self.stack.first() {
-            position.get_fen()
-        } else {
-            self.get_fen()
-        }
+        self.starting_fen.clone()
     }
 
     pub fn get_pgn(&self) -> String {
         let mut pgn = String::new();
+        if self.get_variant() != Variant::Standard {
+            pgn += &format!("[Variant \"{}\"]\n", self.get_variant());
+        }
         let starting_fen = &self.get_starting_board_fen();
-        if starting_fen != STARTING_POSITION_FEN {
+        if starting_fen != self.get_variant().get_starting_fen() {
             pgn += &format!("[FEN \"{}\"]\n", starting_fen);
         }
         pgn += &Self::variation_san(
-            &Self::from_fen(starting_fen).unwrap(),
-            self.stack
-                .clone()
-                .into_iter()
-                .map(|(_, optional_m)| optional_m)
-                .collect_vec(),
+            &Self::from_fen_and_variant(starting_fen, self.get_variant()).unwrap(),
+            self.get_all_stack_moves(),
         );
         pgn
     }
@@ -391,10 +442,10 @@ impl Board {
 
 impl BoardMethodOverload<Move> for Board {
     fn push_unchecked(&mut self, move_: Move) {
-        let position_copy = self.position.clone();
-        self.position.make_move(move_);
+        let undo_record = self.position.make_move_with_undo(move_.into());
         self.repetition_table.insert(self.get_hash());
-        self.stack.push((position_copy, move_.into()));
+        self.stack.push(undo_record);
+        self.verify_hash();
     }
 
     fn push(&mut self, move_: Move) -> Result<()> {
@@ -434,10 +485,10 @@ impl BoardMethodOverload<Move> for Board {
 
 impl BoardMethodOverload<ValidOrNullMove> for Board {
     fn push_unchecked(&mut self, valid_or_null_move: ValidOrNullMove) {
-        let position_copy = self.position.clone();
-        self.position.make_move(valid_or_null_move);
+        let undo_record = self.position.make_move_with_undo(valid_or_null_move);
         self.repetition_table.insert(self.get_hash());
-        self.stack.push((position_copy, valid_or_null_move));
+        self.stack.push(undo_record);
+        self.verify_hash();
     }
 
     fn push(&mut self, valid_or_null_move: ValidOrNullMove) -> Result<()> {
@@ -486,7 +537,7 @@ impl fmt::Display for Board {
 
 impl Default for Board {
     fn default() -> Self {
-        Self::from_fen(STARTING_POSITION_FEN).unwrap()
+        Self::new()
     }
 }
 
@@ -503,6 +554,7 @@ impl From<BoardPosition> for Board {
         let mut board = Self {
             #[cfg(feature = "extras")]
             evaluator: Evaluator::new(&position),
+            starting_fen: position.get_fen(),
             position,
             stack: Vec::new(),
             repetition_table: RepetitionTable::new(),
@@ -540,15 +592,15 @@ impl<'source> FromPyObject<'source> for Board {
                 let moves = moves_py_object
                     .extract::<Vec<ValidOrNullMove>>()
                     .unwrap_or_default();
-                board.stack = states.into_iter().zip(moves).collect_vec();
-                if !board.stack.windows(2).all(|window| {
-                    let (prev_board, move_) = get_item_unchecked!(window, 0);
-                    let (new_board, _) = get_item_unchecked!(window, 1);
-                    prev_board.make_move_new(*move_) == *new_board
-                }) {
-                    board.stack.clear()
+                if let Some(starting_position) = states.first() {
+                    let mut replayed_board = Board::from(starting_position);
+                    if states.iter().zip(moves).all(|(state, move_)| {
+                        replayed_board.get_position() == state && replayed_board.push(move_).is_ok()
+                    }) && replayed_board.get_position() == board.get_position()
+                    {
+                        board = replayed_board;
+                    }
                 }
-                board.update_repetition_table();
             }
             return Ok(board);
         }
@@ -0,0 +1,219 @@
+use super::*;
+#[cfg(feature = "serde")]
+use std::io::BufWriter;
+
+/// Iterations completed by an analysis, from which it can be continued.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default)]
+pub struct SearchProgress {
+    iterations: Vec<SearchIteration>,
+    num_nodes_searched: usize,
+    time_elapsed: Duration,
+}
+
+impl SearchProgress {
+    #[inline]
+    pub fn new(
+        iterations: Vec<SearchIteration>,
+        num_nodes_searched: usize,
+        time_elapsed: Duration,
+    ) -> Self {
+        Self {
+            iterations,
+            num_nodes_searched,
+            time_elapsed,
+        }
+    }
+
+    #[inline]
+    pub fn get_iterations(&self) -> &[SearchIteration] {
+        &self.iterations
+    }
+
+    #[inline]
+    pub fn get_depth_completed(&self) -> Depth {
+        self.iterations
+            .last()
+            .map_or(0, |iteration| iteration.get_depth())
+    }
+
+    #[inline]
+    pub fn get_num_nodes_searched(&self) -> usize {
+        self.num_nodes_searched
+    }
+
+    #[inline]
+    pub fn get_time_elapsed(&self) -> Duration {
+        self.time_elapsed
+    }
+
+    #[inline]
+    pub fn into_iterations(self) -> Vec<SearchIteration> {
+        self.iterations
+    }
+}
+
+/// Snapshot of an analysis written periodically, so that it can be resumed after the engine is restarted.
+///
+/// The transposition table is written next to the checkpoint in the hash file format, with the extension `.hash` appended to the path.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Checkpoint {
+    board: Board,
+    move_sorter: MoveSorter,
+    progress: SearchProgress,
+}
+
+impl Checkpoint {
+    #[inline]
+    pub fn new(board: Board, move_sorter: MoveSorter, progress: SearchProgress) -> Self {
+        Self {
+            board,
+            move_sorter,
+            progress,
+        }
+    }
+
+    #[inline]
+    pub fn get_board(&self) -> &Board {
+        &self.board
+    }
+
+    #[inline]
+    pub fn get_move_sorter(&self) -> &MoveSorter {
+        &self.move_sorter
+    }
+
+    #[inline]
+    pub fn get_progress(&self) -> &SearchProgress {
+        &self.progress
+    }
+
+    #[inline]
+    pub fn into_parts(self) -> (Board, MoveSorter, SearchProgress) {
+        (self.board, self.move_sorter, self.progress)
+    }
+
+    pub fn get_hash_file_path(path: impl AsRef<Path>) -> PathBuf {
+        let mut path = path.as_ref().as_os_str().to_owned();
+        path.push(".hash");
+        path.into()
+    }
+
+    /// Writes the file under a temporary name first, so that a checkpoint interrupted while saving does not replace the last one.
+    #[cfg(feature = "serde")]
+    fn write_atomically(path: &Path, write: impl FnOnce(&Path) -> Result<()>) -> Result<()> {
+        let mut temporary_path = path.as_os_str().to_owned();
+        temporary_path.push(".tmp");
+        let temporary_path = PathBuf::from(temporary_path);
+        write(&temporary_path)?;
+        fs::rename(temporary_path, path)?;
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn save_to_file(
+        &self,
+        path: impl AsRef<Path>,
+        transposition_table: &TranspositionTable,
+    ) -> Result<()> {
+        let path = path.as_ref();
+        Self::write_atomically(&Self::get_hash_file_path(path), |path| {
+            transposition_table.save_to_file(path)
+        })?;
+        Self::write_atomically(path, |path| {
+            let mut writer = BufWriter::new(fs::File::create(path)?);
+            serde_json::to_writer(&mut writer, self).map_err(|error| error.to_string())?;
+            writer.flush()?;
+            Ok(())
+        })
+    }
+
+    #[cfg(not(feature = "serde"))]
+    #[expect(unused_variables)]
+    pub fn save_to_file(
+        &self,
+        path: impl AsRef<Path>,
+        transposition_table: &TranspositionTable,
+    ) -> Result<()> {
+        Err(TimecatError::FeatureNotEnabled {
+            s: "serde".to_string(),
+        })
+    }
+
+    /// Reads the checkpoint saved by [`Self::save_to_file`], loading its transposition table into `transposition_table`.
+    #[cfg(feature = "serde")]
+    pub fn load_from_file(
+        path: impl AsRef<Path>,
+        transposition_table: &TranspositionTable,
+    ) -> Result<Self> {
+        let path = path.as_ref();
+        let checkpoint: Self = serde_json::from_reader(BufReader::new(fs::File::open(path)?))
+            .map_err(|error| TimecatError::InvalidCheckpointFile {
+                path: path.display().to_string(),
+                err_msg: error.to_string(),
+            })?;
+        transposition_table.load_from_file(Self::get_hash_file_path(path))?;
+        Ok(checkpoint)
+    }
+
+    #[cfg(not(feature = "serde"))]
+    #[expect(unused_variables)]
+    pub fn load_from_file(
+        path: impl AsRef<Path>,
+        transposition_table: &TranspositionTable,
+    ) -> Result<Self> {
+        Err(TimecatError::FeatureNotEnabled {
+            s: "serde".to_string(),
+        })
+    }
+}
+
+/// Writes the checkpoints on a background thread, so that the search does not wait for the files to be written.
+///
+/// A clone does not wait for the checkpoint being written by the original.
+#[derive(Debug, Default)]
+pub struct CheckpointWriter {
+    join_handle: Option<thread::JoinHandle<()>>,
+}
+
+impl CheckpointWriter {
+    #[inline]
+    pub fn is_writing(&self) -> bool {
+        self.join_handle
+            .as_ref()
+            .is_some_and(|join_handle| !join_handle.is_finished())
+    }
+
+    /// Starts writing the checkpoint, unless the previous one is still being written. Returns whether it was started.
+    pub fn write(
+        &mut self,
+        checkpoint: Checkpoint,
+        path: String,
+        transposition_table: Arc<TranspositionTable>,
+    ) -> bool {
+        if self.is_writing() {
+            return false;
+        }
+        self.wait();
+        self.join_handle = Some(thread::spawn(move || {
+            if let Err(err) = checkpoint.save_to_file(path, &transposition_table) {
+                println_info("Checkpoint Error", err);
+            }
+        }));
+        true
+    }
+
+    /// Waits for the checkpoint being written, if any.
+    pub fn wait(&mut self) {
+        if let Some(join_handle) = self.join_handle.take() {
+            join_handle.join().unwrap();
+        }
+    }
+}
+
+impl Clone for CheckpointWriter {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
@@ -59,6 +59,16 @@ impl CastleRights {
         )
     }
 
+    /// Files the king and the rook land on after castling on this side, which are the same in chess960.
+    #[inline]
+    pub fn get_king_and_rook_dest_files(self) -> (File, File) {
+        match self {
+            Self::KingSide => (File::G, File::F),
+            Self::QueenSide => (File::C, File::D),
+            _ => panic!("Castling destination is defined only for one side, got {self:?}"),
+        }
+    }
+
     /// Remove castle rights, and return a new `CastleRights`.
     #[inline]
     pub fn remove(self, remove: Self) -> Self {
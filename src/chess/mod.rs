@@ -2,6 +2,7 @@ pub mod castle;
 pub mod move_generator;
 pub mod position;
 pub mod position_builder;
+pub mod variant;
 pub mod zobrist;
 
 use super::*;
@@ -9,4 +10,5 @@ pub use castle::*;
 pub use move_generator::*;
 pub use position::*;
 pub use position_builder::*;
+pub use variant::*;
 pub use zobrist::*;
@@ -4,15 +4,21 @@ trait PieceMoves {
     fn is(piece: PieceType) -> bool;
     fn into_piece() -> PieceType;
     fn pseudo_legals(src: Square, color: Color, occupied: BitBoard, mask: BitBoard) -> BitBoard;
-    fn legals<T>(move_list: &mut MoveList, position: &BoardPosition, mask: BitBoard)
-    where
+    /// Adds the legal moves of the pieces on `from_mask` to the squares on `to_mask`.
+    fn legals<T>(
+        move_list: &mut MoveList,
+        position: &BoardPosition,
+        from_mask: BitBoard,
+        to_mask: BitBoard,
+    ) where
         T: CheckMoves,
     {
+        let mask = to_mask & !position.self_occupied();
         let occupied = position.occupied();
         let color = position.turn();
         let ksq = position.get_king_square(color);
 
-        let pieces = position.get_colored_piece_mask(Self::into_piece(), color);
+        let pieces = position.get_colored_piece_mask(Self::into_piece(), color) & from_mask;
         let pinned = position.pinned();
         let checkers = position.get_checkers();
 
@@ -126,15 +132,20 @@ impl PieceMoves for PawnMoves {
     }
 
     #[inline]
-    fn legals<T>(move_list: &mut MoveList, position: &BoardPosition, mask: BitBoard)
-    where
+    fn legals<T>(
+        move_list: &mut MoveList,
+        position: &BoardPosition,
+        from_mask: BitBoard,
+        to_mask: BitBoard,
+    ) where
         T: CheckMoves,
     {
+        let mask = to_mask & !position.self_occupied();
         let occupied = position.occupied();
         let color = position.turn();
         let ksq = position.get_king_square(color);
 
-        let pieces = position.get_colored_piece_mask(Self::into_piece(), color);
+        let pieces = position.get_colored_piece_mask(Self::into_piece(), color) & from_mask;
         let pinned = position.pinned();
         let checkers = position.get_checkers();
 
@@ -174,7 +185,7 @@ impl PieceMoves for PawnMoves {
             }
         }
 
-        if let Some(dest) = position.ep_square() {
+        if let Some(dest) = position.ep_square().filter(|&dest| mask.contains(dest)) {
             let dest_rank = dest.get_rank();
             let rank_bb = if dest_rank.to_int() > 3 {
                 dest_rank.wrapping_down().to_bitboard()
@@ -227,15 +238,20 @@ impl PieceMoves for KnightMoves {
     }
 
     #[inline]
-    fn legals<T>(move_list: &mut MoveList, position: &BoardPosition, mask: BitBoard)
-    where
+    fn legals<T>(
+        move_list: &mut MoveList,
+        position: &BoardPosition,
+        from_mask: BitBoard,
+        to_mask: BitBoard,
+    ) where
         T: CheckMoves,
     {
+        let mask = to_mask & !position.self_occupied();
         let occupied = position.occupied();
         let color = position.turn();
         let ksq = position.get_king_square(color);
 
-        let pieces = position.get_colored_piece_mask(Self::into_piece(), color);
+        let pieces = position.get_colored_piece_mask(Self::into_piece(), color) & from_mask;
         let pinned = position.pinned();
         let checkers = position.get_checkers();
 
@@ -307,7 +323,12 @@ impl KingMoves {
     fn legal_king_move(position: &BoardPosition, dest: Square) -> bool {
         let occupied = position.occupied() ^ position.get_colored_piece_mask(King, position.turn())
             | dest.to_bitboard();
+        Self::is_safe(position, dest, occupied)
+    }
 
+    /// Whether the square is not attacked by the opponent when the board is occupied by `occupied`.
+    #[inline]
+    fn is_safe(position: &BoardPosition, dest: Square, occupied: BitBoard) -> bool {
         let rooks = (position.get_piece_mask(Rook) ^ position.get_piece_mask(Queen))
             & position.opponent_occupied();
 
@@ -333,6 +354,57 @@ impl KingMoves {
     }
 }
 
+impl KingMoves {
+    /// Destination of the castling move on the given side if it is legal, assuming that we are not in check.
+    ///
+    /// We can castle iff:
+    ///  * the `BoardPosition` structure says we can.
+    ///  * the squares the king and the rook pass through and land on are empty, apart from
+    ///    the king and the rook themselves.
+    ///  * no enemy pieces are attacking the squares the king passes through, with the rook still in
+    ///    place, or the square it lands on, with the rook already moved.
+    fn legal_castling_dest(position: &BoardPosition, side: CastleRights) -> Option<Square> {
+        let color = position.turn();
+        if position.my_castle_rights().to_index() & side.to_index() == 0 {
+            return None;
+        }
+        let ksq = position.get_king_square(color);
+        let rook_square = position.get_castling_rook_square(color, side);
+        let (king_dest_file, rook_dest_file) = side.get_king_and_rook_dest_files();
+        let king_dest = Square::from_rank_and_file(color.to_my_backrank(), king_dest_file);
+        let rook_dest = Square::from_rank_and_file(color.to_my_backrank(), rook_dest_file);
+
+        let occupied_without_king = position.occupied() ^ ksq.to_bitboard();
+        let occupied = occupied_without_king ^ rook_square.to_bitboard();
+        let king_path = ksq.between(king_dest) | king_dest.to_bitboard();
+        let rook_path = rook_square.between(rook_dest) | rook_dest.to_bitboard();
+        // In Atomic the squares touching the opponent king cannot be attacked.
+        let safe_squares = if position.get_variant() == Variant::Atomic {
+            position
+                .get_colored_piece_mask(King, !color)
+                .to_square_unchecked()
+                .get_king_moves()
+        } else {
+            BitBoard::EMPTY
+        };
+        if !((king_path | rook_path) & occupied).is_empty()
+            || !(ksq.between(king_dest) & !safe_squares)
+                .into_iter()
+                .all(|square| Self::is_safe(position, square, occupied_without_king))
+            || !(safe_squares.contains(king_dest)
+                || Self::is_safe(position, king_dest, occupied | rook_dest.to_bitboard()))
+        {
+            return None;
+        }
+
+        Some(if position.is_chess960() {
+            rook_square
+        } else {
+            king_dest
+        })
+    }
+}
+
 impl PieceMoves for KingMoves {
     fn is(piece: PieceType) -> bool {
         piece == King
@@ -348,13 +420,21 @@ impl PieceMoves for KingMoves {
     }
 
     #[inline]
-    fn legals<T>(move_list: &mut MoveList, position: &BoardPosition, mask: BitBoard)
-    where
+    fn legals<T>(
+        move_list: &mut MoveList,
+        position: &BoardPosition,
+        from_mask: BitBoard,
+        to_mask: BitBoard,
+    ) where
         T: CheckMoves,
     {
+        let mask = to_mask & !position.self_occupied();
         let occupied = position.occupied();
         let color = position.turn();
         let ksq = position.get_king_square(color);
+        if !from_mask.contains(ksq) {
+            return;
+        }
 
         let mut square_and_bitboard_array = Self::pseudo_legals(ksq, color, occupied, mask);
 
@@ -366,35 +446,10 @@ impl PieceMoves for KingMoves {
         }
 
         // If we are not in check, we may be able to castle.
-        // We can do so iff:
-        //  * the `BoardPosition` structure says we can.
-        //  * the squares between my king and my rook are empty.
-        //  * no enemy pieces are attacking the squares between the king, and the kings
-        //    destination square.
-        //  ** This is determined by going to the left or right, and calling
-        //     'legal_king_move' for that square.
         if !T::IN_CHECK {
-            if position.my_castle_rights().has_kingside()
-                && (occupied & position.my_castle_rights().kingside_squares(color)).is_empty()
-            {
-                let middle = ksq.wrapping_right();
-                let right = middle.wrapping_right();
-                if KingMoves::legal_king_move(position, middle)
-                    && KingMoves::legal_king_move(position, right)
-                {
-                    square_and_bitboard_array ^= right.to_bitboard();
-                }
-            }
-
-            if position.my_castle_rights().has_queenside()
-                && (occupied & position.my_castle_rights().queenside_squares(color)).is_empty()
-            {
-                let middle = ksq.wrapping_left();
-                let left = middle.wrapping_left();
-                if KingMoves::legal_king_move(position, middle)
-                    && KingMoves::legal_king_move(position, left)
-                {
-                    square_and_bitboard_array ^= left.to_bitboard();
+            for side in [CastleRights::KingSide, CastleRights::QueenSide] {
+                if let Some(dest) = Self::legal_castling_dest(position, side) {
+                    square_and_bitboard_array |= dest.to_bitboard() & to_mask;
                 }
             }
         }
@@ -416,6 +471,7 @@ struct SquareAndBitBoard {
     square: Square,
     bitboard: BitBoard,
     promotion: bool,
+    drop: Option<PieceType>,
 }
 
 impl SquareAndBitBoard {
@@ -424,16 +480,227 @@ impl SquareAndBitBoard {
             square,
             bitboard: bb,
             promotion,
+            drop: None,
+        }
+    }
+
+    /// The square is not used for the drops.
+    fn new_drop(piece_type: PieceType, bb: BitBoard) -> SquareAndBitBoard {
+        SquareAndBitBoard {
+            square: Square::A1,
+            bitboard: bb,
+            promotion: false,
+            drop: Some(piece_type),
+        }
+    }
+
+    /// Drops have no source square, so they are only included when the source squares are not masked.
+    #[inline]
+    fn is_from(&self, from_bitboard_iterator_mask: BitBoard) -> bool {
+        if self.drop.is_some() {
+            from_bitboard_iterator_mask == BB_ALL
+        } else {
+            from_bitboard_iterator_mask.contains(self.square)
+        }
+    }
+
+    #[inline]
+    fn get_move(&self, dest: Square, promotion: Option<PieceType>) -> Move {
+        match self.drop {
+            Some(piece_type) => Move::new_drop(piece_type, dest),
+            None => Move::new_unchecked(self.square, dest, promotion),
+        }
+    }
+}
+
+struct DropMoves;
+
+impl DropMoves {
+    /// Drops are possible on the empty squares, or only between the king and the checker when in check.
+    fn legals(move_list: &mut MoveList, position: &BoardPosition, to_mask: BitBoard) {
+        let color = position.turn();
+        let checkers = position.get_checkers();
+        let targets = to_mask
+            & match checkers.popcnt() {
+                0 => !position.occupied(),
+                1 => checkers
+                    .to_square_unchecked()
+                    .between(position.get_king_square(color)),
+                _ => return,
+            };
+        if targets.is_empty() {
+            return;
+        }
+        for piece_type in const { [Pawn, Knight, Bishop, Rook, Queen] } {
+            if position.get_pocket_count(color, piece_type) == 0 {
+                continue;
+            }
+            let targets = if piece_type == Pawn {
+                targets & !BB_BACKRANKS
+            } else {
+                targets
+            };
+            if !targets.is_empty() {
+                unsafe {
+                    move_list.push_unchecked(SquareAndBitBoard::new_drop(piece_type, targets));
+                }
+            }
+        }
+    }
+}
+
+struct AtomicMoves;
+
+impl AtomicMoves {
+    /// Whether the move keeps the king of the side to move on the board and out of check, unless it
+    /// explodes the opponent king.
+    fn is_legal_after_explosion(position: &BoardPosition, move_: Move) -> bool {
+        let color = position.turn();
+        let new_position = position.make_move_new(move_);
+        let Some(ksq) = new_position.get_colored_piece_mask(King, color).to_square() else {
+            return false;
+        };
+        let their_king = new_position.get_colored_piece_mask(King, !color);
+        their_king.is_empty()
+            || !(ksq.get_king_moves() & their_king).is_empty()
+            || (new_position.get_attackers_mask(ksq, !color) & !their_king).is_empty()
+    }
+
+    /// Pins do not work as usual in Atomic, since capturing the pinning piece may explode it, so each
+    /// pseudo legal move is made to check whether it is legal.
+    fn legals(move_list: &mut MoveList, position: &BoardPosition) {
+        let occupied = position.occupied();
+        let color = position.turn();
+        let mask = !position.self_occupied();
+
+        for (piece_type, src) in ALL_PIECE_TYPES.into_iter().flat_map(|piece_type| {
+            position
+                .get_colored_piece_mask(piece_type, color)
+                .map(move |src| (piece_type, src))
+        }) {
+            let mut square_and_bitboard_array = match piece_type {
+                Pawn => {
+                    PawnMoves::pseudo_legals(src, color, occupied, mask)
+                        | position.ep_square().map_or(BitBoard::EMPTY, |ep_square| {
+                            src.get_pawn_attacks(color, ep_square.to_bitboard())
+                        })
+                }
+                Knight => KnightMoves::pseudo_legals(src, color, occupied, mask),
+                Bishop => BishopMoves::pseudo_legals(src, color, occupied, mask),
+                Rook => RookMoves::pseudo_legals(src, color, occupied, mask),
+                Queen => QueenMoves::pseudo_legals(src, color, occupied, mask),
+                // The king cannot capture, as it would explode itself.
+                King => {
+                    let mut square_and_bitboard_array =
+                        KingMoves::pseudo_legals(src, color, occupied, !occupied);
+                    if position.get_checkers().is_empty() {
+                        for side in [CastleRights::KingSide, CastleRights::QueenSide] {
+                            if let Some(dest) = KingMoves::legal_castling_dest(position, side) {
+                                square_and_bitboard_array |= dest.to_bitboard();
+                            }
+                        }
+                    }
+                    square_and_bitboard_array
+                }
+            };
+            let promotion = piece_type == Pawn && src.get_rank() == color.to_seventh_rank();
+            let copy = square_and_bitboard_array;
+            for dest in copy {
+                let move_ = Move::new_unchecked(src, dest, promotion.then_some(Queen));
+                if !Self::is_legal_after_explosion(position, move_) {
+                    square_and_bitboard_array ^= dest.to_bitboard();
+                }
+            }
+            if !square_and_bitboard_array.is_empty() {
+                unsafe {
+                    move_list.push_unchecked(SquareAndBitBoard::new(
+                        src,
+                        square_and_bitboard_array,
+                        promotion,
+                    ));
+                }
+            }
         }
     }
 }
 
-type MoveList = ArrayVec<SquareAndBitBoard, 18>;
+struct KinglessMoves;
+
+impl KinglessMoves {
+    /// Without a king that can be checked, as in Antichess or for white in Horde, all the pseudo legal
+    /// moves to the squares in the mask are legal.
+    fn legals(move_list: &mut MoveList, position: &BoardPosition, mask: BitBoard) {
+        let occupied = position.occupied();
+        let color = position.turn();
+
+        for piece_type in ALL_PIECE_TYPES {
+            for src in position.get_colored_piece_mask(piece_type, color) {
+                let square_and_bitboard_array = match piece_type {
+                    Pawn => {
+                        let mut square_and_bitboard_array =
+                            PawnMoves::pseudo_legals(src, color, occupied, mask);
+                        // In Horde the pawns on the first rank can also move two squares forward.
+                        if position.get_variant() == Variant::Horde
+                            && src.get_rank() == color.to_my_backrank()
+                        {
+                            let single_push = src.to_bitboard().shift_forward(color) & !occupied;
+                            square_and_bitboard_array |=
+                                single_push.shift_forward(color) & !occupied & mask;
+                        }
+                        // The en passant capture is only included if the captured pawn is in the mask.
+                        if let Some(ep_square) = position
+                            .ep_square()
+                            .filter(|ep_square| mask.contains(ep_square.wrapping_backward(color)))
+                        {
+                            square_and_bitboard_array |=
+                                src.get_pawn_attacks(color, ep_square.to_bitboard());
+                        }
+                        square_and_bitboard_array
+                    }
+                    Knight => KnightMoves::pseudo_legals(src, color, occupied, mask),
+                    Bishop => BishopMoves::pseudo_legals(src, color, occupied, mask),
+                    Rook => RookMoves::pseudo_legals(src, color, occupied, mask),
+                    Queen => QueenMoves::pseudo_legals(src, color, occupied, mask),
+                    King => KingMoves::pseudo_legals(src, color, occupied, mask),
+                };
+                if !square_and_bitboard_array.is_empty() {
+                    unsafe {
+                        move_list.push_unchecked(SquareAndBitBoard::new(
+                            src,
+                            square_and_bitboard_array,
+                            piece_type == Pawn && src.get_rank() == color.to_seventh_rank(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct RacingKingsMoves;
+
+impl RacingKingsMoves {
+    /// Removes the moves giving check, which are not allowed in Racing Kings.
+    fn remove_checks(move_list: &mut MoveList, position: &BoardPosition) {
+        for square_and_bitboard in move_list.iter_mut() {
+            for dest in square_and_bitboard.bitboard {
+                if position.gives_check(square_and_bitboard.get_move(dest, None)) {
+                    square_and_bitboard.bitboard ^= dest.to_bitboard();
+                }
+            }
+        }
+        move_list.retain(|square_and_bitboard| !square_and_bitboard.bitboard.is_empty());
+    }
+}
+
+// The white horde can have up to 36 pieces.
+type MoveList = ArrayVec<SquareAndBitBoard, 36>;
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub struct MoveGenerator {
     square_and_bitboard_array: MoveList,
+    variant: Variant,
     promotion_index: usize,
     from_bitboard_iterator_mask: BitBoard,
     to_bitboard_iterator_mask: BitBoard,
@@ -444,34 +711,100 @@ pub struct MoveGenerator {
 impl MoveGenerator {
     #[inline]
     fn enumerate_moves(position: &BoardPosition) -> MoveList {
+        Self::enumerate_masked_moves(position, BB_ALL, BB_ALL)
+    }
+
+    /// Generates the legal moves from the squares on `from_mask` to the squares on `to_mask`. The
+    /// variants needing full generation ignore the masks, so the moves still have to be filtered by the
+    /// iterator masks.
+    #[inline]
+    fn enumerate_masked_moves(
+        position: &BoardPosition,
+        from_mask: BitBoard,
+        to_mask: BitBoard,
+    ) -> MoveList {
         let checkers = position.get_checkers();
         let mask = !position.self_occupied();
         let mut move_list = ArrayVec::new();
 
+        if position.get_variant_status().is_some() {
+            return move_list;
+        }
+
+        match position.get_variant() {
+            Variant::Atomic => {
+                AtomicMoves::legals(&mut move_list, position);
+                return move_list;
+            }
+            // Captures are compulsory in Antichess.
+            Variant::Antichess => {
+                KinglessMoves::legals(&mut move_list, position, position.opponent_occupied());
+                if move_list.is_empty() {
+                    KinglessMoves::legals(&mut move_list, position, !position.occupied());
+                }
+                return move_list;
+            }
+            Variant::Horde
+                if position
+                    .get_colored_piece_mask(King, position.turn())
+                    .is_empty() =>
+            {
+                KinglessMoves::legals(&mut move_list, position, mask);
+                return move_list;
+            }
+            _ => (),
+        }
+
         if checkers.is_empty() {
-            PawnMoves::legals::<NotInCheckMoves>(&mut move_list, position, mask);
-            KnightMoves::legals::<NotInCheckMoves>(&mut move_list, position, mask);
-            BishopMoves::legals::<NotInCheckMoves>(&mut move_list, position, mask);
-            RookMoves::legals::<NotInCheckMoves>(&mut move_list, position, mask);
-            QueenMoves::legals::<NotInCheckMoves>(&mut move_list, position, mask);
-            KingMoves::legals::<NotInCheckMoves>(&mut move_list, position, mask);
+            PawnMoves::legals::<NotInCheckMoves>(&mut move_list, position, from_mask, to_mask);
+            KnightMoves::legals::<NotInCheckMoves>(&mut move_list, position, from_mask, to_mask);
+            BishopMoves::legals::<NotInCheckMoves>(&mut move_list, position, from_mask, to_mask);
+            RookMoves::legals::<NotInCheckMoves>(&mut move_list, position, from_mask, to_mask);
+            QueenMoves::legals::<NotInCheckMoves>(&mut move_list, position, from_mask, to_mask);
+            KingMoves::legals::<NotInCheckMoves>(&mut move_list, position, from_mask, to_mask);
         } else if checkers.popcnt() == 1 {
-            PawnMoves::legals::<InCheckMoves>(&mut move_list, position, mask);
-            KnightMoves::legals::<InCheckMoves>(&mut move_list, position, mask);
-            BishopMoves::legals::<InCheckMoves>(&mut move_list, position, mask);
-            RookMoves::legals::<InCheckMoves>(&mut move_list, position, mask);
-            QueenMoves::legals::<InCheckMoves>(&mut move_list, position, mask);
-            KingMoves::legals::<InCheckMoves>(&mut move_list, position, mask);
+            PawnMoves::legals::<InCheckMoves>(&mut move_list, position, from_mask, to_mask);
+            KnightMoves::legals::<InCheckMoves>(&mut move_list, position, from_mask, to_mask);
+            BishopMoves::legals::<InCheckMoves>(&mut move_list, position, from_mask, to_mask);
+            RookMoves::legals::<InCheckMoves>(&mut move_list, position, from_mask, to_mask);
+            QueenMoves::legals::<InCheckMoves>(&mut move_list, position, from_mask, to_mask);
+            KingMoves::legals::<InCheckMoves>(&mut move_list, position, from_mask, to_mask);
         } else {
-            KingMoves::legals::<InCheckMoves>(&mut move_list, position, mask);
+            KingMoves::legals::<InCheckMoves>(&mut move_list, position, from_mask, to_mask);
+        }
+
+        match position.get_variant() {
+            // Drops have no source square, so they are only generated when the sources are not masked.
+            Variant::Crazyhouse if from_mask == BB_ALL => {
+                DropMoves::legals(&mut move_list, position, to_mask)
+            }
+            Variant::RacingKings => RacingKingsMoves::remove_checks(&mut move_list, position),
+            _ => (),
         }
 
         move_list
     }
 
+    /// Whether the legal moves can only be found by generating all of them, as the shortcuts used for
+    /// the standard rules do not apply.
+    #[inline]
+    fn needs_full_generation(position: &BoardPosition) -> bool {
+        matches!(
+            position.get_variant(),
+            Variant::Atomic | Variant::Antichess | Variant::RacingKings
+        ) || position
+            .get_colored_piece_mask(King, position.turn())
+            .is_empty()
+    }
+
     pub fn has_legal_moves(position: &BoardPosition) -> bool {
+        if position.get_variant_status().is_some() {
+            return false;
+        }
+        if Self::needs_full_generation(position) {
+            return !Self::enumerate_moves(position).is_empty();
+        }
         let checkers = position.get_checkers();
-        let mask = !position.occupied_color(position.turn());
         let mut move_list = ArrayVec::new();
 
         let legal_functions = if checkers == BitBoard::EMPTY {
@@ -493,12 +826,19 @@ impl MoveGenerator {
                 KingMoves::legals::<InCheckMoves>,
             ]
         } else {
-            KingMoves::legals::<InCheckMoves>(&mut move_list, position, mask);
+            KingMoves::legals::<InCheckMoves>(&mut move_list, position, BB_ALL, BB_ALL);
             return !move_list.is_empty();
         };
 
+        if position.get_variant() == Variant::Crazyhouse {
+            DropMoves::legals(&mut move_list, position, BB_ALL);
+            if !move_list.is_empty() {
+                return true;
+            }
+        }
+
         for function in legal_functions {
-            function(&mut move_list, position, mask);
+            function(&mut move_list, position, BB_ALL, BB_ALL);
             if !move_list.is_empty() {
                 return true;
             }
@@ -512,6 +852,7 @@ impl MoveGenerator {
     pub fn new_legal(position: &BoardPosition) -> MoveGenerator {
         MoveGenerator {
             square_and_bitboard_array: MoveGenerator::enumerate_moves(position),
+            variant: position.get_variant(),
             promotion_index: 0,
             from_bitboard_iterator_mask: BB_ALL,
             to_bitboard_iterator_mask: BB_ALL,
@@ -520,6 +861,31 @@ impl MoveGenerator {
         }
     }
 
+    /// Generates only the legal moves from the squares on `from_bitboard` to the squares on
+    /// `to_bitboard`, which is cheaper than masking all the legal moves.
+    #[inline]
+    pub fn new_masked_legal(
+        position: &BoardPosition,
+        from_bitboard: BitBoard,
+        to_bitboard: BitBoard,
+    ) -> MoveGenerator {
+        let mut move_generator = MoveGenerator {
+            square_and_bitboard_array: MoveGenerator::enumerate_masked_moves(
+                position,
+                from_bitboard,
+                to_bitboard,
+            ),
+            variant: position.get_variant(),
+            promotion_index: 0,
+            from_bitboard_iterator_mask: from_bitboard,
+            to_bitboard_iterator_mask: to_bitboard,
+            index: 0,
+            last_index: usize::MAX,
+        };
+        move_generator.reorganize_square_and_bitboard_array();
+        move_generator
+    }
+
     /// The iterator portion of this struct relies on the invariant that
     /// the bitboards at the beginning of the square_and_bitboard_array[] array are the only
     /// ones used.  As a result, we must partition the list such that the
@@ -559,7 +925,9 @@ impl MoveGenerator {
     pub fn remove_move(&mut self, move_: Move) -> bool {
         let mut square_removed = false;
         for square_and_bitboard in self.square_and_bitboard_array.iter_mut() {
-            if square_and_bitboard.square == move_.get_source() {
+            if square_and_bitboard.drop == move_.get_drop()
+                && (move_.is_drop() || square_and_bitboard.square == move_.get_source())
+            {
                 square_and_bitboard.bitboard &= !move_.get_dest().to_bitboard();
                 square_removed = true;
             }
@@ -639,6 +1007,17 @@ impl MoveGenerator {
                 }
             }
         }
+        // Drops have no source square, so they are not included in any of the piece masks.
+        for x in position
+            .generate_legal_moves()
+            .filter(|move_| move_.is_drop())
+        {
+            result += if depth == 1 {
+                1
+            } else {
+                MoveGenerator::perft_test(&position.make_move_new(x), depth - 1)
+            };
+        }
         result
     }
 
@@ -646,8 +1025,7 @@ impl MoveGenerator {
         self.square_and_bitboard_array
             .iter()
             .filter(|square_and_bitboard| {
-                self.from_bitboard_iterator_mask
-                    .contains(square_and_bitboard.square)
+                square_and_bitboard.is_from(self.from_bitboard_iterator_mask)
             })
             .take_while(|square_and_bitboard| {
                 !(square_and_bitboard.bitboard & self.to_bitboard_iterator_mask).is_empty()
@@ -659,11 +1037,8 @@ impl MoveGenerator {
                     const { &[None] }
                 };
                 promotion_pieces.iter().flat_map(move |&promotion| {
-                    (square_and_bitboard.bitboard & self.to_bitboard_iterator_mask).map(
-                        move |dest| {
-                            Move::new_unchecked(square_and_bitboard.square, dest, promotion)
-                        },
-                    )
+                    (square_and_bitboard.bitboard & self.to_bitboard_iterator_mask)
+                        .map(move |dest| square_and_bitboard.get_move(dest, promotion))
                 })
             })
     }
@@ -673,11 +1048,14 @@ impl MoveGenerator {
         self.square_and_bitboard_array
             .iter()
             .any(|square_and_bitboard| {
-                square_and_bitboard.square == move_.get_source()
+                square_and_bitboard.drop == move_.get_drop()
+                    && (square_and_bitboard.drop.is_some()
+                        || square_and_bitboard.square == move_.get_source())
                     && square_and_bitboard.bitboard.contains(move_.get_dest())
                     && if square_and_bitboard.promotion {
-                        const { [Some(Knight), Some(Bishop), Some(Rook), Some(Queen)] }
-                            .contains(&move_.get_promotion())
+                        move_.get_promotion().is_some_and(|promotion| {
+                            self.variant.get_promotion_pieces().contains(&promotion)
+                        })
                     } else {
                         move_.get_promotion().is_none()
                     }
@@ -686,30 +1064,32 @@ impl MoveGenerator {
 
     #[inline]
     pub fn is_legal(position: &BoardPosition, move_: &Move) -> bool {
+        if position.get_variant_status().is_some() {
+            return false;
+        }
         // TODO: Scope of improvement
-        let Some(piece_type) = position.get_piece_type_at(move_.get_source()) else {
+        let Some(piece_type) = position.get_moving_piece_type(*move_) else {
             return false;
         };
+        if Self::needs_full_generation(position) {
+            return position.generate_legal_moves().contains(move_);
+        }
         let possibly_legal = match piece_type {
             Pawn => {
                 if move_.get_source().get_file() != move_.get_dest().get_file()
                     && position.get_piece_type_at(move_.get_dest()).is_none()
                 {
                     // en-passant
-                    PawnMoves::legal_ep_move(position, move_.get_source(), move_.get_dest())
+                    position.ep_square() == Some(move_.get_dest())
+                        && PawnMoves::legal_ep_move(position, move_.get_source(), move_.get_dest())
                 } else {
                     true
                 }
             }
             King => {
-                let bb = move_.get_source().between(move_.get_dest());
-                if bb.popcnt() == 1 {
-                    // castles
-                    if !KingMoves::legal_king_move(position, bb.to_square_unchecked()) {
-                        false
-                    } else {
-                        KingMoves::legal_king_move(position, move_.get_dest())
-                    }
+                if position.is_castling(*move_) {
+                    // castles, the squares passed through are checked while generating the moves
+                    true
                 } else {
                     KingMoves::legal_king_move(position, move_.get_dest())
                 }
@@ -719,7 +1099,13 @@ impl MoveGenerator {
         if !possibly_legal {
             return false;
         }
-        position.generate_legal_moves().contains(move_)
+        let from_bitboard = if move_.is_drop() {
+            BB_ALL
+        } else {
+            move_.get_source().to_bitboard()
+        };
+        Self::new_masked_legal(position, from_bitboard, move_.get_dest().to_bitboard())
+            .contains(move_)
     }
 }
 
@@ -729,16 +1115,14 @@ impl ExactSizeIterator for MoveGenerator {
         for square_and_bitboard in &self.square_and_bitboard_array {
             let bitboard_and_to_bitboard_iterator_mask =
                 square_and_bitboard.bitboard & self.to_bitboard_iterator_mask;
-            if !self
-                .from_bitboard_iterator_mask
-                .contains(square_and_bitboard.square)
+            if !square_and_bitboard.is_from(self.from_bitboard_iterator_mask)
                 || bitboard_and_to_bitboard_iterator_mask.is_empty()
             {
                 break;
             }
             if square_and_bitboard.promotion {
                 result += (bitboard_and_to_bitboard_iterator_mask.popcnt() as usize)
-                    * NUM_PROMOTION_PIECES;
+                    * self.variant.get_promotion_pieces().len();
             } else {
                 result += bitboard_and_to_bitboard_iterator_mask.popcnt() as usize;
             }
@@ -763,9 +1147,9 @@ impl Iterator for MoveGenerator {
             return None;
         }
         if self.index != self.last_index {
-            while !self.from_bitboard_iterator_mask.contains(
-                get_item_unchecked_mut!(self.square_and_bitboard_array, self.index).square,
-            ) {
+            while !get_item_unchecked!(self.square_and_bitboard_array, self.index)
+                .is_from(self.from_bitboard_iterator_mask)
+            {
                 self.index += 1;
                 if self.index >= square_and_bitboard_array_len {
                     return None;
@@ -776,9 +1160,7 @@ impl Iterator for MoveGenerator {
         let square_and_bitboard =
             get_item_unchecked_mut!(self.square_and_bitboard_array, self.index);
 
-        if !self
-            .from_bitboard_iterator_mask
-            .contains(square_and_bitboard.square)
+        if !square_and_bitboard.is_from(self.from_bitboard_iterator_mask)
             || (square_and_bitboard.bitboard & self.to_bitboard_iterator_mask).is_empty()
         {
             // are we done?
@@ -791,10 +1173,13 @@ impl Iterator for MoveGenerator {
             let result = Move::new_unchecked(
                 square_and_bitboard.square,
                 dest,
-                Some(*get_item_unchecked!(PROMOTION_PIECES, self.promotion_index)),
+                Some(*get_item_unchecked!(
+                    self.variant.get_promotion_pieces(),
+                    self.promotion_index
+                )),
             );
             self.promotion_index += 1;
-            if self.promotion_index >= NUM_PROMOTION_PIECES {
+            if self.promotion_index >= self.variant.get_promotion_pieces().len() {
                 square_and_bitboard.bitboard ^= dest.to_bitboard();
                 self.promotion_index = 0;
                 if (square_and_bitboard.bitboard & self.to_bitboard_iterator_mask).is_empty() {
@@ -811,7 +1196,7 @@ impl Iterator for MoveGenerator {
             if (square_and_bitboard.bitboard & self.to_bitboard_iterator_mask).is_empty() {
                 self.index += 1;
             }
-            Some(Move::new_unchecked(square_and_bitboard.square, dest, None))
+            Some(square_and_bitboard.get_move(dest, None))
         }
     }
 }
@@ -6,6 +6,32 @@ pub enum BoardStatus {
     Ongoing,
     Stalemate,
     Checkmate,
+    /// The side to move has received the third check.
+    ThreeCheck,
+    /// The king of the side not to move has reached the centre.
+    KingOfTheHill,
+    /// The king of the side to move has been exploded.
+    KingExploded,
+    /// The side to move has no pieces left, which is how black wins in Horde.
+    AllPiecesCaptured,
+    /// A king has reached the eighth rank in Racing Kings, with the winner or `None` if both did.
+    RacingKings(Option<Color>),
+}
+
+impl BoardStatus {
+    /// Winner of the game ended with this status, where `turn` is the side to move.
+    #[inline]
+    pub fn get_winner(self, turn: Color) -> Option<Color> {
+        match self {
+            Self::Ongoing | Self::Stalemate => None,
+            Self::Checkmate
+            | Self::ThreeCheck
+            | Self::KingOfTheHill
+            | Self::KingExploded
+            | Self::AllPiecesCaptured => Some(!turn),
+            Self::RacingKings(winner) => winner,
+        }
+    }
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -16,6 +42,13 @@ pub struct BoardPosition {
     _occupied: BitBoard,
     _turn: Color,
     _castle_rights: [CastleRights; NUM_COLORS],
+    _castling_rook_files: [[File; 2]; NUM_COLORS],
+    _chess960: bool,
+    _variant: Variant,
+    _remaining_checks: [u8; NUM_COLORS],
+    _pockets: [[u8; NUM_PIECE_TYPES]; NUM_COLORS],
+    _promoted: BitBoard,
+    _pockets_hash: u64,
     _ep_square: Option<Square>,
     _pinned: BitBoard,
     _checkers: BitBoard,
@@ -36,6 +69,8 @@ impl UniqueIdentifier for BoardPosition {
             &self._castle_rights,
             &self._turn,
             &self._ep_square,
+            &self._remaining_checks,
+            &self._pockets,
         )
     }
 }
@@ -50,6 +85,72 @@ impl PartialEq for BoardPosition {
     }
 }
 
+/// Pieces removed from the board by a capture in Atomic, in the order of their squares.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Explosion {
+    squares: BitBoard,
+    pieces: [Option<Piece>; 9],
+}
+
+impl Explosion {
+    const NONE: Self = Self {
+        squares: BitBoard::EMPTY,
+        pieces: [None; 9],
+    };
+}
+
+/// State of the variants which cannot be recovered after a move is made, kept apart from the
+/// [`UndoRecord`] so that it is not copied for the standard rules.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct VariantUndoRecord {
+    explosion: Explosion,
+    remaining_checks: [u8; NUM_COLORS],
+    pockets: [[u8; NUM_PIECE_TYPES]; NUM_COLORS],
+    promoted: BitBoard,
+    pockets_hash: u64,
+}
+
+/// State of a position which cannot be recovered after a move is made on it, along with the checkers,
+/// the pins and the material scores which are cheaper to restore than to recompute.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UndoRecord {
+    valid_or_null_move: ValidOrNullMove,
+    captured: Option<PieceType>,
+    castle_rights: [CastleRights; NUM_COLORS],
+    ep_square: Option<Square>,
+    halfmove_clock: u8,
+    pawn_transposition_hash: u64,
+    non_pawn_transposition_hash: u64,
+    checkers: BitBoard,
+    pinned: BitBoard,
+    material_scores: [Score; NUM_COLORS],
+    variant_undo_record: Option<Box<VariantUndoRecord>>,
+}
+
+impl UndoRecord {
+    #[inline]
+    pub fn get_move(&self) -> ValidOrNullMove {
+        self.valid_or_null_move
+    }
+
+    #[inline]
+    pub fn get_captured_piece_type(&self) -> Option<PieceType> {
+        self.captured
+    }
+
+    /// Pawn and non pawn hashes of the position before the move was made.
+    #[inline]
+    pub fn get_hashes(&self) -> (u64, u64) {
+        (
+            self.pawn_transposition_hash,
+            self.non_pawn_transposition_hash,
+        )
+    }
+}
+
 impl BoardPosition {
     #[inline]
     fn new_empty() -> Self {
@@ -59,6 +160,13 @@ impl BoardPosition {
             _occupied: BitBoard::EMPTY,
             _turn: White,
             _castle_rights: [CastleRights::None; NUM_COLORS],
+            _castling_rook_files: [[File::H, File::A]; NUM_COLORS],
+            _chess960: false,
+            _variant: Variant::Standard,
+            _remaining_checks: [3; NUM_COLORS],
+            _pockets: [[0; NUM_PIECE_TYPES]; NUM_COLORS],
+            _promoted: BitBoard::EMPTY,
+            _pockets_hash: 0,
             _pinned: BitBoard::EMPTY,
             _checkers: BitBoard::EMPTY,
             _pawn_transposition_hash: 0,
@@ -78,7 +186,9 @@ impl BoardPosition {
 
     #[inline]
     pub fn status(&self) -> BoardStatus {
-        if self.has_legal_moves() {
+        if let Some(status) = self.get_variant_status() {
+            status
+        } else if self.has_legal_moves() {
             BoardStatus::Ongoing
         } else if self.get_checkers() == BitBoard::EMPTY {
             BoardStatus::Stalemate
@@ -179,8 +289,66 @@ impl BoardPosition {
             || non_king_pieces_mask & BB_DARK_SQUARES == bishop_bitboard
     }
 
+    /// Whether the color cannot explode the king of the opponent in Atomic.
+    fn has_insufficient_atomic_material(&self, color: Color) -> bool {
+        if self.get_colored_piece_mask(King, !color).is_empty() {
+            return false;
+        }
+        if self.occupied_color(color) == self.get_colored_piece_mask(King, color) {
+            return true;
+        }
+        // Any other piece of the opponent can be exploded next to its king, unless only the
+        // bishops of both sides are left and they can never capture each other.
+        if self.occupied_color(!color) != self.get_colored_piece_mask(King, !color) {
+            let bishops = self.get_piece_mask(Bishop);
+            if self.occupied() == bishops ^ self.get_piece_mask(King) {
+                let my_bishops = bishops & self.occupied_color(color);
+                let their_bishops = bishops & self.occupied_color(!color);
+                return ((my_bishops & BB_DARK_SQUARES).is_empty()
+                    && (their_bishops & BB_LIGHT_SQUARES).is_empty())
+                    || ((my_bishops & BB_LIGHT_SQUARES).is_empty()
+                        && (their_bishops & BB_DARK_SQUARES).is_empty());
+            }
+            return false;
+        }
+        // A queen or a pawn can mate the bare king, but a single minor piece or rook and two
+        // knights cannot.
+        if !(self.get_piece_mask(Queen) | self.get_piece_mask(Pawn)).is_empty() {
+            return false;
+        }
+        let minors_and_rooks =
+            self.get_piece_mask(Knight) | self.get_piece_mask(Bishop) | self.get_piece_mask(Rook);
+        minors_and_rooks.popcnt() == 1
+            || (minors_and_rooks == self.get_piece_mask(Knight) && minors_and_rooks.popcnt() <= 2)
+    }
+
     #[inline]
     pub fn is_insufficient_material(&self) -> bool {
+        match self.get_variant() {
+            Variant::Standard => (),
+            // Checks can be given as long as there is a piece other than the kings.
+            Variant::ThreeCheck => return self.get_non_king_pieces_mask().is_empty(),
+            // The kings can always walk to their goal.
+            Variant::KingOfTheHill | Variant::RacingKings => return false,
+            // Not detected for Horde, where the game goes on until the horde is captured.
+            Variant::Horde => return false,
+            // Captured pieces can always be dropped back.
+            Variant::Crazyhouse => return false,
+            Variant::Atomic => {
+                return ALL_COLORS
+                    .into_iter()
+                    .all(|color| self.has_insufficient_atomic_material(color))
+            }
+            // Only the bishops which can never capture each other are left.
+            Variant::Antichess => {
+                let bishops = self.get_piece_mask(Bishop);
+                return self.occupied() == bishops
+                    && ALL_COLORS.into_iter().any(|color| {
+                        (bishops & self.occupied_color(color) & BB_LIGHT_SQUARES).is_empty()
+                            && (bishops & self.occupied_color(!color) & BB_DARK_SQUARES).is_empty()
+                    });
+            }
+        }
         match self.occupied().popcnt() {
             2 => true,
             3 => const { [Pawn, Rook, Queen] }
@@ -195,28 +363,149 @@ impl BoardPosition {
         *get_item_unchecked!(self._castle_rights, color.to_index())
     }
 
+    /// Squares of the rooks which can still castle.
     pub fn clean_castling_rights(&self) -> BitBoard {
-        let white_castling_rights = get_item_unchecked!(
-            const
-            [
-                BitBoard::EMPTY,
-                BB_H1,
-                BB_A1,
-                BitBoard::new(BB_A1.into_inner() ^ BB_H1.into_inner()),
-            ],
-            self.castle_rights(White).to_index(),
-        );
-        let black_castling_rights = get_item_unchecked!(
-            const
-            [
-                BitBoard::EMPTY,
-                BB_H8,
-                BB_A8,
-                BitBoard::new(BB_A8.into_inner() ^ BB_H8.into_inner()),
-            ],
-            self.castle_rights(Black).to_index(),
-        );
-        white_castling_rights ^ black_castling_rights
+        let mut castling_rooks = BitBoard::EMPTY;
+        for color in ALL_COLORS {
+            let castle_rights = self.castle_rights(color);
+            if castle_rights.has_kingside() {
+                castling_rooks ^= self
+                    .get_castling_rook_square(color, CastleRights::KingSide)
+                    .to_bitboard();
+            }
+            if castle_rights.has_queenside() {
+                castling_rooks ^= self
+                    .get_castling_rook_square(color, CastleRights::QueenSide)
+                    .to_bitboard();
+            }
+        }
+        castling_rooks
+    }
+
+    /// Whether castling moves are written as the king capturing its own rook, as in chess960.
+    #[inline]
+    pub fn is_chess960(&self) -> bool {
+        self._chess960
+    }
+
+    /// Square of the rook which castles with the king on the given side, `side` being either [`CastleRights::KingSide`] or [`CastleRights::QueenSide`].
+    #[inline]
+    pub fn get_castling_rook_square(&self, color: Color, side: CastleRights) -> Square {
+        Square::from_rank_and_file(
+            color.to_my_backrank(),
+            *get_item_unchecked!(
+                self._castling_rook_files,
+                color.to_index(),
+                side.to_index() - 1
+            ),
+        )
+    }
+
+    #[inline]
+    pub fn get_variant(&self) -> Variant {
+        self._variant
+    }
+
+    /// Checks the color has to give to win in Three-check, always 3 in the other variants.
+    #[inline]
+    pub fn get_remaining_checks(&self, color: Color) -> u8 {
+        *get_item_unchecked!(self._remaining_checks, color.to_index())
+    }
+
+    /// Number of pieces of the given type in the pocket of the color, always 0 outside Crazyhouse.
+    #[inline]
+    pub fn get_pocket_count(&self, color: Color, piece_type: PieceType) -> u8 {
+        *get_item_unchecked!(self._pockets, color.to_index(), piece_type.to_index())
+    }
+
+    /// Pieces which were promoted from pawns, and go back to the pocket as pawns when captured in Crazyhouse.
+    #[inline]
+    pub fn get_promoted_pieces_mask(&self) -> BitBoard {
+        self._promoted
+    }
+
+    fn add_to_pocket(&mut self, color: Color, piece_type: PieceType, count: i8) {
+        let pocket_count =
+            get_item_unchecked_mut!(self._pockets, color.to_index(), piece_type.to_index());
+        self._pockets_hash ^= Zobrist::pocket(piece_type, color, *pocket_count);
+        *pocket_count = pocket_count.wrapping_add_signed(count);
+        self._pockets_hash ^= Zobrist::pocket(piece_type, color, *pocket_count);
+    }
+
+    /// Type of the piece moved, or dropped, by the move.
+    #[inline]
+    pub fn get_moving_piece_type(&self, move_: Move) -> Option<PieceType> {
+        move_
+            .get_drop()
+            .or_else(|| self.get_piece_type_at(move_.get_source()))
+    }
+
+    #[inline]
+    pub fn get_moving_piece(&self, move_: Move) -> Option<Piece> {
+        match move_.get_drop() {
+            Some(piece_type) => Some(Piece::new(piece_type, self.turn())),
+            None => self.get_piece_at(move_.get_source()),
+        }
+    }
+
+    /// Status of the game if it has been ended by the rules of the variant.
+    #[inline]
+    pub fn get_variant_status(&self) -> Option<BoardStatus> {
+        self.get_variant().get_status(self)
+    }
+
+    /// Castle rights lost when the piece on the square moves or is captured.
+    fn get_castle_rights_at(&self, color: Color, square: Square) -> CastleRights {
+        if square.get_rank() != color.to_my_backrank() {
+            return CastleRights::None;
+        }
+        [CastleRights::KingSide, CastleRights::QueenSide]
+            .into_iter()
+            .filter(|&side| self.get_castling_rook_square(color, side) == square)
+            .fold(CastleRights::None, |castle_rights, side| {
+                castle_rights + side
+            })
+    }
+
+    /// Side castled by the move, given the castle rights before it was made.
+    ///
+    /// In chess960 positions castling is written as the king capturing its own rook, otherwise as the king moving to its destination square, in which case the caller must make sure that the king is moving.
+    fn get_castling_side(
+        &self,
+        move_: Move,
+        color: Color,
+        castle_rights: CastleRights,
+    ) -> Option<CastleRights> {
+        if move_.get_source().get_rank() != color.to_my_backrank()
+            || move_.get_promotion().is_some()
+        {
+            return None;
+        }
+        [CastleRights::KingSide, CastleRights::QueenSide]
+            .into_iter()
+            .find(|&side| {
+                (castle_rights.to_index() & side.to_index()) != 0
+                    && move_.get_dest()
+                        == if self.is_chess960() {
+                            self.get_castling_rook_square(color, side)
+                        } else {
+                            Square::from_rank_and_file(
+                                color.to_my_backrank(),
+                                side.get_king_and_rook_dest_files().0,
+                            )
+                        }
+            })
+    }
+
+    /// Source and destination squares of the rook castling on the given side.
+    fn get_castling_rook_squares(&self, color: Color, side: CastleRights) -> (Square, Square) {
+        (
+            self.get_castling_rook_square(color, side),
+            Square::from_rank_and_file(
+                color.to_my_backrank(),
+                side.get_king_and_rook_dest_files().1,
+            ),
+        )
     }
 
     #[inline]
@@ -280,11 +569,16 @@ impl BoardPosition {
     // }
 
     fn xor(&mut self, piece_type: PieceType, bb: BitBoard, color: Color) {
-        *get_item_unchecked_mut!(self._piece_masks, piece_type.to_index()) ^= bb;
-        let colored_piece_mask = get_item_unchecked_mut!(self._occupied_color, color.to_index());
-        let colored_piece_mask_before = *colored_piece_mask;
-        *colored_piece_mask ^= bb;
-        self._occupied ^= bb;
+        if piece_type != King {
+            let is_added =
+                (*get_item_unchecked!(self._occupied_color, color.to_index()) & bb).is_empty();
+            *get_item_unchecked_mut!(self._material_scores, color.to_index()) += if is_added {
+                piece_type.evaluate()
+            } else {
+                -piece_type.evaluate()
+            };
+        }
+        self.xor_bitboards(piece_type, bb, color);
         if piece_type == Pawn {
             self._pawn_transposition_hash ^=
                 Zobrist::piece(piece_type, bb.to_square_unchecked(), color);
@@ -292,14 +586,15 @@ impl BoardPosition {
             self._non_pawn_transposition_hash ^=
                 Zobrist::piece(piece_type, bb.to_square_unchecked(), color);
         }
-        if piece_type != King {
-            *get_item_unchecked_mut!(self._material_scores, color.to_index()) +=
-                if *colored_piece_mask > colored_piece_mask_before {
-                    piece_type.evaluate()
-                } else {
-                    -piece_type.evaluate()
-                };
-        }
+    }
+
+    /// Updates the bitboards only, used while unmaking a move as the hashes and the material scores are restored
+    /// from the [`UndoRecord`].
+    #[inline]
+    fn xor_bitboards(&mut self, piece_type: PieceType, bb: BitBoard, color: Color) {
+        *get_item_unchecked_mut!(self._piece_masks, piece_type.to_index()) ^= bb;
+        *get_item_unchecked_mut!(self._occupied_color, color.to_index()) ^= bb;
+        self._occupied ^= bb;
     }
 
     #[inline]
@@ -317,13 +612,66 @@ impl BoardPosition {
     pub fn get_hash(&self) -> u64 {
         self.get_pawn_hash()
             ^ self.get_non_pawn_hash()
+            ^ self._pockets_hash
             ^ Zobrist::castle(self.castle_rights(White), self.castle_rights(Black))
             ^ self
                 .ep_square()
                 .map_or(0, |ep| Zobrist::en_passant(ep.get_file()))
+            ^ Zobrist::remaining_checks(
+                self.get_remaining_checks(White),
+                self.get_remaining_checks(Black),
+            )
             ^ Zobrist::color(self.turn())
     }
 
+    /// Pawn and non pawn hashes computed from scratch instead of incrementally.
+    pub fn compute_hashes(&self) -> (u64, u64) {
+        let mut pawn_hash = 0;
+        let mut non_pawn_hash = 0;
+        for (piece, square) in self.iter() {
+            let piece_hash = Zobrist::piece(piece.get_piece_type(), square, piece.get_color());
+            if piece.get_piece_type() == Pawn {
+                pawn_hash ^= piece_hash;
+            } else {
+                non_pawn_hash ^= piece_hash;
+            }
+        }
+        (pawn_hash, non_pawn_hash)
+    }
+
+    /// Checks the incrementally updated hashes against the ones computed from scratch.
+    pub fn verify_hash(&self) -> Result<()> {
+        let error = |err_msg: String| TimecatError::HashMismatch {
+            fen: self.get_fen(),
+            err_msg,
+        };
+        let (pawn_hash, non_pawn_hash) = self.compute_hashes();
+        if pawn_hash != self.get_pawn_hash() {
+            return Err(error(format!(
+                "pawn hash is {:#018x} instead of {pawn_hash:#018x}",
+                self.get_pawn_hash()
+            )));
+        }
+        if non_pawn_hash != self.get_non_pawn_hash() {
+            return Err(error(format!(
+                "non pawn hash is {:#018x} instead of {non_pawn_hash:#018x}",
+                self.get_non_pawn_hash()
+            )));
+        }
+        // The variant is set explicitly as it cannot always be recovered from the fen.
+        let hash = Self::try_from(
+            BoardPositionBuilder::from_str(&self.get_fen())?.variant(self.get_variant()),
+        )?
+        .get_hash();
+        if hash != self.get_hash() {
+            return Err(error(format!(
+                "hash is {:#018x} instead of {hash:#018x}",
+                self.get_hash()
+            )));
+        }
+        Ok(())
+    }
+
     #[inline]
     pub fn get_white_material_score(&self) -> Score {
         *get_item_unchecked!(self._material_scores, 0)
@@ -366,6 +714,299 @@ impl BoardPosition {
         }
     }
 
+    /// Removes the piece on the square and all the pieces around it except the pawns.
+    fn explode(&mut self, square: Square) -> Explosion {
+        let mut explosion = Explosion {
+            squares: (square.get_king_moves() & self.occupied() & !self.get_piece_mask(Pawn))
+                | square.to_bitboard(),
+            ..Explosion::NONE
+        };
+        for (index, square) in explosion.squares.enumerate() {
+            let piece = self.get_piece_at(square).unwrap();
+            let (piece_type, color) = (piece.get_piece_type(), piece.get_color());
+            self.xor(piece_type, square.to_bitboard(), color);
+            self.remove_castle_rights(
+                color,
+                if piece_type == King {
+                    CastleRights::Both
+                } else {
+                    self.get_castle_rights_at(color, square)
+                },
+            );
+            *get_item_unchecked_mut!(explosion.pieces, index) = Some(piece);
+        }
+        explosion
+    }
+
+    fn make_valid_move(&mut self, move_: Move) -> (Option<PieceType>, Explosion) {
+        let turn = self.turn();
+        let source = move_.get_source();
+        let dest = move_.get_dest();
+        let source_bb = source.to_bitboard();
+        let dest_bb = dest.to_bitboard();
+        let moved = self.get_moving_piece_type(move_).unwrap();
+        let mut captured = self.get_piece_type_at(dest);
+        let ep_square = self.ep_square();
+        let is_crazyhouse = self.get_variant() == Variant::Crazyhouse;
+        let is_captured_promoted = self._promoted.contains(dest);
+        if is_crazyhouse {
+            self._promoted &= !dest_bb;
+            if self._promoted.contains(source) {
+                self._promoted ^= source_bb ^ dest_bb;
+            }
+        }
+
+        if self.is_zeroing(move_) {
+            self._halfmove_clock = 0;
+        } else {
+            self._halfmove_clock += 1;
+        }
+        if turn == Black {
+            self._fullmove_number += 1;
+        }
+
+        self.remove_ep();
+        self._checkers = BitBoard::EMPTY;
+        self._pinned = BitBoard::EMPTY;
+
+        let castling_side = if moved == King {
+            self.get_castling_side(move_, turn, self.my_castle_rights())
+        } else {
+            None
+        };
+
+        if move_.is_drop() {
+            self.add_to_pocket(turn, moved, -1);
+            self.xor(moved, dest_bb, turn);
+        } else if let Some(side) = castling_side {
+            // In chess960 the king or the rook may land on the square the other one started from.
+            let king_dest = Square::from_rank_and_file(
+                turn.to_my_backrank(),
+                side.get_king_and_rook_dest_files().0,
+            );
+            let (rook_source, rook_dest) = self.get_castling_rook_squares(turn, side);
+            captured = None;
+            self.xor(King, source_bb, turn);
+            self.xor(Rook, rook_source.to_bitboard(), turn);
+            self.xor(King, king_dest.to_bitboard(), turn);
+            self.xor(Rook, rook_dest.to_bitboard(), turn);
+        } else {
+            self.xor(moved, source_bb, turn);
+            self.xor(moved, dest_bb, turn);
+            if let Some(captured) = captured {
+                self.xor(captured, dest_bb, !turn);
+            }
+        }
+
+        if moved == King {
+            self.remove_my_castle_rights(CastleRights::Both);
+        } else {
+            self.remove_my_castle_rights(self.get_castle_rights_at(turn, source));
+        }
+        self.remove_their_castle_rights(self.get_castle_rights_at(!turn, dest));
+
+        // Without a king to check the checkers and the pins are found for any square, and are
+        // recomputed after the move.
+        let their_king_square = self.get_colored_piece_mask(King, !turn).to_square();
+        let ksq = their_king_square.unwrap_or(dest);
+
+        if moved == Knight {
+            self._checkers ^= ksq.get_knight_moves() & dest_bb;
+        } else if moved == Pawn {
+            if let Some(Knight) = move_.get_promotion() {
+                self.xor(Pawn, dest_bb, turn);
+                self.xor(Knight, dest_bb, turn);
+                self._checkers ^= ksq.get_knight_moves() & dest_bb;
+            } else if let Some(promotion) = move_.get_promotion() {
+                self.xor(Pawn, dest_bb, turn);
+                self.xor(promotion, dest_bb, turn);
+            } else if move_.is_drop() {
+                self._checkers ^= ksq.get_pawn_attacks(!turn, dest_bb);
+            } else if !(source_bb & get_pawn_source_double_moves()).is_empty()
+                && !(dest_bb & get_pawn_dest_double_moves()).is_empty()
+            {
+                self.set_ep(dest.wrapping_backward(turn));
+                self._checkers ^= ksq.get_pawn_attacks(!turn, dest_bb);
+            } else if Some(dest) == ep_square {
+                self.xor(Pawn, dest.wrapping_backward(turn).to_bitboard(), !turn);
+                self._checkers ^= ksq.get_pawn_attacks(!turn, dest_bb);
+                captured = Some(Pawn);
+            } else {
+                self._checkers ^= ksq.get_pawn_attacks(!turn, dest_bb);
+            }
+        }
+        // now, lets see if we're in check or pinned
+        let attackers = self.occupied_color(turn)
+            & ((ksq.get_bishop_rays_bb()
+                & (self.get_piece_mask(Bishop) | self.get_piece_mask(Queen)))
+                | (ksq.get_rook_rays_bb()
+                    & (self.get_piece_mask(Rook) | self.get_piece_mask(Queen))));
+
+        for square in attackers {
+            let between = square.between(ksq) & self.occupied();
+            if between.is_empty() {
+                self._checkers ^= square.to_bitboard();
+            } else if between.popcnt() == 1 {
+                self._pinned ^= between;
+            }
+        }
+
+        if is_crazyhouse {
+            if move_.get_promotion().is_some() {
+                self._promoted ^= dest_bb;
+            }
+            if let Some(captured) = captured {
+                self.add_to_pocket(turn, if is_captured_promoted { Pawn } else { captured }, 1);
+            }
+        }
+
+        if self.get_variant() == Variant::ThreeCheck && !self._checkers.is_empty() {
+            let remaining_checks = get_item_unchecked_mut!(self._remaining_checks, turn.to_index());
+            *remaining_checks = remaining_checks.saturating_sub(1);
+        }
+
+        let explosion = if self.get_variant() == Variant::Atomic && captured.is_some() {
+            self.explode(dest)
+        } else {
+            Explosion::NONE
+        };
+
+        self.flip_turn_unchecked();
+
+        if matches!(self.get_variant(), Variant::Atomic | Variant::Antichess)
+            || their_king_square.is_none()
+        {
+            self.update_pin_and_checkers_info();
+        }
+
+        (captured, explosion)
+    }
+
+    /// Makes the move in place and returns the [`UndoRecord`] needed to unmake it.
+    pub fn make_move_with_undo(&mut self, valid_or_null_move: ValidOrNullMove) -> UndoRecord {
+        let mut variant_undo_record = matches!(
+            self.get_variant(),
+            Variant::Atomic | Variant::Crazyhouse | Variant::ThreeCheck
+        )
+        .then(|| {
+            Box::new(VariantUndoRecord {
+                explosion: Explosion::NONE,
+                remaining_checks: self._remaining_checks,
+                pockets: self._pockets,
+                promoted: self._promoted,
+                pockets_hash: self._pockets_hash,
+            })
+        });
+        let mut undo_record = UndoRecord {
+            valid_or_null_move,
+            captured: None,
+            castle_rights: self._castle_rights,
+            ep_square: self.ep_square(),
+            halfmove_clock: self.get_halfmove_clock(),
+            pawn_transposition_hash: self.get_pawn_hash(),
+            non_pawn_transposition_hash: self.get_non_pawn_hash(),
+            checkers: self._checkers,
+            pinned: self._pinned,
+            material_scores: self._material_scores,
+            variant_undo_record: None,
+        };
+        if let Some(move_) = *valid_or_null_move {
+            let explosion;
+            (undo_record.captured, explosion) = self.make_valid_move(move_);
+            if let Some(variant_undo_record) = variant_undo_record.as_mut() {
+                variant_undo_record.explosion = explosion;
+            }
+        } else {
+            self.flip_turn_unchecked();
+            self.remove_ep();
+            self._halfmove_clock += 1;
+            self._fullmove_number += 1;
+            self.update_pin_and_checkers_info();
+        }
+        undo_record.variant_undo_record = variant_undo_record;
+        undo_record
+    }
+
+    /// Restores the position to the one before the move of the [`UndoRecord`] was made.
+    pub fn unmake_move(&mut self, undo_record: &UndoRecord) {
+        let turn = !self.turn();
+        if let Some(move_) = *undo_record.valid_or_null_move {
+            let source = move_.get_source();
+            let dest = move_.get_dest();
+            let source_bb = source.to_bitboard();
+            let dest_bb = dest.to_bitboard();
+            if let Some(variant_undo_record) = undo_record.variant_undo_record.as_deref() {
+                let explosion = variant_undo_record.explosion;
+                for (square, piece) in explosion
+                    .squares
+                    .zip(explosion.pieces.into_iter().flatten())
+                {
+                    self.xor_bitboards(
+                        piece.get_piece_type(),
+                        square.to_bitboard(),
+                        piece.get_color(),
+                    );
+                }
+            }
+            let castling_side = self
+                .get_castling_side(
+                    move_,
+                    turn,
+                    *get_item_unchecked!(undo_record.castle_rights, turn.to_index()),
+                )
+                .filter(|_| self.is_chess960() || self.get_piece_type_at(dest) == Some(King));
+            if let Some(piece_type) = move_.get_drop() {
+                self.xor_bitboards(piece_type, dest_bb, turn);
+            } else if let Some(side) = castling_side {
+                let king_dest = Square::from_rank_and_file(
+                    turn.to_my_backrank(),
+                    side.get_king_and_rook_dest_files().0,
+                );
+                let (rook_source, rook_dest) = self.get_castling_rook_squares(turn, side);
+                self.xor_bitboards(King, king_dest.to_bitboard(), turn);
+                self.xor_bitboards(Rook, rook_dest.to_bitboard(), turn);
+                self.xor_bitboards(King, source_bb, turn);
+                self.xor_bitboards(Rook, rook_source.to_bitboard(), turn);
+            } else {
+                let mut moved = self.get_piece_type_at(dest).unwrap();
+                if let Some(promotion) = move_.get_promotion() {
+                    self.xor_bitboards(promotion, dest_bb, turn);
+                    self.xor_bitboards(Pawn, dest_bb, turn);
+                    moved = Pawn;
+                }
+                self.xor_bitboards(moved, dest_bb, turn);
+                self.xor_bitboards(moved, source_bb, turn);
+                if let Some(captured) = undo_record.captured {
+                    if moved == Pawn && Some(dest) == undo_record.ep_square {
+                        self.xor_bitboards(Pawn, dest.wrapping_backward(turn).to_bitboard(), !turn);
+                    } else {
+                        self.xor_bitboards(captured, dest_bb, !turn);
+                    }
+                }
+            }
+            if turn == Black {
+                self._fullmove_number -= 1;
+            }
+        } else {
+            self._fullmove_number -= 1;
+        }
+        if let Some(variant_undo_record) = undo_record.variant_undo_record.as_deref() {
+            self._remaining_checks = variant_undo_record.remaining_checks;
+            self._pockets = variant_undo_record.pockets;
+            self._promoted = variant_undo_record.promoted;
+            self._pockets_hash = variant_undo_record.pockets_hash;
+        }
+        self._turn = turn;
+        self._castle_rights = undo_record.castle_rights;
+        self._ep_square = undo_record.ep_square;
+        self._halfmove_clock = undo_record.halfmove_clock;
+        self._pawn_transposition_hash = undo_record.pawn_transposition_hash;
+        self._non_pawn_transposition_hash = undo_record.non_pawn_transposition_hash;
+        self._checkers = undo_record.checkers;
+        self._pinned = undo_record.pinned;
+        self._material_scores = undo_record.material_scores;
+    }
+
     pub fn is_sane(&self) -> bool {
         // make sure there is no square with multiple pieces on it
         for x in ALL_PIECE_TYPES {
@@ -391,13 +1032,27 @@ impl BoardPosition {
             return false;
         }
 
-        // make sure there is exactly one white king
-        if self.get_colored_piece_mask(King, White).popcnt() != 1 {
-            return false;
+        // make sure there is exactly one king of each color, except in Atomic where the king of
+        // the side to move may have been exploded, in Horde where white has no king, and in
+        // Antichess where any number of kings is allowed
+        for color in ALL_COLORS
+            .into_iter()
+            .filter(|_| self.get_variant().has_checks())
+        {
+            match self.get_colored_piece_mask(King, color).popcnt() {
+                1 => (),
+                0 if self.get_variant() == Variant::Atomic
+                    && color == self.turn()
+                    && self.castle_rights(color) == CastleRights::None => {}
+                0 if self.get_variant() == Variant::Horde && color == White => {}
+                _ => return false,
+            }
         }
 
-        // make sure there is exactly one black king
-        if self.get_colored_piece_mask(King, Black).popcnt() != 1 {
+        // Racing Kings is played without pawns and without checks
+        if self.get_variant() == Variant::RacingKings
+            && (!self.get_piece_mask(Pawn).is_empty() || !self.get_checkers().is_empty())
+        {
             return false;
         }
 
@@ -414,41 +1069,58 @@ impl BoardPosition {
             }
         }
 
-        // make sure my opponent is not currently in check (because that would be illegal)
+        // make sure my opponent is not currently in check (because that would be illegal), unless
+        // the check was ignored to explode my king in Atomic
         let mut board_copy = self.to_owned();
         board_copy.flip_turn_unchecked();
         board_copy.update_pin_and_checkers_info();
-        if !board_copy.get_checkers().is_empty() {
+        if !board_copy.get_checkers().is_empty()
+            && !self.get_colored_piece_mask(King, self.turn()).is_empty()
+        {
             return false;
         }
 
         // for each color, verify that, if they have castle rights, that they haven't moved their
         // rooks or king
         for color in ALL_COLORS {
-            // get the castle rights
             let castle_rights = self.castle_rights(color);
+            if castle_rights == CastleRights::None {
+                continue;
+            }
 
-            // the castle rights object will tell us which rooks shouldn't have moved yet.
-            // verify there are rooks on all those squares
-            if castle_rights.unmoved_rooks(color)
-                & self.get_piece_mask(Rook)
-                & self.occupied_color(color)
-                != castle_rights.unmoved_rooks(color)
-            {
+            // the king must still be on its back rank
+            let Some(ksq) = self.get_colored_piece_mask(King, color).to_square() else {
                 return false;
-            }
-            // if we have castle rights, make sure we have a king on the (E, {1,8}) square,
-            // depending on the color
-            if castle_rights != CastleRights::None
-                && self.get_colored_piece_mask(King, color)
-                    != BB_FILE_E & color.to_my_backrank().to_bitboard()
-            {
+            };
+            if ksq.get_rank() != color.to_my_backrank() {
                 return false;
             }
+
+            // and every rook which can castle must be on its square, on the correct side of the king
+            for side in [CastleRights::KingSide, CastleRights::QueenSide] {
+                if castle_rights.to_index() & side.to_index() == 0 {
+                    continue;
+                }
+                let rook_square = self.get_castling_rook_square(color, side);
+                if !self
+                    .get_colored_piece_mask(Rook, color)
+                    .contains(rook_square)
+                    || (rook_square.get_file() > ksq.get_file()) != (side == CastleRights::KingSide)
+                {
+                    return false;
+                }
+            }
         }
 
-        // we must make sure the kings aren't touching
-        if !(self.get_king_square(White).get_king_moves() & self.get_piece_mask(King)).is_empty() {
+        // we must make sure the kings aren't touching, which is allowed in Atomic
+        if !matches!(self.get_variant(), Variant::Atomic | Variant::Antichess)
+            && self
+                .get_colored_piece_mask(King, White)
+                .to_square()
+                .is_some_and(|square| {
+                    !(square.get_king_moves() & self.get_piece_mask(King)).is_empty()
+                })
+        {
             return false;
         }
 
@@ -536,11 +1208,27 @@ impl BoardPosition {
         self.to_string()
     }
 
+    /// FEN with the castle rights written as the files of the castling rooks.
+    #[inline]
+    pub fn get_shredder_fen(&self) -> String {
+        BoardPositionBuilder::from(self).get_shredder_fen()
+    }
+
     #[inline]
     pub fn from_fen(fen: &str) -> Result<Self> {
         Self::from_str(fen)
     }
 
+    /// Parses the fen as a position of the given variant, which cannot always be recovered from the fen. The variants
+    /// written in the fen, through the check counters of Three-check or the pockets of Crazyhouse, take precedence.
+    pub fn from_fen_and_variant(fen: &str, variant: Variant) -> Result<Self> {
+        let mut position_builder = BoardPositionBuilder::from_str(fen)?;
+        if position_builder.get_variant() == Variant::Standard {
+            position_builder.variant(variant);
+        }
+        Self::try_from(position_builder)
+    }
+
     pub fn is_good_fen(fen: &str) -> bool {
         let fen = simplify_fen(fen);
         let mut splitted_fen = fen.split(' ');
@@ -553,12 +1241,18 @@ impl BoardPosition {
         Self::from_str(&fen).is_ok()
     }
 
+    /// Sets the position from the fen keeping the variant.
+    #[inline]
     pub fn set_fen(&mut self, fen: &str) -> Result<()> {
+        self.set_fen_and_variant(fen, self.get_variant())
+    }
+
+    pub fn set_fen_and_variant(&mut self, fen: &str, variant: Variant) -> Result<()> {
         let fen = simplify_fen(fen);
-        if fen == self.get_fen() {
+        if fen == self.get_fen() && variant == self.get_variant() {
             return Ok(());
         }
-        *self = Self::from_str(&fen)?;
+        *self = Self::from_fen_and_variant(&fen, variant)?;
         Ok(())
     }
 
@@ -646,10 +1340,7 @@ impl BoardPosition {
         from_bitboard: BitBoard,
         to_bitboard: BitBoard,
     ) -> MoveGenerator {
-        // TODO: Scope of improvement
-        let mut moves = self.generate_legal_moves();
-        moves.set_iterator_masks(from_bitboard, to_bitboard);
-        moves
+        MoveGenerator::new_masked_legal(self, from_bitboard, to_bitboard)
     }
 
     pub fn generate_legal_captures(&self) -> MoveGenerator {
@@ -657,9 +1348,8 @@ impl BoardPosition {
         if let Some(ep_square) = self.ep_square() {
             targets ^= ep_square.to_bitboard()
         }
-        let mut moves = self.generate_legal_moves();
-        moves.set_to_bitboard_iterator_mask(targets);
-        moves
+        // The source squares are masked so that the drops on the en passant square are not included.
+        self.generate_masked_legal_moves(self.self_occupied(), targets)
     }
 
     #[inline]
@@ -668,18 +1358,37 @@ impl BoardPosition {
     }
 
     pub fn is_castling(&self, move_: Move) -> bool {
-        if !self.get_piece_mask(King).contains(move_.get_source()) {
-            return false;
+        self.get_king_square(self.turn()) == move_.get_source()
+            && self
+                .get_castling_side(move_, self.turn(), self.my_castle_rights())
+                .is_some()
+    }
+
+    /// Converts castling written as the king capturing its own rook, as in polyglot books, to the way castling is written in this position.
+    pub fn convert_king_takes_rook_castling(&self, move_: Move) -> Move {
+        let color = self.turn();
+        if self.is_chess960()
+            || self.get_king_square(color) != move_.get_source()
+            || move_.get_promotion().is_some()
+        {
+            return move_;
         }
-        let rank_diff = move_
-            .get_source()
-            .get_file()
-            .to_index()
-            .abs_diff(move_.get_dest().get_file().to_index());
-        rank_diff > 1
-            || self
-                .get_colored_piece_mask(Rook, self.turn())
-                .contains(move_.get_dest())
+        [CastleRights::KingSide, CastleRights::QueenSide]
+            .into_iter()
+            .find(|&side| {
+                self.my_castle_rights().to_index() & side.to_index() != 0
+                    && self.get_castling_rook_square(color, side) == move_.get_dest()
+            })
+            .map_or(move_, |side| {
+                Move::new_unchecked(
+                    move_.get_source(),
+                    Square::from_rank_and_file(
+                        color.to_my_backrank(),
+                        side.get_king_and_rook_dest_files().0,
+                    ),
+                    None,
+                )
+            })
     }
 
     pub fn is_zeroing(&self, move_: Move) -> bool {
@@ -735,7 +1444,15 @@ impl BoardPosition {
         self._pinned = BitBoard::EMPTY;
         self._checkers = BitBoard::EMPTY;
 
-        let ksq = self.get_king_square(self.turn());
+        let Some(ksq) = self
+            .get_colored_piece_mask(King, self.turn())
+            .to_square()
+            .filter(|_| self.get_variant().has_checks())
+        else {
+            // The king can be missing after being exploded in Atomic or in Horde, and cannot be
+            // checked in Antichess.
+            return;
+        };
         let pinners = self.opponent_occupied()
             & ((ksq.get_bishop_rays_bb()
                 & (self.get_piece_mask(Bishop) | self.get_piece_mask(Queen)))
@@ -758,6 +1475,13 @@ impl BoardPosition {
             self.turn(),
             self.opponent_occupied() & self.get_piece_mask(Pawn),
         );
+
+        // In Atomic the king cannot be captured while touching the other king, as the capture would explode both of them.
+        if self.get_variant() == Variant::Atomic
+            && !(ksq.get_king_moves() & self.get_piece_mask(King)).is_empty()
+        {
+            self._checkers = BitBoard::EMPTY;
+        }
     }
 
     #[inline]
@@ -795,13 +1519,23 @@ impl BoardPosition {
         self.get_custom_attacked_squares_bb(&ALL_PIECE_TYPES, &ALL_COLORS, BB_ALL)
     }
 
+    #[inline]
     pub fn get_attackers_mask(
         &self,
         target_square: Square,
         color: impl Into<Option<Color>>,
+    ) -> BitBoard {
+        self.get_attackers_mask_with_occupied(target_square, color, self.occupied())
+    }
+
+    /// The attackers of the square when the sliders are blocked by `occupied` instead of the pieces on the board.
+    pub fn get_attackers_mask_with_occupied(
+        &self,
+        target_square: Square,
+        color: impl Into<Option<Color>>,
+        occupied: BitBoard,
     ) -> BitBoard {
         let color = color.into();
-        let occupied = self.occupied();
 
         let queens_and_bishops = self.get_piece_mask(Bishop) ^ self.get_piece_mask(Queen);
         let queens_and_rooks = self.get_piece_mask(Rook) ^ self.get_piece_mask(Queen);
@@ -906,7 +1640,7 @@ impl BoardPosition {
     pub fn to_board_string(&self, last_move: ValidOrNullMove, use_unicode: bool) -> String {
         let mut skeleton = get_board_skeleton();
         let checkers = self.get_checkers();
-        let king_square = self.get_king_square(self.turn());
+        let king_square = self.get_colored_piece_mask(King, self.turn()).to_square();
         for square in SQUARES_HORIZONTAL_MIRROR {
             let symbol = if use_unicode {
                 self.piece_unicode_symbol_at(square, false)
@@ -919,7 +1653,7 @@ impl BoardPosition {
                     White => WHITE_PIECES_STYLE,
                     Black => BLACK_PIECES_STYLE,
                 });
-                if square == king_square && !checkers.is_empty() {
+                if Some(square) == king_square && !checkers.is_empty() {
                     styles.extend_from_slice(CHECK_STYLE);
                 }
             }
@@ -1165,40 +1899,7 @@ impl BoardPosition {
                 )
             }
         };
-        let (white_castle_rights, black_castle_rights) = {
-            let castling_rights_bb = ob.getattr("castling_rights")?.extract::<BitBoard>()?;
-            const BB_A1_H1: BitBoard = BitBoard::new(BB_A1.into_inner() ^ BB_H1.into_inner());
-            const BB_A8_H8: BitBoard = BitBoard::new(BB_A8.into_inner() ^ BB_H8.into_inner());
-            (
-                match castling_rights_bb & White.to_my_backrank().to_bitboard() {
-                    BitBoard::EMPTY => CastleRights::None,
-                    BB_H1 => CastleRights::KingSide,
-                    BB_A1 => CastleRights::QueenSide,
-                    BB_A1_H1 => CastleRights::Both,
-                    _ => {
-                        return Err(Pyo3Error::Pyo3TypeConversionError {
-                            from: ob.to_string(),
-                            to: std::any::type_name::<Self>().to_string(),
-                        }
-                        .into())
-                    }
-                },
-                match castling_rights_bb & Black.to_my_backrank().to_bitboard() {
-                    BitBoard::EMPTY => CastleRights::None,
-                    BB_H8 => CastleRights::KingSide,
-                    BB_A8 => CastleRights::QueenSide,
-                    BB_A8_H8 => CastleRights::Both,
-                    _ => {
-                        return Err(Pyo3Error::Pyo3TypeConversionError {
-                            from: ob.to_string(),
-                            to: std::any::type_name::<Self>().to_string(),
-                        }
-                        .into())
-                    }
-                },
-            )
-        };
-        Ok(BoardPositionBuilder::setup(
+        let mut position_builder = BoardPositionBuilder::setup(
             ALL_PIECE_TYPES
                 .iter()
                 .zip(pieces_masks)
@@ -1211,16 +1912,37 @@ impl BoardPosition {
                         )
                 }),
             ob.getattr("turn")?.extract()?,
-            white_castle_rights,
-            black_castle_rights,
+            CastleRights::None,
+            CastleRights::None,
             ob.getattr("ep_square")?
                 .extract::<Option<Square>>()
                 .unwrap_or_default()
                 .map(|square| square.get_file()),
             ob.getattr("halfmove_clock")?.extract().unwrap_or(0),
             ob.getattr("fullmove_number")?.extract().unwrap_or(1),
-        )
-        .try_into()?)
+        );
+        // python-chess stores the squares of the rooks which can castle
+        let castling_rights_bb = ob.getattr("castling_rights")?.extract::<BitBoard>()?;
+        for color in ALL_COLORS {
+            let occupied_color = if color == White {
+                white_occupied
+            } else {
+                black_occupied
+            };
+            let ksq = (pieces_masks[King.to_index()] & occupied_color).to_square_unchecked();
+            for rook_square in castling_rights_bb & color.to_my_backrank().to_bitboard() {
+                let side = if rook_square.get_file() > ksq.get_file() {
+                    CastleRights::KingSide
+                } else {
+                    CastleRights::QueenSide
+                };
+                position_builder
+                    .castle_rights(color, position_builder.get_castle_rights(color) + side)
+                    .castling_rook_file(color, side, rook_square.get_file());
+            }
+        }
+        position_builder.chess960(ob.getattr("chess960")?.extract().unwrap_or(false));
+        Ok(position_builder.try_into()?)
     }
 }
 
@@ -1240,130 +1962,16 @@ impl BoardPositionMethodOverload<Move> for BoardPosition {
         Move::from_str(uci)
     }
 
+    #[inline]
     fn make_move_new(&self, move_: Move) -> Self {
         let mut result = self.clone();
-
-        if result.is_zeroing(move_) {
-            result._halfmove_clock = 0;
-        } else {
-            result._halfmove_clock += 1;
-        }
-        if result.turn() == Black {
-            result._fullmove_number += 1;
-        }
-
-        result.remove_ep();
-        result._checkers = BitBoard::EMPTY;
-        result._pinned = BitBoard::EMPTY;
-        let source = move_.get_source();
-        let dest = move_.get_dest();
-
-        let source_bb = source.to_bitboard();
-        let dest_bb = dest.to_bitboard();
-        let move_bb = source_bb ^ dest_bb;
-        let moved = self.get_piece_type_at(source).unwrap();
-
-        result.xor(moved, source_bb, self.turn());
-        result.xor(moved, dest_bb, self.turn());
-        if let Some(captured) = self.get_piece_type_at(dest) {
-            result.xor(captured, dest_bb, !self.turn());
-        }
-
+        result.make_valid_move(move_);
         result
-            .remove_their_castle_rights(CastleRights::square_to_castle_rights(!self.turn(), dest));
-
-        result.remove_my_castle_rights(CastleRights::square_to_castle_rights(self.turn(), source));
-
-        let opp_king = result.get_colored_piece_mask(King, !result.turn());
-
-        let castles = moved == King && (move_bb & get_castle_moves()) == move_bb;
-
-        let ksq = opp_king.to_square_unchecked();
-
-        if moved == Knight {
-            result._checkers ^= ksq.get_knight_moves() & dest_bb;
-        } else if moved == Pawn {
-            if let Some(Knight) = move_.get_promotion() {
-                result.xor(Pawn, dest_bb, self.turn());
-                result.xor(Knight, dest_bb, self.turn());
-                result._checkers ^= ksq.get_knight_moves() & dest_bb;
-            } else if let Some(promotion) = move_.get_promotion() {
-                result.xor(Pawn, dest_bb, self.turn());
-                result.xor(promotion, dest_bb, self.turn());
-            } else if !(source_bb & get_pawn_source_double_moves()).is_empty()
-                && !(dest_bb & get_pawn_dest_double_moves()).is_empty()
-            {
-                result.set_ep(dest.wrapping_backward(result.turn()));
-                result._checkers ^= ksq.get_pawn_attacks(!result.turn(), dest_bb);
-            } else if Some(dest) == self.ep_square() {
-                result.xor(
-                    Pawn,
-                    dest.wrapping_backward(self.turn()).to_bitboard(),
-                    !self.turn(),
-                );
-                result._checkers ^= ksq.get_pawn_attacks(!result.turn(), dest_bb);
-            } else {
-                result._checkers ^= ksq.get_pawn_attacks(!result.turn(), dest_bb);
-            }
-        } else if castles {
-            let my_backrank = self.turn().to_my_backrank();
-            let index = dest.get_file().to_index();
-            let start = BitBoard::from_rank_and_file(
-                my_backrank,
-                *get_item_unchecked!(
-                    const
-                    [
-                        File::A,
-                        File::A,
-                        File::A,
-                        File::A,
-                        File::H,
-                        File::H,
-                        File::H,
-                        File::H,
-                    ],
-                    index,
-                ),
-            );
-            let end = BitBoard::from_rank_and_file(
-                my_backrank,
-                *get_item_unchecked!(
-                    const
-                    [
-                        File::D,
-                        File::D,
-                        File::D,
-                        File::D,
-                        File::F,
-                        File::F,
-                        File::F,
-                        File::F,
-                    ],
-                    index,
-                ),
-            );
-            result.xor(Rook, start, self.turn());
-            result.xor(Rook, end, self.turn());
-        }
-        // now, lets see if we're in check or pinned
-        let attackers = result.occupied_color(result.turn())
-            & ((ksq.get_bishop_rays_bb()
-                & (result.get_piece_mask(Bishop) | result.get_piece_mask(Queen)))
-                | (ksq.get_rook_rays_bb()
-                    & (result.get_piece_mask(Rook) | result.get_piece_mask(Queen))));
-
-        for square in attackers {
-            let between = square.between(ksq) & result.occupied();
-            if between.is_empty() {
-                result._checkers ^= square.to_bitboard();
-            } else if between.popcnt() == 1 {
-                result._pinned ^= between;
-            }
-        }
-
-        result.flip_turn_unchecked();
+    }
 
-        result
+    #[inline]
+    fn make_move(&mut self, move_: Move) {
+        self.make_valid_move(move_);
     }
 }
 
@@ -1390,6 +1998,11 @@ impl BoardPositionMethodOverload<ValidOrNullMove> for BoardPosition {
             self.null_move().unwrap()
         }
     }
+
+    #[inline]
+    fn make_move(&mut self, valid_or_null_move: ValidOrNullMove) {
+        self.make_move_with_undo(valid_or_null_move);
+    }
 }
 
 impl TryFrom<&BoardPositionBuilder> for BoardPosition {
@@ -1416,8 +2029,40 @@ impl TryFrom<&BoardPositionBuilder> for BoardPosition {
             position._turn = !position.turn();
         }
 
-        position.add_castle_rights(White, position_builder.get_castle_rights(White));
-        position.add_castle_rights(Black, position_builder.get_castle_rights(Black));
+        for color in ALL_COLORS {
+            // There is no castling in Antichess.
+            if position_builder.get_variant() != Variant::Antichess {
+                position.add_castle_rights(color, position_builder.get_castle_rights(color));
+            }
+            for side in [CastleRights::KingSide, CastleRights::QueenSide] {
+                *get_item_unchecked_mut!(
+                    position._castling_rook_files,
+                    color.to_index(),
+                    side.to_index() - 1
+                ) = position_builder.get_castling_rook_file(color, side);
+            }
+        }
+        position._chess960 =
+            position_builder.is_chess960() || position_builder.has_chess960_castling();
+        position._variant = position_builder.get_variant();
+        if position.get_variant() == Variant::Crazyhouse {
+            for color in ALL_COLORS {
+                for piece_type in ALL_PIECE_TYPES {
+                    position.add_to_pocket(
+                        color,
+                        piece_type,
+                        position_builder.get_pocket_count(color, piece_type) as i8,
+                    );
+                }
+            }
+            position._promoted = position_builder.get_promoted_pieces_mask() & position.occupied();
+        }
+        if position.get_variant() == Variant::ThreeCheck {
+            for color in ALL_COLORS {
+                *get_item_unchecked_mut!(position._remaining_checks, color.to_index()) =
+                    position_builder.get_remaining_checks(color);
+            }
+        }
 
         position._halfmove_clock = position_builder.get_halfmove_clock();
         position._fullmove_number = position_builder.get_fullmove_number();
@@ -1456,10 +2101,17 @@ impl FromStr for BoardPosition {
     }
 }
 
+/// The standard starting position, whatever the variant set globally, as it is also used to
+/// initialize the evaluators.
 impl Default for BoardPosition {
     #[inline]
     fn default() -> Self {
-        Self::from_str(STARTING_POSITION_FEN).unwrap()
+        Self::try_from(
+            BoardPositionBuilder::from_str(STARTING_POSITION_FEN)
+                .unwrap()
+                .variant(Variant::Standard),
+        )
+        .unwrap()
     }
 }
 
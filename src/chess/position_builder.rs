@@ -7,6 +7,12 @@ pub struct BoardPositionBuilder {
     pieces: [Option<Piece>; 64],
     turn: Color,
     castle_rights: [CastleRights; 2],
+    castling_rook_files: [[File; 2]; 2],
+    chess960: bool,
+    variant: Variant,
+    remaining_checks: [u8; 2],
+    pockets: [[u8; NUM_PIECE_TYPES]; 2],
+    promoted: BitBoard,
     ep_file: Option<File>,
     halfmove_clock: u8,
     fullmove_number: NumMoves,
@@ -19,6 +25,12 @@ impl BoardPositionBuilder {
             pieces: [None; 64],
             turn: White,
             castle_rights: [CastleRights::None, CastleRights::None],
+            castling_rook_files: [[File::H, File::A]; 2],
+            chess960: GLOBAL_TIMECAT_STATE.is_in_chess960_mode(),
+            variant: Variant::Standard,
+            remaining_checks: [3; 2],
+            pockets: [[0; NUM_PIECE_TYPES]; 2],
+            promoted: BitBoard::EMPTY,
             ep_file: None,
             halfmove_clock: 0,
             fullmove_number: 1,
@@ -38,6 +50,12 @@ impl BoardPositionBuilder {
             pieces: [None; 64],
             turn,
             castle_rights: [white_castle_rights, black_castle_rights],
+            castling_rook_files: [[File::H, File::A]; 2],
+            chess960: GLOBAL_TIMECAT_STATE.is_in_chess960_mode(),
+            variant: Variant::Standard,
+            remaining_checks: [3; 2],
+            pockets: [[0; NUM_PIECE_TYPES]; 2],
+            promoted: BitBoard::EMPTY,
             ep_file,
             halfmove_clock,
             fullmove_number,
@@ -58,6 +76,50 @@ impl BoardPositionBuilder {
         self.castle_rights[color.to_index()]
     }
 
+    /// File of the rook castling on the given side, `side` being either [`CastleRights::KingSide`] or [`CastleRights::QueenSide`].
+    pub fn get_castling_rook_file(&self, color: Color, side: CastleRights) -> File {
+        self.castling_rook_files[color.to_index()][side.to_index() - 1]
+    }
+
+    #[inline]
+    pub fn is_chess960(&self) -> bool {
+        self.chess960
+    }
+
+    /// Whether the castle rights can only be described in chess960, in which case the position is treated as a chess960 one whatever [`Self::is_chess960`] says.
+    pub fn has_chess960_castling(&self) -> bool {
+        ALL_COLORS.into_iter().any(|color| {
+            let castle_rights = self.get_castle_rights(color);
+            castle_rights != CastleRights::None
+                && (self[Square::from_rank_and_file(color.to_my_backrank(), File::E)]
+                    != Some(Piece::new(King, color))
+                    || (castle_rights.has_kingside()
+                        && self.get_castling_rook_file(color, CastleRights::KingSide) != File::H)
+                    || (castle_rights.has_queenside()
+                        && self.get_castling_rook_file(color, CastleRights::QueenSide) != File::A))
+        })
+    }
+
+    #[inline]
+    pub fn get_variant(&self) -> Variant {
+        self.variant
+    }
+
+    #[inline]
+    pub fn get_remaining_checks(&self, color: Color) -> u8 {
+        self.remaining_checks[color.to_index()]
+    }
+
+    #[inline]
+    pub fn get_pocket_count(&self, color: Color, piece_type: PieceType) -> u8 {
+        self.pockets[color.to_index()][piece_type.to_index()]
+    }
+
+    #[inline]
+    pub fn get_promoted_pieces_mask(&self) -> BitBoard {
+        self.promoted
+    }
+
     pub fn get_en_passant(&self) -> Option<Square> {
         self.ep_file
             .map(|f| Square::from_rank_and_file((!self.get_turn()).to_third_rank(), f))
@@ -83,6 +145,41 @@ impl BoardPositionBuilder {
         self
     }
 
+    pub fn castling_rook_file(
+        &mut self,
+        color: Color,
+        side: CastleRights,
+        file: File,
+    ) -> &mut Self {
+        self.castling_rook_files[color.to_index()][side.to_index() - 1] = file;
+        self
+    }
+
+    pub fn chess960(&mut self, chess960: bool) -> &mut Self {
+        self.chess960 = chess960;
+        self
+    }
+
+    pub fn variant(&mut self, variant: Variant) -> &mut Self {
+        self.variant = variant;
+        self
+    }
+
+    pub fn remaining_checks(&mut self, color: Color, remaining_checks: u8) -> &mut Self {
+        self.remaining_checks[color.to_index()] = remaining_checks;
+        self
+    }
+
+    pub fn pocket_count(&mut self, color: Color, piece_type: PieceType, count: u8) -> &mut Self {
+        self.pockets[color.to_index()][piece_type.to_index()] = count;
+        self
+    }
+
+    pub fn promoted_pieces_mask(&mut self, mask: BitBoard) -> &mut Self {
+        self.promoted = mask;
+        self
+    }
+
     pub fn add_piece(&mut self, square: Square, piece: Piece) -> &mut Self {
         self[square] = Some(piece);
         self
@@ -109,81 +206,315 @@ impl BoardPositionBuilder {
     }
 }
 
-impl Index<Square> for BoardPositionBuilder {
-    type Output = Option<Piece>;
+impl BoardPositionBuilder {
+    /// Parses the check counters of Three-check, written as `W+B`, which are the remaining checks, or `+W+B`, which are the checks given.
+    fn parse_check_counters(&mut self, counters: &str) -> Result<()> {
+        let (checks_given, counters) = match counters.strip_prefix('+') {
+            Some(counters) => (true, counters),
+            None => (false, counters),
+        };
+        let Some((white_checks, black_checks)) = counters.split_once('+') else {
+            return Err(TimecatError::BadFen {
+                fen: counters.to_string(),
+            });
+        };
+        for (color, checks) in [(White, white_checks), (Black, black_checks)] {
+            let checks: u8 =
+                checks
+                    .parse()
+                    .ok()
+                    .filter(|&checks| checks <= 3)
+                    .ok_or(TimecatError::BadFen {
+                        fen: counters.to_string(),
+                    })?;
+            self.remaining_checks(color, if checks_given { 3 - checks } else { checks });
+        }
+        self.variant(Variant::ThreeCheck);
+        Ok(())
+    }
 
-    fn index(&self, index: Square) -> &Self::Output {
-        &self.pieces[index.to_index()]
+    /// Parses the pieces in the pockets of Crazyhouse, written as in `[QNPqp]`.
+    fn parse_pockets(&mut self, pockets: &str) -> Result<()> {
+        for piece in pockets.chars() {
+            let piece = Piece::from_str(&piece.to_string())?;
+            let count = self.get_pocket_count(piece.get_color(), piece.get_piece_type());
+            if piece.get_piece_type() == King || count as usize >= MAX_POCKET_COUNT {
+                return Err(TimecatError::BadFen {
+                    fen: pockets.to_string(),
+                });
+            }
+            self.pocket_count(piece.get_color(), piece.get_piece_type(), count + 1);
+        }
+        self.variant(Variant::Crazyhouse);
+        Ok(())
     }
-}
 
-impl IndexMut<Square> for BoardPositionBuilder {
-    fn index_mut(&mut self, index: Square) -> &mut Self::Output {
-        &mut self.pieces[index.to_index()]
+    fn get_pockets_string(&self) -> String {
+        let mut pockets_string = String::new();
+        for color in ALL_COLORS {
+            for piece_type in ALL_PIECE_TYPES.into_iter().rev() {
+                for _ in 0..self.get_pocket_count(color, piece_type) {
+                    pockets_string += &piece_type.to_colored_piece_string(color);
+                }
+            }
+        }
+        pockets_string
     }
-}
 
-impl fmt::Display for BoardPositionBuilder {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fn get_king_file(&self, color: Color) -> Option<File> {
+        ALL_FILES.into_iter().find(|&file| {
+            self[Square::from_rank_and_file(color.to_my_backrank(), file)]
+                == Some(Piece::new(King, color))
+        })
+    }
+
+    /// File of the rook on the back rank which is the farthest from the king on the given side.
+    fn get_outermost_rook_file(&self, color: Color, side: CastleRights) -> Option<File> {
+        let king_file = self.get_king_file(color).unwrap_or(File::E);
+        let is_my_rook = |&file: &File| {
+            self[Square::from_rank_and_file(color.to_my_backrank(), file)]
+                == Some(Piece::new(Rook, color))
+        };
+        if side == CastleRights::KingSide {
+            ALL_FILES[king_file.to_index() + 1..]
+                .iter()
+                .rev()
+                .copied()
+                .find(is_my_rook)
+        } else {
+            ALL_FILES[..king_file.to_index()]
+                .iter()
+                .copied()
+                .find(is_my_rook)
+        }
+    }
+
+    /// Parses the castling field of the FEN, written as `KQkq` (X-FEN, where the outermost rook is meant) or as rook files (Shredder-FEN).
+    fn parse_castle_rights(&mut self, castles: &str) -> Result<()> {
+        self.castle_rights = [CastleRights::None; 2];
+        for c in castles.chars().filter(|&c| c != '-') {
+            let color = if c.is_ascii_uppercase() { White } else { Black };
+            let (side, file) = match c.to_ascii_lowercase() {
+                'k' => (
+                    CastleRights::KingSide,
+                    self.get_outermost_rook_file(color, CastleRights::KingSide)
+                        .unwrap_or(File::H),
+                ),
+                'q' => (
+                    CastleRights::QueenSide,
+                    self.get_outermost_rook_file(color, CastleRights::QueenSide)
+                        .unwrap_or(File::A),
+                ),
+                file_char @ 'a'..='h' => {
+                    let file = File::from_int(file_char as u8 - b'a');
+                    let king_file = self.get_king_file(color).ok_or(TimecatError::BadFen {
+                        fen: castles.to_string(),
+                    })?;
+                    let side = if file > king_file {
+                        CastleRights::KingSide
+                    } else {
+                        CastleRights::QueenSide
+                    };
+                    (side, file)
+                }
+                _ => {
+                    return Err(TimecatError::BadFen {
+                        fen: castles.to_string(),
+                    })
+                }
+            };
+            self.castle_rights[color.to_index()] += side;
+            self.castling_rook_file(color, side, file);
+        }
+        Ok(())
+    }
+
+    /// Castling field of the FEN, with the rook files written only when `KQkq` would be ambiguous unless `shredder` is set.
+    fn get_castle_rights_string(&self, shredder: bool) -> String {
+        let mut castle_rights_string = String::new();
+        for color in ALL_COLORS {
+            for (side, side_char) in [
+                (CastleRights::KingSide, 'k'),
+                (CastleRights::QueenSide, 'q'),
+            ] {
+                if self.get_castle_rights(color).to_index() & side.to_index() == 0 {
+                    continue;
+                }
+                let file = self.get_castling_rook_file(color, side);
+                let c = if shredder || self.get_outermost_rook_file(color, side) != Some(file) {
+                    file.to_string()
+                } else {
+                    side_char.to_string()
+                };
+                if color == White {
+                    castle_rights_string += &c.to_uppercase();
+                } else {
+                    castle_rights_string += &c;
+                }
+            }
+        }
+        if castle_rights_string.is_empty() {
+            castle_rights_string += "-";
+        }
+        castle_rights_string
+    }
+
+    fn get_fen_with_castle_rights_format(&self, shredder: bool) -> String {
+        let mut fen = String::new();
         let mut count = 0;
         for &rank in ALL_RANKS.iter().rev() {
             for file in ALL_FILES {
                 let square = Square::from_rank_and_file(rank, file).to_index();
 
                 if self.pieces[square].is_some() && count != 0 {
-                    write!(f, "{}", count)?;
+                    fen += &count.to_string();
                     count = 0;
                 }
 
                 if let Some(piece) = self.pieces[square] {
-                    write!(f, "{piece}")?;
+                    fen += &piece.to_string();
+                    if self.variant == Variant::Crazyhouse
+                        && self.promoted.contains(Square::from_index(square))
+                    {
+                        fen += "~";
+                    }
                 } else {
                     count += 1;
                 }
             }
 
             if count != 0 {
-                write!(f, "{}", count)?;
+                fen += &count.to_string();
             }
 
             if rank != Rank::First {
-                write!(f, "/")?;
+                fen += "/";
             }
             count = 0;
         }
 
-        write!(f, " ")?;
+        if self.variant == Variant::Crazyhouse {
+            fen += &format!("[{}]", self.get_pockets_string());
+        }
 
-        if self.turn == White {
-            write!(f, "w ")?;
+        fen += if self.turn == White { " w " } else { " b " };
+        fen += &self.get_castle_rights_string(shredder);
+        fen += " ";
+        if let Some(square) = self.get_en_passant() {
+            fen += &square.to_string();
         } else {
-            write!(f, "b ")?;
+            fen += "-";
         }
+        if self.variant == Variant::ThreeCheck {
+            fen += &format!(
+                " {}+{}",
+                self.get_remaining_checks(White),
+                self.get_remaining_checks(Black)
+            );
+        }
+        fen + &format!(" {} {}", self.halfmove_clock, self.fullmove_number)
+    }
 
-        write!(
-            f,
-            "{}",
-            self.castle_rights[White.to_index()].to_string(White)
-        )?;
-        write!(
-            f,
-            "{}",
-            self.castle_rights[Black.to_index()].to_string(Black)
-        )?;
-        if self.castle_rights[0] == CastleRights::None
-            && self.castle_rights[1] == CastleRights::None
-        {
-            write!(f, "-")?;
+    /// FEN with the castle rights written as rook files, as in Shredder-FEN.
+    #[inline]
+    pub fn get_shredder_fen(&self) -> String {
+        self.get_fen_with_castle_rights_format(true)
+    }
+}
+
+impl BoardPositionBuilder {
+    /// Back rank pieces of the Chess960 starting position with the given Scharnagl index, 518 being the standard starting position.
+    pub fn get_chess960_back_rank(index: u16) -> Result<[PieceType; 8]> {
+        if index >= 960 {
+            return Err(TimecatError::InvalidChess960Index { index });
         }
+        let mut back_rank = [None; 8];
+        let mut n = index as usize;
+        back_rank[2 * (n % 4) + 1] = Some(Bishop);
+        n /= 4;
+        back_rank[2 * (n % 4)] = Some(Bishop);
+        n /= 4;
+        let mut place_on_empty_file = |piece_type: PieceType, nth_empty_file: usize| {
+            let file = (0..8)
+                .filter(|&file| back_rank[file].is_none())
+                .nth(nth_empty_file)
+                .unwrap();
+            back_rank[file] = Some(piece_type);
+        };
+        place_on_empty_file(Queen, n % 6);
+        n /= 6;
+        let (first_knight, second_knight) = [
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (0, 4),
+            (1, 2),
+            (1, 3),
+            (1, 4),
+            (2, 3),
+            (2, 4),
+            (3, 4),
+        ][n];
+        // the second knight is placed first so that the first one still counts the same empty files
+        place_on_empty_file(Knight, second_knight);
+        place_on_empty_file(Knight, first_knight);
+        for piece_type in [Rook, King, Rook] {
+            place_on_empty_file(piece_type, 0);
+        }
+        Ok(back_rank.map(Option::unwrap))
+    }
 
-        write!(f, " ")?;
-        if let Some(square) = self.get_en_passant() {
-            write!(f, "{}", square)?;
-        } else {
-            write!(f, "-")?;
+    /// Double Fischer Random Chess starting position, where both colors get their own Chess960 back rank.
+    pub fn from_double_chess960_indices(white_index: u16, black_index: u16) -> Result<Self> {
+        let mut position_builder = Self::new();
+        position_builder.chess960(true);
+        for (color, index) in [(White, white_index), (Black, black_index)] {
+            let back_rank = Self::get_chess960_back_rank(index)?;
+            for (file, piece_type) in ALL_FILES.into_iter().zip(back_rank) {
+                position_builder
+                    .add_piece(
+                        Square::from_rank_and_file(color.to_my_backrank(), file),
+                        Piece::new(piece_type, color),
+                    )
+                    .add_piece(
+                        Square::from_rank_and_file(color.to_second_rank(), file),
+                        Piece::new(Pawn, color),
+                    );
+            }
+            let mut rook_files = ALL_FILES
+                .into_iter()
+                .filter(|&file| back_rank[file.to_index()] == Rook);
+            position_builder
+                .castle_rights(color, CastleRights::Both)
+                .castling_rook_file(color, CastleRights::QueenSide, rook_files.next().unwrap())
+                .castling_rook_file(color, CastleRights::KingSide, rook_files.next().unwrap());
         }
+        Ok(position_builder)
+    }
 
-        write!(f, " {} {}", self.halfmove_clock, self.fullmove_number)
+    #[inline]
+    pub fn from_chess960_index(index: u16) -> Result<Self> {
+        Self::from_double_chess960_indices(index, index)
+    }
+}
+
+impl Index<Square> for BoardPositionBuilder {
+    type Output = Option<Piece>;
+
+    fn index(&self, index: Square) -> &Self::Output {
+        &self.pieces[index.to_index()]
+    }
+}
+
+impl IndexMut<Square> for BoardPositionBuilder {
+    fn index_mut(&mut self, index: Square) -> &mut Self::Output {
+        &mut self.pieces[index.to_index()]
+    }
+}
+
+impl fmt::Display for BoardPositionBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.get_fen_with_castle_rights_format(false))
     }
 }
 
@@ -201,14 +532,45 @@ impl FromStr for BoardPositionBuilder {
         let mut cur_file = File::A;
         let mut position_builder = BoardPositionBuilder::new();
 
-        let tokens: Vec<&str> = value.split(' ').collect();
+        let mut tokens: Vec<&str> = value.split(' ').collect();
         if tokens.len() < 4 {
             return Err(TimecatError::BadFen {
                 fen: value.to_string(),
             });
         }
+        let check_counters = [
+            tokens
+                .last()
+                .filter(|token| token.starts_with('+'))
+                .copied(),
+            tokens.get(4).filter(|token| token.contains('+')).copied(),
+        ];
+        tokens.retain(|token| !token.contains('+'));
+        for counters in check_counters.into_iter().flatten() {
+            position_builder
+                .parse_check_counters(counters)
+                .map_err(|_| TimecatError::BadFen {
+                    fen: value.to_string(),
+                })?;
+        }
 
-        let pieces = tokens[0];
+        let (pieces, pockets) = match tokens[0].split_once('[') {
+            Some((pieces, pockets)) => (pieces, pockets.strip_suffix(']')),
+            // The pockets can also be written as a ninth rank.
+            None if tokens[0].matches('/').count() == 8 => tokens[0]
+                .rsplit_once('/')
+                .map_or((tokens[0], None), |(pieces, pockets)| {
+                    (pieces, Some(pockets))
+                }),
+            None => (tokens[0], None),
+        };
+        if let Some(pockets) = pockets {
+            position_builder
+                .parse_pockets(pockets)
+                .map_err(|_| TimecatError::BadFen {
+                    fen: value.to_string(),
+                })?;
+        }
         let side = tokens[1];
         let castles = tokens[2];
         let ep = tokens[3];
@@ -285,6 +647,15 @@ impl FromStr for BoardPositionBuilder {
                         Some(WhiteKing);
                     cur_file = cur_file.wrapping_right();
                 }
+                '~' => {
+                    let square = Square::from_rank_and_file(cur_rank, cur_file.wrapping_left());
+                    if position_builder[square].is_none() {
+                        return Err(TimecatError::BadFen {
+                            fen: value.to_string(),
+                        });
+                    }
+                    position_builder.promoted ^= square.to_bitboard();
+                }
                 _ => {
                     return Err(TimecatError::BadFen {
                         fen: value.to_string(),
@@ -302,25 +673,11 @@ impl FromStr for BoardPositionBuilder {
             }
         }
 
-        if castles.contains('K') && castles.contains('Q') {
-            position_builder.castle_rights[White.to_index()] = CastleRights::Both;
-        } else if castles.contains('K') {
-            position_builder.castle_rights[White.to_index()] = CastleRights::KingSide;
-        } else if castles.contains('Q') {
-            position_builder.castle_rights[White.to_index()] = CastleRights::QueenSide;
-        } else {
-            position_builder.castle_rights[White.to_index()] = CastleRights::None;
-        }
-
-        if castles.contains('k') && castles.contains('q') {
-            position_builder.castle_rights[Black.to_index()] = CastleRights::Both;
-        } else if castles.contains('k') {
-            position_builder.castle_rights[Black.to_index()] = CastleRights::KingSide;
-        } else if castles.contains('q') {
-            position_builder.castle_rights[Black.to_index()] = CastleRights::QueenSide;
-        } else {
-            position_builder.castle_rights[Black.to_index()] = CastleRights::None;
-        }
+        position_builder
+            .parse_castle_rights(castles)
+            .map_err(|_| TimecatError::BadFen {
+                fen: value.to_string(),
+            })?;
 
         if let Ok(square) = Square::from_str(ep) {
             position_builder.ep_file(Some(square.get_file()));
@@ -343,7 +700,7 @@ impl From<&BoardPosition> for BoardPositionBuilder {
             }
         }
 
-        BoardPositionBuilder::setup(
+        let mut position_builder = BoardPositionBuilder::setup(
             pieces,
             board.turn(),
             board.castle_rights(White),
@@ -351,7 +708,30 @@ impl From<&BoardPosition> for BoardPositionBuilder {
             board.ep_square().map(|square| square.get_file()),
             board.get_halfmove_clock(),
             board.get_fullmove_number(),
-        )
+        );
+        for color in ALL_COLORS {
+            for side in [CastleRights::KingSide, CastleRights::QueenSide] {
+                position_builder.castling_rook_file(
+                    color,
+                    side,
+                    board.get_castling_rook_square(color, side).get_file(),
+                );
+            }
+        }
+        position_builder.chess960(board.is_chess960());
+        position_builder.variant(board.get_variant());
+        for color in ALL_COLORS {
+            position_builder.remaining_checks(color, board.get_remaining_checks(color));
+            for piece_type in ALL_PIECE_TYPES {
+                position_builder.pocket_count(
+                    color,
+                    piece_type,
+                    board.get_pocket_count(color, piece_type),
+                );
+            }
+        }
+        position_builder.promoted_pieces_mask(board.get_promoted_pieces_mask());
+        position_builder
     }
 }
 
@@ -0,0 +1,220 @@
+use super::*;
+
+/// Rules the game is played with.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Default)]
+pub enum Variant {
+    #[default]
+    Standard = 0,
+    /// Giving the third check wins the game.
+    ThreeCheck = 1,
+    /// Bringing the king to one of the four central squares wins the game.
+    KingOfTheHill = 2,
+    /// Captured pieces go to the pocket of the capturing side, from where they can be dropped back on the board.
+    Crazyhouse = 3,
+    /// Captures explode the capturing piece and all the pieces around, except the pawns.
+    Atomic = 4,
+    /// Captures are compulsory and losing all the pieces or being stalemated wins the game, the king being an ordinary piece.
+    Antichess = 5,
+    /// White has a horde of pawns and no king, and black wins by capturing all of them.
+    Horde = 6,
+    /// Checks are not allowed and the first king to reach the eighth rank wins the game.
+    RacingKings = 7,
+}
+
+impl Variant {
+    #[inline]
+    pub const fn to_int(self) -> u8 {
+        self as u8
+    }
+
+    #[inline]
+    pub const fn to_index(self) -> usize {
+        self as usize
+    }
+
+    /// Convert `u8` to `Variant`.  Panic if invalid number.
+    #[inline]
+    pub const fn from_int(i: u8) -> Self {
+        ALL_VARIANTS[i as usize]
+    }
+
+    /// Name used by the `UCI_Variant` option, as sent by the GUIs.
+    pub const fn get_uci_name(self) -> &'static str {
+        match self {
+            Self::Standard => "chess",
+            Self::ThreeCheck => "3check",
+            Self::KingOfTheHill => "kingofthehill",
+            Self::Crazyhouse => "crazyhouse",
+            Self::Atomic => "atomic",
+            Self::Antichess => "antichess",
+            Self::Horde => "horde",
+            Self::RacingKings => "racingkings",
+        }
+    }
+
+    pub const fn get_starting_fen(self) -> &'static str {
+        match self {
+            Self::ThreeCheck => "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 3+3 0 1",
+            Self::Crazyhouse => "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1",
+            Self::Antichess => "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1",
+            Self::Horde => {
+                "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1"
+            }
+            Self::RacingKings => "8/8/8/8/8/8/krbnNBRK/qrbnNBRQ w - - 0 1",
+            _ => STARTING_POSITION_FEN,
+        }
+    }
+
+    /// Whether the king can be checked, which is not the case in Antichess where it is an ordinary piece.
+    #[inline]
+    pub const fn has_checks(self) -> bool {
+        !matches!(self, Self::Antichess)
+    }
+
+    /// Whether the side to move wins instead of drawing when it has no legal moves and is not in check.
+    #[inline]
+    pub const fn is_stalemate_win(self) -> bool {
+        matches!(self, Self::Antichess)
+    }
+
+    #[inline]
+    pub const fn get_promotion_pieces(self) -> &'static [PieceType] {
+        match self {
+            Self::Antichess => &ANTICHESS_PROMOTION_PIECES,
+            _ => &PROMOTION_PIECES,
+        }
+    }
+
+    /// Status of the game ended by the rules of the variant, which does not need the legal moves to be generated.
+    pub fn get_status(self, position: &BoardPosition) -> Option<BoardStatus> {
+        match self {
+            Self::Standard | Self::Crazyhouse | Self::Antichess => None,
+            Self::Horde => position
+                .self_occupied()
+                .is_empty()
+                .then_some(BoardStatus::AllPiecesCaptured),
+            Self::ThreeCheck => (position.get_remaining_checks(!position.turn()) == 0)
+                .then_some(BoardStatus::ThreeCheck),
+            Self::Atomic => position
+                .get_colored_piece_mask(King, position.turn())
+                .is_empty()
+                .then_some(BoardStatus::KingExploded),
+            Self::KingOfTheHill => {
+                (!(position.get_colored_piece_mask(King, !position.turn()) & BB_CENTER).is_empty())
+                    .then_some(BoardStatus::KingOfTheHill)
+            }
+            Self::RacingKings => Self::get_racing_kings_status(position),
+        }
+    }
+
+    /// White reaching the eighth rank first only wins if black cannot reach it on the next move, in
+    /// which case the game is drawn.
+    fn get_racing_kings_status(position: &BoardPosition) -> Option<BoardStatus> {
+        let is_in_goal =
+            |color| !(position.get_colored_piece_mask(King, color) & BB_RANK_8).is_empty();
+        match (is_in_goal(White), is_in_goal(Black)) {
+            (false, false) => None,
+            (true, true) => Some(BoardStatus::RacingKings(None)),
+            (false, true) => Some(BoardStatus::RacingKings(Some(Black))),
+            (true, false) => {
+                let black_king = position.get_colored_piece_mask(King, Black);
+                // The black king does not block the sliders attacking the squares it moves to.
+                let occupied = position.occupied() ^ black_king;
+                let black_can_reach_goal = position.turn() == Black
+                    && black_king.to_square().is_some_and(|square| {
+                        (square.get_king_moves() & BB_RANK_8 & !position.get_black_occupied())
+                            .into_iter()
+                            .any(|target| {
+                                position
+                                    .get_attackers_mask_with_occupied(target, White, occupied)
+                                    .is_empty()
+                            })
+                    });
+                (!black_can_reach_goal).then_some(BoardStatus::RacingKings(Some(White)))
+            }
+        }
+    }
+
+    /// Score added to the evaluation for the goal of the variant, from white's point of view.
+    pub fn evaluate(self, position: &BoardPosition) -> Score {
+        match self {
+            // Antichess and Horde have their own evaluators.
+            Self::Standard | Self::Atomic | Self::Antichess | Self::Horde => 0,
+            Self::ThreeCheck => {
+                const CHECKS_GIVEN_SCORES: [Score; 4] = [0, 6 * PAWN_VALUE, 2 * PAWN_VALUE, 0];
+                CHECKS_GIVEN_SCORES[position.get_remaining_checks(White) as usize]
+                    - CHECKS_GIVEN_SCORES[position.get_remaining_checks(Black) as usize]
+            }
+            Self::KingOfTheHill => {
+                let center_distance_score = |color| {
+                    let king_square = position.get_king_square(color);
+                    let distance = BB_CENTER
+                        .map(|square| king_square.distance(square))
+                        .min()
+                        .unwrap_or_default() as Score;
+                    (3 - distance) * (3 - distance) * PAWN_VALUE / 3
+                };
+                center_distance_score(White) - center_distance_score(Black)
+            }
+            Self::RacingKings => {
+                (position.get_king_square(White).get_rank().to_int() as Score
+                    - position.get_king_square(Black).get_rank().to_int() as Score)
+                    * PAWN_VALUE
+            }
+            Self::Crazyhouse => {
+                // Pieces in hand are worth a bit more than the ones on the board as they can be dropped anywhere.
+                let pocket_score = |color| {
+                    ALL_PIECE_TYPES
+                        .into_iter()
+                        .map(|piece_type| {
+                            position.get_pocket_count(color, piece_type) as Score
+                                * piece_type.evaluate()
+                        })
+                        .sum::<Score>()
+                        * 11
+                        / 10
+                };
+                pocket_score(White) - pocket_score(Black)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Standard => "Standard",
+                Self::ThreeCheck => "Three-check",
+                Self::KingOfTheHill => "King of the Hill",
+                Self::Crazyhouse => "Crazyhouse",
+                Self::Atomic => "Atomic",
+                Self::Antichess => "Antichess",
+                Self::Horde => "Horde",
+                Self::RacingKings => "Racing Kings",
+            }
+        )
+    }
+}
+
+impl FromStr for Variant {
+    type Err = TimecatError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().replace(['-', '_', ' '], "").as_str() {
+            "chess" | "standard" | "normal" => Ok(Self::Standard),
+            "3check" | "threecheck" => Ok(Self::ThreeCheck),
+            "kingofthehill" | "koth" => Ok(Self::KingOfTheHill),
+            "crazyhouse" | "zh" => Ok(Self::Crazyhouse),
+            "atomic" => Ok(Self::Atomic),
+            "antichess" | "losingchess" | "giveaway" => Ok(Self::Antichess),
+            "horde" => Ok(Self::Horde),
+            "racingkings" | "racing" => Ok(Self::RacingKings),
+            _ => Err(TimecatError::InvalidVariantString { s: s.to_string() }),
+        }
+    }
+}
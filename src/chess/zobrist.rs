@@ -246,6 +246,47 @@ const ZOBRIST_EP: [u64; NUM_FILES] = [
     0x67A34DAC4356550B,
 ];
 
+/// Keys for the checks remaining in Three-check, the key of 3 remaining checks being 0 so that the hashes of the other variants are unaffected.
+const ZOBRIST_REMAINING_CHECKS: [[u64; 4]; NUM_COLORS] = [
+    [
+        0x2D6B2D34E0CA1E83,
+        0x8A6F5C12B4E3D7A9,
+        0x5C1E9B7F03A24D66,
+        0,
+    ],
+    [
+        0xB3E8174C9D5F20A1,
+        0x47D9A2E6C18B3F5D,
+        0xE0F4386B72C9A15E,
+        0,
+    ],
+];
+
+/// Keys for the number of pieces of each type in the pockets of Crazyhouse, the key of an empty pocket being 0.
+const ZOBRIST_POCKETS: [[[u64; MAX_POCKET_COUNT + 1]; NUM_COLORS]; NUM_PIECE_TYPES] = {
+    // splitmix64
+    let mut state: u64 = 0x9E3779B97F4A7C15;
+    let mut array = [[[0; MAX_POCKET_COUNT + 1]; NUM_COLORS]; NUM_PIECE_TYPES];
+    let mut piece_index = 0;
+    while piece_index < NUM_PIECE_TYPES {
+        let mut color_index = 0;
+        while color_index < NUM_COLORS {
+            let mut count = 1;
+            while count <= MAX_POCKET_COUNT {
+                state = state.wrapping_add(0x9E3779B97F4A7C15);
+                let mut key = state;
+                key = (key ^ (key >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+                key = (key ^ (key >> 27)).wrapping_mul(0x94D049BB133111EB);
+                array[piece_index][color_index][count] = key ^ (key >> 31);
+                count += 1;
+            }
+            color_index += 1;
+        }
+        piece_index += 1;
+    }
+    array
+};
+
 const TURN: u64 = 0xF8D626AAAF278509;
 
 pub struct Zobrist;
@@ -276,6 +317,29 @@ impl Zobrist {
         *get_item_unchecked!(ZOBRIST_EP, file.to_index())
     }
 
+    #[inline]
+    pub fn remaining_checks(white_remaining_checks: u8, black_remaining_checks: u8) -> u64 {
+        *get_item_unchecked!(
+            ZOBRIST_REMAINING_CHECKS,
+            White.to_index(),
+            white_remaining_checks as usize
+        ) ^ *get_item_unchecked!(
+            ZOBRIST_REMAINING_CHECKS,
+            Black.to_index(),
+            black_remaining_checks as usize
+        )
+    }
+
+    #[inline]
+    pub fn pocket(piece: PieceType, color: Color, count: u8) -> u64 {
+        *get_item_unchecked!(
+            ZOBRIST_POCKETS,
+            piece.to_index(),
+            color.to_index(),
+            (count as usize).min(MAX_POCKET_COUNT),
+        )
+    }
+
     #[inline]
     pub fn color(color: Color) -> u64 {
         *get_item_unchecked!(const [TURN, 0], color.to_index())
@@ -0,0 +1,444 @@
+use super::*;
+use std::io::{BufRead, LineWriter};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+
+const MESSAGE_SEPARATOR: char = '|';
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub enum ClusterAddress {
+    Tcp(SocketAddr),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl fmt::Display for ClusterAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(address) => write!(f, "tcp:{address}"),
+            #[cfg(unix)]
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+impl FromStr for ClusterAddress {
+    type Err = TimecatError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let error = || TimecatError::InvalidClusterAddress { s: s.to_string() };
+        let (protocol, address) = s.trim().split_once(':').ok_or_else(error)?;
+        match protocol.to_lowercase().as_str() {
+            "tcp" => Ok(Self::Tcp(
+                address
+                    .to_socket_addrs()
+                    .ok()
+                    .and_then(|mut addresses| addresses.next())
+                    .ok_or_else(error)?,
+            )),
+            #[cfg(unix)]
+            "unix" if !address.is_empty() => Ok(Self::Unix(PathBuf::from(address))),
+            _ => Err(error()),
+        }
+    }
+}
+
+enum ClusterStream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl ClusterStream {
+    fn connect(address: &ClusterAddress) -> Result<Self> {
+        Ok(match address {
+            ClusterAddress::Tcp(address) => Self::Tcp(TcpStream::connect(address)?),
+            #[cfg(unix)]
+            ClusterAddress::Unix(path) => Self::Unix(UnixStream::connect(path)?),
+        })
+    }
+
+    fn try_clone(&self) -> Result<Self> {
+        Ok(match self {
+            Self::Tcp(stream) => Self::Tcp(stream.try_clone()?),
+            #[cfg(unix)]
+            Self::Unix(stream) => Self::Unix(stream.try_clone()?),
+        })
+    }
+
+    fn send(&mut self, message: impl fmt::Display) -> Result<()> {
+        let mut writer = LineWriter::new(self);
+        writeln!(writer, "{message}")?;
+        Ok(())
+    }
+}
+
+impl Read for ClusterStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for ClusterStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.flush(),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+// The requests only live until they are sent or handled.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ClusterRequest {
+    Search {
+        variant: Variant,
+        chess960: bool,
+        starting_fen: String,
+        moves: Vec<ValidOrNullMove>,
+        config: SearchConfig,
+    },
+    Quit,
+}
+
+impl ClusterRequest {
+    pub fn from_board(board: &Board, config: SearchConfig) -> Self {
+        Self::Search {
+            variant: board.get_variant(),
+            chess960: board.is_chess960(),
+            starting_fen: board.get_starting_board_fen(),
+            moves: board.get_all_stack_moves(),
+            config,
+        }
+    }
+
+    /// The variant and the chess960 flag are given explicitly as they cannot always be recovered from the fen.
+    pub fn generate_board(
+        variant: Variant,
+        chess960: bool,
+        starting_fen: &str,
+        moves: &[ValidOrNullMove],
+    ) -> Result<Board> {
+        let mut position_builder = BoardPositionBuilder::from_str(starting_fen)?;
+        position_builder.variant(variant).chess960(chess960);
+        let mut board = Board::from(BoardPosition::try_from(position_builder)?);
+        for &valid_or_null_move in moves {
+            board.push(valid_or_null_move)?;
+        }
+        Ok(board)
+    }
+}
+
+impl fmt::Display for ClusterRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Search {
+                variant,
+                chess960,
+                starting_fen,
+                moves,
+                config,
+            } => write!(
+                f,
+                "search{0}{1}{0}{chess960}{0}{starting_fen}{0}{2}{0}{config}",
+                MESSAGE_SEPARATOR,
+                variant.get_uci_name(),
+                moves.iter().join(" "),
+            ),
+            Self::Quit => write!(f, "quit"),
+        }
+    }
+}
+
+impl FromStr for ClusterRequest {
+    type Err = TimecatError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parts = s.trim().split(MESSAGE_SEPARATOR).collect_vec();
+        match parts.as_slice() {
+            ["quit"] => Ok(Self::Quit),
+            ["search", variant, chess960, starting_fen, moves, config] => Ok(Self::Search {
+                variant: variant.parse()?,
+                chess960: chess960.parse()?,
+                starting_fen: starting_fen.to_string(),
+                moves: moves
+                    .split_whitespace()
+                    .map(ValidOrNullMove::from_str)
+                    .collect::<Result<_>>()?,
+                config: config.parse()?,
+            }),
+            _ => Err(TimecatError::InvalidClusterMessage { s: s.to_string() }),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ClusterResponse {
+    score: Score,
+    depth: Depth,
+    seldepth: Ply,
+    nodes: usize,
+    time_elapsed: Duration,
+    pv: Vec<Move>,
+}
+
+impl ClusterResponse {
+    fn from_search_info(search_info: &SearchInfo) -> Self {
+        Self {
+            score: search_info.get_score().unwrap_or_default(),
+            depth: search_info.get_current_depth().unwrap_or_default(),
+            seldepth: search_info.get_seldepth().unwrap_or_default(),
+            nodes: search_info.get_num_nodes_searched().unwrap_or_default(),
+            time_elapsed: search_info.get_time_elapsed().unwrap_or_default(),
+            pv: search_info.get_pv().to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for ClusterResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "result{0}{1}{0}{2}{0}{3}{0}{4}{0}{5}{0}{6}",
+            MESSAGE_SEPARATOR,
+            self.score,
+            self.depth,
+            self.seldepth,
+            self.nodes,
+            self.time_elapsed.as_millis(),
+            self.pv.iter().join(" "),
+        )
+    }
+}
+
+impl FromStr for ClusterResponse {
+    type Err = TimecatError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parts = s.trim().split(MESSAGE_SEPARATOR).collect_vec();
+        match parts.as_slice() {
+            ["result", score, depth, seldepth, nodes, time_elapsed, pv] => Ok(Self {
+                score: score.parse()?,
+                depth: depth.parse()?,
+                seldepth: seldepth.parse()?,
+                nodes: nodes.parse()?,
+                time_elapsed: Duration::from_millis(time_elapsed.parse()?),
+                pv: pv
+                    .split_whitespace()
+                    .map(Move::from_str)
+                    .collect::<Result<_>>()?,
+            }),
+            ["error", err_msg] => Err(TimecatError::CustomError {
+                err_msg: err_msg.to_string(),
+            }),
+            _ => Err(TimecatError::InvalidClusterMessage { s: s.to_string() }),
+        }
+    }
+}
+
+enum ClusterListener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener, PathBuf),
+}
+
+/// Worker side of the cluster mode, searches the root moves assigned by a [`ClusterCoordinator`].
+pub struct ClusterWorker {
+    listener: ClusterListener,
+}
+
+impl ClusterWorker {
+    pub fn bind(address: &ClusterAddress) -> Result<Self> {
+        let listener = match address {
+            ClusterAddress::Tcp(address) => ClusterListener::Tcp(TcpListener::bind(address)?),
+            #[cfg(unix)]
+            ClusterAddress::Unix(path) => {
+                ClusterListener::Unix(UnixListener::bind(path)?, path.to_owned())
+            }
+        };
+        Ok(Self { listener })
+    }
+
+    pub fn get_local_address(&self) -> Result<ClusterAddress> {
+        Ok(match &self.listener {
+            ClusterListener::Tcp(listener) => ClusterAddress::Tcp(listener.local_addr()?),
+            #[cfg(unix)]
+            ClusterListener::Unix(_, path) => ClusterAddress::Unix(path.to_owned()),
+        })
+    }
+
+    fn accept(&self) -> Result<ClusterStream> {
+        Ok(match &self.listener {
+            ClusterListener::Tcp(listener) => ClusterStream::Tcp(listener.accept()?.0),
+            #[cfg(unix)]
+            ClusterListener::Unix(listener, _) => ClusterStream::Unix(listener.accept()?.0),
+        })
+    }
+
+    fn search(engine: &mut impl ChessEngine, request_string: &str) -> Result<Option<String>> {
+        let (board, config) = match request_string.parse()? {
+            ClusterRequest::Search {
+                variant,
+                chess960,
+                starting_fen,
+                moves,
+                config,
+            } => (
+                ClusterRequest::generate_board(variant, chess960, &starting_fen, &moves)?,
+                config,
+            ),
+            ClusterRequest::Quit => return Ok(None),
+        };
+        engine.set_position(board)?;
+        let search_info = engine.go_quiet(&config);
+        Ok(Some(
+            ClusterResponse::from_search_info(&search_info).to_string(),
+        ))
+    }
+
+    /// Serves coordinators one connection at a time until a quit request is received.
+    pub fn serve(&self, engine: &mut impl ChessEngine) -> Result<()> {
+        engine.set_opening_book::<PolyglotBookHashMap>(None);
+        loop {
+            let mut stream = self.accept()?;
+            let reader = BufReader::new(stream.try_clone()?);
+            for line in reader.lines() {
+                match Self::search(engine, &line?) {
+                    Ok(Some(response)) => stream.send(response)?,
+                    Ok(None) => return Ok(()),
+                    Err(error) => stream.send(format!("error{MESSAGE_SEPARATOR}{error}"))?,
+                }
+            }
+        }
+    }
+}
+
+impl Drop for ClusterWorker {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let ClusterListener::Unix(_, path) = &self.listener {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Coordinator side of the cluster mode, splits the root moves between the workers and merges their results.
+#[derive(Clone, Debug)]
+pub struct ClusterCoordinator {
+    workers: Vec<ClusterAddress>,
+}
+
+impl ClusterCoordinator {
+    pub fn new(workers: Vec<ClusterAddress>) -> Self {
+        Self { workers }
+    }
+
+    #[inline]
+    pub fn get_workers(&self) -> &[ClusterAddress] {
+        &self.workers
+    }
+
+    fn request(address: &ClusterAddress, request: &ClusterRequest) -> Result<ClusterResponse> {
+        let mut stream = ClusterStream::connect(address)?;
+        stream.send(request)?;
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line)?;
+        line.parse()
+            .map_err(|error: TimecatError| TimecatError::ClusterWorkerError {
+                address: address.to_string(),
+                err_msg: error.to_string(),
+            })
+    }
+
+    pub fn go(&self, board: &Board, config: &SearchConfig) -> Result<SearchInfo> {
+        if self.workers.is_empty() {
+            return Err(TimecatError::NoClusterWorkers);
+        }
+        if matches!(
+            config.get_go_command(),
+            GoCommand::Infinite | GoCommand::Ponder
+        ) {
+            return Err(TimecatError::InvalidGoCommand {
+                s: config.to_string(),
+            });
+        }
+        if board.is_game_over() {
+            return Err(TimecatError::GameAlreadyOver);
+        }
+        let root_moves = config
+            .get_moves_to_search()
+            .map(|moves| moves.to_vec())
+            .unwrap_or_else(|| board.generate_legal_moves().collect_vec());
+        let num_workers = self.workers.len().min(root_moves.len());
+        let mut moves_split = vec![vec![]; num_workers];
+        for (index, move_) in root_moves.into_iter().enumerate() {
+            moves_split[index % num_workers].push(move_);
+        }
+        let clock = Instant::now();
+        let join_handles = self
+            .workers
+            .iter()
+            .zip(moves_split)
+            .map(|(address, moves)| {
+                let mut worker_config = config.clone();
+                worker_config.set_moves_to_search(moves);
+                let request = ClusterRequest::from_board(board, worker_config);
+                let address = address.to_owned();
+                thread::spawn(move || Self::request(&address, &request))
+            })
+            .collect_vec();
+        let responses = join_handles
+            .into_iter()
+            .map(|join_handle| join_handle.join().unwrap())
+            .collect::<Result<Vec<_>>>()?;
+        let best_response = responses
+            .iter()
+            .max_by_key(|response| board.score_flipped(response.score))
+            .unwrap();
+        Ok(
+            SearchInfoBuilder::new(board.get_position().to_owned(), best_response.pv.clone())
+                .set_score(best_response.score)
+                .set_current_depth(
+                    responses
+                        .iter()
+                        .map(|response| response.depth)
+                        .min()
+                        .unwrap(),
+                )
+                .set_seldepth(
+                    responses
+                        .iter()
+                        .map(|response| response.seldepth)
+                        .max()
+                        .unwrap(),
+                )
+                .set_nodes(responses.iter().map(|response| response.nodes).sum())
+                .set_time_elapsed(clock.elapsed())
+                .build(),
+        )
+    }
+
+    /// Asks every worker to stop serving.
+    pub fn quit_workers(&self) -> Result<()> {
+        for address in &self.workers {
+            ClusterStream::connect(address)?.send(ClusterRequest::Quit)?;
+        }
+        Ok(())
+    }
+}
@@ -11,6 +11,7 @@ pub mod types {
 
     pub type Ply = usize;
     pub type Depth = i8;
+    pub type FractionalDepth = i16;
     pub type Score = i16;
     pub type MoveWeight = i32;
     pub type NumMoves = u16;
@@ -198,6 +199,7 @@ pub mod evaluate {
 
     pub const ENDGAME_PIECE_THRESHOLD: u32 = 12;
     pub const CACHE_TABLE_SIZE: CacheTableSize = CacheTableSize::Exact(16);
+    pub const PAWN_HASH_TABLE_SIZE: CacheTableSize = CacheTableSize::Exact(4);
     pub const DRAW_SCORE: Score = PAWN_VALUE / 2;
     pub const CHECKMATE_SCORE: Score = 25_000;
     pub const CHECKMATE_THRESHOLD: Score = CHECKMATE_SCORE - MAX_PLY as Score - 1;
@@ -216,6 +218,11 @@ pub mod cache_table {
 
     // pub const DEFAULT_HASH: NonZeroU64 = NonZeroU64::new(1).unwrap();
     pub const DEFAULT_HASH: NonZeroU64 = unsafe { NonZeroU64::new_unchecked(1) };
+    /// Ratios in which the memory budget is split between the transposition table, the evaluation cache and the pawn hash table.
+    pub const MEMORY_BUDGET_RATIOS: [usize; 3] = [4, 4, 1];
+    pub const DEFAULT_MEMORY_BUDGET: usize = TIMECAT_DEFAULTS.t_table_size.unwrap()
+        + CACHE_TABLE_SIZE.unwrap()
+        + PAWN_HASH_TABLE_SIZE.unwrap();
 }
 
 pub mod engine {
@@ -223,6 +230,26 @@ pub mod engine {
 
     pub const NUM_KILLER_MOVES: usize = 3;
 
+    pub const TT_BUCKET_SIZE: usize = 3;
+    pub const TT_AGE_PENALTY: i32 = 8;
+    pub const TT_HASH_FULL_SAMPLE_SIZE: usize = 999 * TT_BUCKET_SIZE;
+    pub const TT_FILE_MAGIC: [u8; 4] = *b"TCTT";
+    pub const TT_FILE_VERSION: u32 = 1;
+    /// Needs to be bumped whenever the packing of the transposition table entries changes.
+    pub const TT_ENTRY_LAYOUT_VERSION: u32 = 1;
+
+    pub const EXPERIENCE_FILE_MAGIC: [u8; 4] = *b"TCEX";
+    pub const EXPERIENCE_FILE_VERSION: u32 = 1;
+    /// Searches shallower than this are not worth remembering.
+    pub const EXPERIENCE_MIN_DEPTH: Depth = 6;
+    pub const EXPERIENCE_RESULT_ADJUSTMENT: Score = PAWN_VALUE / 5;
+
+    /// A new position extending the previous game by at most these many plies keeps the search state.
+    pub const MAX_PLIES_TO_KEEP_SEARCH_STATE: Ply = 2;
+
+    /// Number of fractional depth units in one ply, the search tracks depth internally in these units.
+    pub const ONE_PLY: FractionalDepth = 16;
+
     pub const DISABLE_ALL_PRUNINGS: bool = false;
 
     pub const NULL_MOVE_MIN_DEPTH: Depth = 2;
@@ -278,6 +305,25 @@ pub mod color {
     pub const ALL_COLORS: [Color; NUM_COLORS] = [Color::White, Color::Black];
 }
 
+pub mod variant {
+    use super::*;
+
+    pub const NUM_VARIANTS: usize = 8;
+    pub const MAX_POCKET_COUNT: usize = 16;
+    pub const ALL_VARIANTS: [Variant; NUM_VARIANTS] = [
+        Variant::Standard,
+        Variant::ThreeCheck,
+        Variant::KingOfTheHill,
+        Variant::Crazyhouse,
+        Variant::Atomic,
+        Variant::Antichess,
+        Variant::Horde,
+        Variant::RacingKings,
+    ];
+    pub const ANTICHESS_PROMOTION_PIECES: [PieceType; NUM_PROMOTION_PIECES + 1] =
+        [Queen, Knight, Rook, Bishop, King];
+}
+
 pub mod piece {
     use super::*;
 
@@ -349,6 +395,11 @@ pub mod default_parameters {
         book_path: None,
         inbuilt_book_bytes: None,
         debug_mode: true,
+        verify_hash: false,
         chess960_mode: false,
+        variant: Variant::Standard,
+        use_experience: false,
+        experience_path: "timecat.exp",
+        checkpoint_interval: Duration::from_secs(60),
     };
 }
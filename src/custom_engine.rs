@@ -6,6 +6,11 @@ pub struct EngineProperties {
     _use_mate_distance_pruning: bool,
     _clear_table_after_each_search: bool,
     _use_lmr: bool,
+    _collect_search_statistics: bool,
+    _search_tree_config: Option<SearchTreeConfig>,
+    _use_experience: bool,
+    _checkpoint_path: Option<String>,
+    _checkpoint_interval: Duration,
 }
 
 impl EngineProperties {
@@ -32,14 +37,59 @@ impl EngineProperties {
     pub fn set_using_lmr(&mut self, value: bool) {
         self._use_lmr = value;
     }
+
+    pub fn collect_search_statistics(&self) -> bool {
+        self._collect_search_statistics
+    }
+
+    pub fn set_collecting_search_statistics(&mut self, value: bool) {
+        self._collect_search_statistics = value;
+    }
+
+    pub fn get_search_tree_config(&self) -> Option<&SearchTreeConfig> {
+        self._search_tree_config.as_ref()
+    }
+
+    pub fn set_search_tree_config(&mut self, config: Option<SearchTreeConfig>) {
+        self._search_tree_config = config;
+    }
+
+    pub fn use_experience(&self) -> bool {
+        self._use_experience
+    }
+
+    pub fn set_using_experience(&mut self, value: bool) {
+        self._use_experience = value;
+    }
+
+    pub fn get_checkpoint_path(&self) -> Option<&str> {
+        self._checkpoint_path.as_deref()
+    }
+
+    pub fn set_checkpoint_path(&mut self, path: Option<String>) {
+        self._checkpoint_path = path;
+    }
+
+    pub fn get_checkpoint_interval(&self) -> Duration {
+        self._checkpoint_interval
+    }
+
+    pub fn set_checkpoint_interval(&mut self, interval: Duration) {
+        self._checkpoint_interval = interval;
+    }
 }
 
 impl Default for EngineProperties {
     fn default() -> Self {
         Self {
             _use_mate_distance_pruning: true,
-            _clear_table_after_each_search: true,
+            _clear_table_after_each_search: false,
             _use_lmr: true,
+            _collect_search_statistics: false,
+            _search_tree_config: None,
+            _use_experience: TIMECAT_DEFAULTS.use_experience,
+            _checkpoint_path: None,
+            _checkpoint_interval: TIMECAT_DEFAULTS.checkpoint_interval,
         }
     }
 }
@@ -49,6 +99,7 @@ pub struct CustomEngine<T: SearchControl<Searcher<P>>, P: PositionEvaluation> {
     board: Board,
     #[cfg_attr(feature = "serde", serde(with = "SerdeHandler"))]
     transposition_table: Arc<TranspositionTable>,
+    is_sharing_transposition_table: bool,
     evaluator: P,
     controller: T,
     num_threads: NonZeroUsize,
@@ -63,20 +114,29 @@ pub struct CustomEngine<T: SearchControl<Searcher<P>>, P: PositionEvaluation> {
     #[cfg_attr(feature = "serde", serde(with = "SerdeHandler"))]
     terminate: Arc<AtomicBool>,
     properties: EngineProperties,
+    move_sorter: MoveSorter,
+    search_statistics: Option<SearchStatistics>,
+    search_tree: Option<SearchTree>,
     #[cfg_attr(feature = "serde", serde(skip))]
     opening_book: Option<Arc<dyn PolyglotBook>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    experience: Option<Experience>,
+    is_game_end_reported: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    progress_to_resume: Option<SearchProgress>,
 }
 
 impl<T: SearchControl<Searcher<P>>, P: PositionEvaluation> CustomEngine<T, P> {
     pub fn new(
         board: Board,
-        transposition_table: TranspositionTable,
+        transposition_table: impl Into<Arc<TranspositionTable>>,
         controller: T,
         evaluator: P,
     ) -> Self {
         Self {
             board,
             transposition_table: transposition_table.into(),
+            is_sharing_transposition_table: false,
             evaluator,
             controller,
             num_threads: TIMECAT_DEFAULTS.num_threads,
@@ -86,19 +146,59 @@ impl<T: SearchControl<Searcher<P>>, P: PositionEvaluation> CustomEngine<T, P> {
             stop_command: AtomicBool::new(false).into(),
             terminate: AtomicBool::new(false).into(),
             properties: EngineProperties::default(),
+            move_sorter: MoveSorter::new(),
+            search_statistics: None,
+            search_tree: None,
             opening_book: TIMECAT_DEFAULTS
                 .inbuilt_book_bytes
                 .map(|bytes| PolyglotBookHashMap::try_from(bytes).ok())
                 .flatten()
                 .map(|book| Arc::new(book) as Arc<dyn PolyglotBook>),
+            experience: None,
+            is_game_end_reported: false,
+            progress_to_resume: None,
         }
     }
 
     #[inline]
-    fn get_transposition_table(&self) -> &TranspositionTable {
+    pub fn get_transposition_table(&self) -> &TranspositionTable {
         &self.transposition_table
     }
 
+    /// Creates an engine using a transposition table and evaluator caches shared with other engines, which it never
+    /// clears, resizes or replaces, and never ages as the owner of the table starts the new generations.
+    pub fn new_sharing_transposition_table(
+        board: Board,
+        transposition_table: Arc<TranspositionTable>,
+        controller: T,
+        evaluator: P,
+    ) -> Self {
+        let mut engine = Self::new(board, transposition_table, controller, evaluator);
+        engine.is_sharing_transposition_table = true;
+        engine
+    }
+
+    /// Clone of the engine using the same transposition table and evaluator caches, both engines treat them as shared
+    /// from now on, unlike [`Clone::clone`] which copies the table.
+    pub fn share_transposition_table(&mut self) -> Self {
+        self.is_sharing_transposition_table = true;
+        self.clone_with_transposition_table(self.transposition_table.clone(), true)
+    }
+
+    /// Whether other engines use the same transposition table, like the ones created by an [`EnginePool`].
+    #[inline]
+    pub fn is_sharing_transposition_table(&self) -> bool {
+        self.is_sharing_transposition_table
+    }
+
+    /// Fails if the tables are shared, as they can only be resized or replaced by their owner.
+    fn ensure_tables_are_owned(&self) -> Result<()> {
+        if self.is_sharing_transposition_table {
+            return Err(TimecatError::SharedTablesNotModifiable);
+        }
+        Ok(())
+    }
+
     #[inline]
     pub fn get_search_controller(&self) -> &impl SearchControl<Searcher<P>> {
         &self.controller
@@ -149,12 +249,92 @@ impl<T: SearchControl<Searcher<P>>, P: PositionEvaluation> CustomEngine<T, P> {
         self.selective_depth.store(0, MEMORY_ORDERING);
         self.controller.reset_variables();
         self.evaluator.reset_variables();
-        if self.properties.clear_table_after_each_search() {
+        if self.properties.clear_table_after_each_search() && !self.is_sharing_transposition_table {
             self.transposition_table.clear();
         }
         self.transposition_table.reset_variables();
+        // A shared table is aged by its pool when a new game starts, as a search of one engine would
+        // otherwise age the entries of all the others.
+        if !self.is_sharing_transposition_table {
+            self.transposition_table.increment_generation();
+        }
         self.set_stop_command(false);
         self.set_termination(false);
+        self.progress_to_resume = None;
+    }
+
+    /// Forgets the current game, so that the result of the next game can be reported.
+    fn start_new_game(&mut self) {
+        self.is_game_end_reported = false;
+        if let Some(experience) = self.experience.as_mut() {
+            experience.start_new_game();
+        }
+    }
+
+    /// Clears everything learnt from the previous searches, used when the position does not continue the current game.
+    /// A shared transposition table is not cleared as the other engines still use it, its old entries are replaced over time instead.
+    pub fn clear_search_state(&mut self) {
+        if !self.is_sharing_transposition_table {
+            self.transposition_table.clear();
+        }
+        self.move_sorter.reset_variables();
+    }
+
+    /// Number of plies by which `board` extends the current game, if it does.
+    fn get_num_plies_continued_by(&self, board: &Board) -> Option<Ply> {
+        let current_moves = self.board.get_all_stack_moves();
+        let new_moves = board.get_all_stack_moves();
+        (new_moves.starts_with(&current_moves)
+            && self.board.get_starting_board_fen() == board.get_starting_board_fen())
+        .then(|| new_moves.len() - current_moves.len())
+    }
+
+    #[inline]
+    pub fn get_experience(&self) -> Option<&Experience> {
+        self.experience.as_ref()
+    }
+
+    /// Makes the best move found for the root position in the past games the first move to be searched. The stored
+    /// score is biased by the results of the games, so it is not used as a search result.
+    fn seed_from_experience(&self) {
+        if !self.properties.use_experience() {
+            return;
+        }
+        if let Some(entry) = self
+            .experience
+            .as_ref()
+            .and_then(|experience| experience.probe(self.board.get_position()))
+        {
+            self.transposition_table
+                .write_best_move(self.board.get_hash(), entry.get_best_move());
+        }
+    }
+
+    fn update_experience(&mut self, search_info: &SearchInfo) {
+        if !self.properties.use_experience() {
+            return;
+        }
+        let Some(experience) = self.experience.as_mut() else {
+            return;
+        };
+        let Some(iteration) = search_info
+            .get_iterations()
+            .iter()
+            .rfind(|iteration| iteration.get_bound() == EntryFlagHash::Exact)
+        else {
+            return;
+        };
+        let Some(best_move) = iteration.get_best_move() else {
+            return;
+        };
+        if let Err(err) = experience.record(
+            self.board.get_position(),
+            iteration.get_depth(),
+            self.board.score_flipped(iteration.get_score()),
+            best_move,
+        ) {
+            println_info("Experience Error", err);
+        }
     }
 
     #[inline]
@@ -168,6 +348,7 @@ impl<T: SearchControl<Searcher<P>>, P: PositionEvaluation> CustomEngine<T, P> {
             self.selective_depth.clone(),
             self.stop_command.clone(),
             self.properties.clone(),
+            self.move_sorter.clone(),
         )
     }
 
@@ -219,15 +400,69 @@ impl<T: SearchControl<Searcher<P>>, P: PositionEvaluation> ChessEngine for Custo
 
     fn set_fen(&mut self, fen: &str) -> Result<()> {
         self.get_board_mut().set_fen(fen)?;
+        self.start_new_game();
+        self.clear_search_state();
+        self.reset_variables();
+        Ok(())
+    }
+
+    fn set_position(&mut self, board: Board) -> Result<()> {
+        let optional_num_plies = self.get_num_plies_continued_by(&board);
+        if optional_num_plies.is_none() {
+            self.start_new_game();
+        }
+        match optional_num_plies {
+            Some(num_plies) if num_plies <= MAX_PLIES_TO_KEEP_SEARCH_STATE => {
+                self.move_sorter.age(num_plies)
+            }
+            _ => self.clear_search_state(),
+        }
+        self.board = board;
+        self.on_game_end(self.board.result());
         self.reset_variables();
         Ok(())
     }
 
-    fn set_transposition_table_size(&self, size: CacheTableSize) {
-        self.transposition_table.set_size(size);
+    fn set_transposition_table_size(&self, size: CacheTableSize) -> Result<()> {
+        self.ensure_tables_are_owned()?;
+        self.transposition_table.set_size(size)?;
+        if GLOBAL_TIMECAT_STATE.is_in_debug_mode() {
+            self.transposition_table.print_info();
+        }
+        Ok(())
+    }
+
+    fn set_evaluation_cache_size(&self, size: CacheTableSize) -> Result<()> {
+        self.ensure_tables_are_owned()?;
+        self.evaluator.set_evaluation_cache_size(size)?;
+        if GLOBAL_TIMECAT_STATE.is_in_debug_mode() {
+            self.evaluator.print_info();
+        }
+        Ok(())
+    }
+
+    fn set_pawn_hash_size(&self, size: CacheTableSize) -> Result<()> {
+        self.ensure_tables_are_owned()?;
+        self.evaluator.set_pawn_hash_size(size)?;
+        if GLOBAL_TIMECAT_STATE.is_in_debug_mode() {
+            self.evaluator.print_info();
+        }
+        Ok(())
+    }
+
+    fn set_memory_budget(&self, memory_manager: MemoryManager) -> Result<()> {
+        self.ensure_tables_are_owned()?;
+        memory_manager.apply_to_tables(&self.transposition_table, &self.evaluator)?;
         if GLOBAL_TIMECAT_STATE.is_in_debug_mode() {
             self.transposition_table.print_info();
+            self.evaluator.print_info();
         }
+        Ok(())
+    }
+
+    #[inline]
+    fn get_memory_usage(&self) -> usize {
+        self.transposition_table.get_memory_usage() + self.evaluator.get_memory_usage()
     }
 
     #[inline]
@@ -260,9 +495,85 @@ impl<T: SearchControl<Searcher<P>>, P: PositionEvaluation> ChessEngine for Custo
         self.terminate.store(b, MEMORY_ORDERING);
     }
 
+    /// Shared tables are left untouched, as the other engines still use them.
     fn clear_hash(&mut self) {
-        self.get_transposition_table().clear();
-        self.evaluator.clear();
+        if !self.is_sharing_transposition_table {
+            self.get_transposition_table().clear();
+            self.evaluator.clear();
+        }
+    }
+
+    fn set_experience_file(&mut self, path: &str) -> Result<()> {
+        let experience = Experience::load(path)?;
+        self.on_game_end(self.board.result());
+        self.experience = Some(experience);
+        Ok(())
+    }
+
+    fn set_using_experience(&mut self, b: bool) -> Result<()> {
+        if b && self.experience.is_none() {
+            self.experience = Some(Experience::load(TIMECAT_DEFAULTS.experience_path)?);
+        }
+        self.properties.set_using_experience(b);
+        Ok(())
+    }
+
+    /// Reports the result to the experience once per game, results of games in progress are ignored.
+    fn on_game_end(&mut self, result: GameResult) {
+        if self.is_game_end_reported || result.is_in_progress() {
+            return;
+        }
+        self.is_game_end_reported = true;
+        if let Some(experience) = self.experience.as_mut() {
+            if let Err(err) = experience.on_game_end(result) {
+                println_info("Experience Error", err);
+            }
+        }
+    }
+
+    #[inline]
+    fn save_hash(&self, path: &str) -> Result<()> {
+        self.transposition_table.save_to_file(path)
+    }
+
+    fn load_hash(&mut self, path: &str) -> Result<()> {
+        self.ensure_tables_are_owned()?;
+        self.transposition_table.load_from_file(path)?;
+        if GLOBAL_TIMECAT_STATE.is_in_debug_mode() {
+            self.transposition_table.print_info();
+        }
+        Ok(())
+    }
+
+    fn set_checkpoint_file(&mut self, path: Option<&str>) -> Result<()> {
+        if path.is_some() && cfg!(not(feature = "serde")) {
+            return Err(TimecatError::FeatureNotEnabled {
+                s: "serde".to_string(),
+            });
+        }
+        self.properties
+            .set_checkpoint_path(path.map(|path| path.to_string()));
+        Ok(())
+    }
+
+    #[inline]
+    fn set_checkpoint_interval(&mut self, interval: Duration) {
+        self.properties.set_checkpoint_interval(interval);
+    }
+
+    /// Restores the analysis saved in the checkpoint, which the next search continues. The analysis keeps being saved to the same file unless a checkpoint file is already set.
+    fn resume_from_checkpoint(&mut self, path: &str) -> Result<()> {
+        self.ensure_tables_are_owned()?;
+        let (board, move_sorter, progress) =
+            Checkpoint::load_from_file(path, &self.transposition_table)?.into_parts();
+        self.board = board;
+        self.start_new_game();
+        self.move_sorter = move_sorter;
+        self.progress_to_resume = Some(progress);
+        if self.properties.get_checkpoint_path().is_none() {
+            self.set_checkpoint_file(Some(path))?;
+        }
+        Ok(())
     }
 
     fn print_info(&self) {
@@ -270,6 +581,8 @@ impl<T: SearchControl<Searcher<P>>, P: PositionEvaluation> ChessEngine for Custo
         println_wasm!();
         self.transposition_table.print_info();
         self.evaluator.print_info();
+        print_memory_usage(self.get_memory_usage());
+        print_slider_attack_method();
     }
 
     #[inline]
@@ -277,6 +590,26 @@ impl<T: SearchControl<Searcher<P>>, P: PositionEvaluation> ChessEngine for Custo
         self.optional_io_reader = Some(optional_io_reader);
     }
 
+    #[inline]
+    fn get_search_statistics(&self) -> Option<&SearchStatistics> {
+        self.search_statistics.as_ref()
+    }
+
+    #[inline]
+    fn set_collecting_search_statistics(&mut self, b: bool) {
+        self.properties.set_collecting_search_statistics(b);
+    }
+
+    #[inline]
+    fn get_search_tree(&self) -> Option<&SearchTree> {
+        self.search_tree.as_ref()
+    }
+
+    #[inline]
+    fn set_search_tree_config(&mut self, config: Option<SearchTreeConfig>) {
+        self.properties.set_search_tree_config(config);
+    }
+
     #[inline]
     fn evaluate_current_position(&mut self) -> Score {
         self.evaluator.evaluate(&self.board)
@@ -289,12 +622,17 @@ impl<T: SearchControl<Searcher<P>>, P: PositionEvaluation> ChessEngine for Custo
 
     #[must_use = "If you don't need the search info, you can just search the position."]
     fn go(&mut self, config: &SearchConfig, verbose: bool) -> SearchInfo {
-        if let Some(WeightedMove { move_, weight }) = self.get_opening_book_weighted_move() {
-            return SearchInfoBuilder::new(self.board.get_position().clone(), vec![move_])
-                .set_score(weight as Score)
-                .build();
+        self.on_game_end(self.board.result());
+        let progress_to_resume = self.progress_to_resume.take();
+        if progress_to_resume.is_none() {
+            if let Some(WeightedMove { move_, weight }) = self.get_opening_book_weighted_move() {
+                return SearchInfoBuilder::new(self.board.get_position().clone(), vec![move_])
+                    .set_score(weight as Score)
+                    .build();
+            }
         }
         self.reset_variables();
+        self.seed_from_experience();
         let mut join_handles = vec![];
         for id in 1..self.num_threads.get() {
             let mut threaded_searcher = self.generate_searcher(id);
@@ -313,15 +651,34 @@ impl<T: SearchControl<Searcher<P>>, P: PositionEvaluation> ChessEngine for Custo
             }));
         }
         let mut main_thread_searcher = self.generate_searcher(0);
+        if let Some(progress) = progress_to_resume {
+            main_thread_searcher.resume_from(progress);
+        }
         main_thread_searcher.go(config, self.controller.clone(), verbose);
         self.set_stop_command(true);
         for join_handle in join_handles {
             join_handle.join().unwrap();
         }
+        self.move_sorter = main_thread_searcher.take_move_sorter();
+        self.search_statistics = main_thread_searcher.get_search_statistics().cloned();
+        self.search_tree = main_thread_searcher.take_search_tree();
+        if verbose {
+            if let Some(search_statistics) = self.search_statistics.as_ref() {
+                force_println_info("Search Statistics", search_statistics.to_json());
+            }
+        }
         let mut search_info = main_thread_searcher.get_search_info();
         if search_info.get_pv().is_empty() && self.board.status() == BoardStatus::Ongoing {
             search_info.set_pv(&[self.board.generate_legal_moves().next().unwrap()]);
         }
+        self.update_experience(&search_info);
+        if let Some(best_move) = search_info.get_best_move() {
+            if self.board.push(best_move).is_ok() {
+                let result = self.board.result();
+                self.board.pop();
+                self.on_game_end(result);
+            }
+        }
         search_info
     }
 }
@@ -343,11 +700,16 @@ impl<T: SearchControl<Searcher<P>> + Default, P: PositionEvaluation + Default> C
     }
 }
 
-impl<T: SearchControl<Searcher<P>>, P: PositionEvaluation> Clone for CustomEngine<T, P> {
-    fn clone(&self) -> Self {
+impl<T: SearchControl<Searcher<P>>, P: PositionEvaluation> CustomEngine<T, P> {
+    fn clone_with_transposition_table(
+        &self,
+        transposition_table: Arc<TranspositionTable>,
+        is_sharing_transposition_table: bool,
+    ) -> Self {
         Self {
             board: self.board.clone(),
-            transposition_table: self.transposition_table.as_ref().clone().into(),
+            transposition_table,
+            is_sharing_transposition_table,
             evaluator: self.evaluator.clone(),
             controller: self.controller.clone(),
             num_nodes_searched: AtomicUsize::new(self.num_nodes_searched.load(MEMORY_ORDERING))
@@ -357,12 +719,23 @@ impl<T: SearchControl<Searcher<P>>, P: PositionEvaluation> Clone for CustomEngin
             stop_command: AtomicBool::new(self.stop_command.load(MEMORY_ORDERING)).into(),
             terminate: AtomicBool::new(self.terminate.load(MEMORY_ORDERING)).into(),
             properties: self.properties.clone(),
+            move_sorter: self.move_sorter.clone(),
+            search_statistics: self.search_statistics.clone(),
+            search_tree: self.search_tree.clone(),
             opening_book: self.opening_book.clone(),
+            experience: self.experience.clone(),
+            progress_to_resume: self.progress_to_resume.clone(),
             ..*self
         }
     }
 }
 
+impl<T: SearchControl<Searcher<P>>, P: PositionEvaluation> Clone for CustomEngine<T, P> {
+    fn clone(&self) -> Self {
+        self.clone_with_transposition_table(self.transposition_table.as_ref().clone().into(), false)
+    }
+}
+
 impl<T: SearchControl<Searcher<P>> + Default, P: PositionEvaluation + Default> Default
     for CustomEngine<T, P>
 {
@@ -0,0 +1,105 @@
+use super::*;
+
+/// Creates engines which share one transposition table and evaluator caches, so that the memory used stays within one budget however many engines are running.
+///
+/// The searches of the pooled engines do not age the shared table, so each engine keeps its entries as long as they are deep enough, and [`EnginePool::new_game`] starts a new generation instead.
+/// The entries are replaced by depth and age only, so an engine searching more nodes than the others also gets a bigger part of the table, as there is no quota per engine.
+/// The pooled engines cannot clear, resize or replace the shared tables, which is only done through the pool.
+#[derive(Debug)]
+pub struct EnginePool<P: PositionEvaluation> {
+    transposition_table: Arc<TranspositionTable>,
+    evaluator: P,
+    memory_budget: RwLock<MemoryManager>,
+}
+
+impl<P: PositionEvaluation> EnginePool<P> {
+    pub fn new(memory_budget: MemoryManager, evaluator: P) -> Result<Self> {
+        let pool = Self {
+            transposition_table: TranspositionTable::new(CacheTableSize::ZERO).into(),
+            evaluator,
+            memory_budget: RwLock::new(memory_budget),
+        };
+        memory_budget.apply_to_tables(&pool.transposition_table, &pool.evaluator)?;
+        Ok(pool)
+    }
+
+    #[inline]
+    pub fn get_transposition_table(&self) -> &TranspositionTable {
+        &self.transposition_table
+    }
+
+    #[inline]
+    pub fn get_evaluator(&self) -> &P {
+        &self.evaluator
+    }
+
+    #[inline]
+    pub fn get_memory_budget(&self) -> MemoryManager {
+        *self.memory_budget.read().unwrap()
+    }
+
+    /// Splits the budget between the shared transposition table and evaluator caches, the entries are kept as long as
+    /// they fit.
+    pub fn set_memory_budget(&self, memory_budget: MemoryManager) -> Result<()> {
+        let mut current_memory_budget = self.memory_budget.write().unwrap();
+        memory_budget.apply_to_tables(&self.transposition_table, &self.evaluator)?;
+        *current_memory_budget = memory_budget;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn get_memory_usage(&self) -> usize {
+        self.transposition_table.get_memory_usage() + self.evaluator.get_memory_usage()
+    }
+
+    /// Clears the shared tables, which the pooled engines never do on their own.
+    pub fn clear(&self) {
+        self.transposition_table.clear();
+        // The clones of the evaluator use the same caches, like the ones given to the engines.
+        self.evaluator.clone().clear();
+    }
+
+    /// Starts a new generation of the shared table, so that the entries of the previous games are preferred for
+    /// replacement.
+    #[inline]
+    pub fn new_game(&self) {
+        self.transposition_table.increment_generation();
+    }
+
+    pub fn create_engine_with_controller<T: SearchControl<Searcher<P>>>(
+        &self,
+        board: Board,
+        controller: T,
+    ) -> CustomEngine<T, P> {
+        CustomEngine::new_sharing_transposition_table(
+            board,
+            self.transposition_table.clone(),
+            controller,
+            self.evaluator.clone(),
+        )
+    }
+
+    #[inline]
+    pub fn create_engine<T: SearchControl<Searcher<P>> + Default>(
+        &self,
+        board: Board,
+    ) -> CustomEngine<T, P> {
+        self.create_engine_with_controller(board, T::default())
+    }
+
+    #[inline]
+    pub fn print_info(&self) {
+        print_cache_table_info(
+            "Shared Hash Table",
+            self.transposition_table.len(),
+            self.transposition_table.get_size(),
+        );
+    }
+}
+
+impl<P: PositionEvaluation + Default> Default for EnginePool<P> {
+    fn default() -> Self {
+        Self::new(MemoryManager::default(), P::default())
+            .expect("The default memory budget must be allocatable")
+    }
+}
@@ -82,6 +82,12 @@ pub enum TimecatError {
     InvalidFileString {
         s: String,
     },
+    InvalidChess960Index {
+        index: u16,
+    },
+    InvalidVariantString {
+        s: String,
+    },
     InvalidSquareString {
         s: String,
     },
@@ -103,6 +109,50 @@ pub enum TimecatError {
     IllegalSearchMoves {
         illegal_moves: Vec<Move>,
     },
+    SearchStatisticsNotFound,
+    SearchTreeNotFound,
+    InvalidClusterAddress {
+        s: String,
+    },
+    InvalidClusterMessage {
+        s: String,
+    },
+    ClusterWorkerError {
+        address: String,
+        err_msg: String,
+    },
+    NoClusterWorkers,
+    InvalidExperienceFile {
+        path: String,
+        err_msg: String,
+    },
+    TranspositionTableNotFound,
+    MemoryAllocationFailed {
+        size: CacheTableSize,
+    },
+    HashMismatch {
+        fen: String,
+        err_msg: String,
+    },
+    InvalidHashFile {
+        path: String,
+        err_msg: String,
+    },
+    IncompatibleHashFileLayout {
+        path: String,
+        layout_version: u64,
+    },
+    InvalidCheckpointFile {
+        path: String,
+        err_msg: String,
+    },
+    CheckpointNotSupported,
+    SharedTablesNotModifiable,
+    InvalidPgn {
+        line: usize,
+        column: usize,
+        err_msg: String,
+    },
     FeatureNotEnabled {
         s: String,
     },
@@ -150,6 +200,8 @@ impl fmt::Display for TimecatError {
             InvalidMoveString { s } => write!(f, "Got invalid move string {s}! Please try again!"),
             InvalidRankString { s } => write!(f, "Got invalid rank string {s}! Please try again!"),
             InvalidFileString { s } => write!(f, "Got invalid file string {s}! Please try again!"),
+            InvalidChess960Index { index } => write!(f, "Got invalid Chess960 position index {index}, expected an index from 0 to 959! Please try again!"),
+            InvalidVariantString { s } => write!(f, "Got invalid variant string {s}! Please try again!"),
             InvalidSquareString { s } => write!(f, "Got invalid square string {s}! Please try again!"),
             InvalidPieceTypeString { s } => write!(f, "Got invalid piece type string {s}! Please try again!"),
             InvalidPieceString { s } => write!(f, "Got invalid piece string {s}! Please try again!"),
@@ -157,6 +209,22 @@ impl fmt::Display for TimecatError {
             InvalidBoardPosition { position } => write!(f, "Invalid position generated:\n\n{position:#?}"),
             InvalidGoCommand { s } => write!(f, "Got invalid go command: {s:?}! Please try again!"),
             IllegalSearchMoves { illegal_moves } => write!(f, "Got illegal search moves: {}! Please try again!", illegal_moves.iter().map(ToString::to_string).join(", ")),
+            SearchStatisticsNotFound => write!(f, "No search statistics found! Please enable the Search Statistics option and search again!"),
+            SearchTreeNotFound => write!(f, "No search tree was recorded! Please try again!"),
+            InvalidClusterAddress { s } => write!(f, "Got invalid cluster address {s:?}, expected tcp:<ip>:<port> or unix:<path>! Please try again!"),
+            InvalidClusterMessage { s } => write!(f, "Got invalid cluster message {s:?}! Please try again!"),
+            ClusterWorkerError { address, err_msg } => write!(f, "Cluster worker {address} failed: {err_msg}"),
+            NoClusterWorkers => write!(f, "No cluster workers mentioned! Please try again!"),
+            TranspositionTableNotFound => write!(f, "The engine does not have a transposition table! Please try again!"),
+            InvalidExperienceFile { path, err_msg } => write!(f, "Cannot load the experience file {path:?}, {err_msg}! Please try again!"),
+            MemoryAllocationFailed { size } => write!(f, "Cannot allocate {size} of memory! Please try again with a smaller size!"),
+            HashMismatch { fen, err_msg } => write!(f, "The hash of the position {fen} is inconsistent, {err_msg}!"),
+            InvalidHashFile { path, err_msg } => write!(f, "Cannot load the hash file {path:?}, {err_msg}! Please try again!"),
+            IncompatibleHashFileLayout { path, layout_version } => write!(f, "Cannot load the hash file {path:?}, its entry layout version is {layout_version} but version {TT_ENTRY_LAYOUT_VERSION} is required! Please save the hash again!"),
+            InvalidCheckpointFile { path, err_msg } => write!(f, "Cannot load the checkpoint file {path:?}, {err_msg}! Please try again!"),
+            CheckpointNotSupported => write!(f, "The engine does not support resuming from a checkpoint! Please try again!"),
+            SharedTablesNotModifiable => write!(f, "The hash tables are shared with other engines, change them through the engine pool instead! Please try again!"),
+            InvalidPgn { line, column, err_msg } => write!(f, "Cannot parse the PGN at line {line}, column {column}, {err_msg}! Please try again!"),
             FeatureNotEnabled { s } => write!(f, "The feature {s:?} is not enabled. Please recompile the chess engine with this feature enabled!"),
             BadNNUEFile => write!(f, "The NNUE file cannot be parsed properly! Try again with a different NNUE file!"),
             BadPolyglotFile => write!(f, "The Polyglot file cannot be parsed properly! Try again with a different Polyglot file!"),
@@ -0,0 +1,15 @@
+use super::*;
+
+/// Simple evaluator for Antichess, where the usual evaluation does not apply as the goal is to lose all the pieces.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Default, Debug)]
+pub struct EvaluatorAntichess;
+
+impl PositionEvaluation for EvaluatorAntichess {
+    fn evaluate(&mut self, position: &BoardPosition) -> Score {
+        // The side with fewer pieces is closer to winning.
+        (position.get_black_occupied().popcnt() as Score
+            - position.get_white_occupied().popcnt() as Score)
+            * PAWN_VALUE
+    }
+}
@@ -0,0 +1,17 @@
+use super::*;
+
+/// Simple evaluator for Horde, where the usual evaluation cannot be used as white has no king.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Default, Debug)]
+pub struct EvaluatorHorde;
+
+impl PositionEvaluation for EvaluatorHorde {
+    fn evaluate(&mut self, position: &BoardPosition) -> Score {
+        // The advanced pawns of the horde are closer to promoting.
+        let pawn_advancement = position
+            .get_colored_piece_mask(Pawn, White)
+            .map(|square| square.get_rank().to_int() as Score)
+            .sum::<Score>();
+        position.get_material_score() + pawn_advancement * PAWN_VALUE / 10
+    }
+}
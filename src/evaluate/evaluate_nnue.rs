@@ -249,8 +249,8 @@ impl EvaluatorNNUE {
     }
 
     #[inline]
-    pub fn set_size(&self, size: CacheTableSize) {
-        self.score_cache.set_size(size);
+    pub fn set_size(&self, size: CacheTableSize) -> Result<()> {
+        self.score_cache.set_size(size)
     }
 }
 
@@ -278,6 +278,16 @@ impl PositionEvaluation for EvaluatorNNUE {
             self.score_cache.get_size(),
         );
     }
+
+    #[inline]
+    fn set_evaluation_cache_size(&self, size: CacheTableSize) -> Result<()> {
+        self.set_size(size)
+    }
+
+    #[inline]
+    fn get_memory_usage(&self) -> usize {
+        self.score_cache.get_memory_usage()
+    }
 }
 
 #[cfg(feature = "inbuilt_nnue")]
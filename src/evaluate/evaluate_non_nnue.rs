@@ -3,12 +3,45 @@ use super::*;
 const CONTROL_CENTER_BONUS: Score = 50;
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Default, Debug)]
-pub struct EvaluatorNonNNUE;
+#[derive(Clone, Debug)]
+pub struct EvaluatorNonNNUE {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    score_cache: Arc<CacheTable<Score>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pawn_hash_table: Arc<PawnHashTable>,
+    use_pawn_structure: bool,
+}
 
 impl EvaluatorNonNNUE {
     pub fn new(_: &BoardPosition) -> Self {
-        Self
+        Self {
+            score_cache: CacheTable::new(CACHE_TABLE_SIZE).into(),
+            pawn_hash_table: PawnHashTable::default().into(),
+            use_pawn_structure: false,
+        }
+    }
+
+    #[inline]
+    pub fn is_using_pawn_structure(&self) -> bool {
+        self.use_pawn_structure
+    }
+
+    /// Adds the pawn structure terms from the pawn hash table to the evaluation, which is off by default.
+    pub fn set_using_pawn_structure(&mut self, b: bool) {
+        if self.use_pawn_structure != b {
+            self.use_pawn_structure = b;
+            self.score_cache.clear();
+        }
+    }
+
+    #[inline]
+    pub fn get_pawn_hash_table(&self) -> &PawnHashTable {
+        &self.pawn_hash_table
+    }
+
+    #[inline]
+    pub fn get_pawn_structure(&self, position: &BoardPosition) -> PawnStructure {
+        self.pawn_hash_table.probe(position)
     }
 
     pub fn slow_evaluate(position: &BoardPosition) -> Score {
@@ -22,7 +55,7 @@ impl EvaluatorNonNNUE {
         // Consider additional heuristics
         let mut mobility_score = 0;
         let mut king_safety_score = 0;
-        // let mut pawn_structure_score = 0;
+        // let pawn_structure_score = PawnStructure::new(position).get_score();
         let mut center_control_score = 0;
         // let mut piece_activity_score = 0;
         // let mut threat_score = 0;
@@ -57,16 +90,6 @@ impl EvaluatorNonNNUE {
                 };
             }
 
-            // // Calculate pawn structure
-            // if piece.get_piece_type() == Pawn {
-            //     let pawn_structure = Self::evaluate_pawn_structure(position, square);
-            //     pawn_structure_score += if piece.get_color() == White {
-            //         pawn_structure
-            //     } else {
-            //         -pawn_structure
-            //     };
-            // }
-
             // Calculate control of the center
             if BB_CENTER.contains(square) {
                 center_control_score += if piece.get_color() == White {
@@ -150,66 +173,6 @@ impl EvaluatorNonNNUE {
         safety_score
     }
 
-    // Enhanced Pawn Structure
-    fn evaluate_pawn_structure(position: &BoardPosition, pawn_square: Square) -> Score {
-        let mut structure_score = 0;
-        let pawn = position.get_piece_at(pawn_square).unwrap();
-        let pawn_color = pawn.get_color();
-
-        // Evaluate isolated pawns (no friendly pawns on adjacent files)
-        let file = pawn_square.get_file();
-        let adjacent_files = [file.left(), file.right()];
-
-        let isolated = adjacent_files.iter().flatten().all(|&adj_file| {
-            ALL_RANKS.iter().all(|&rank| {
-                let sq = Square::from_rank_and_file(rank, adj_file);
-                position.get_piece_at(sq).map_or(true, |p| {
-                    p.get_piece_type() != Pawn || p.get_color() != pawn_color
-                })
-            })
-        });
-
-        if isolated {
-            structure_score -= 20; // Example penalty for isolated pawns
-        }
-
-        // Evaluate doubled pawns (multiple pawns of the same color on the same file)
-        let file_pawns = ALL_RANKS
-            .iter()
-            .filter(|&&rank| {
-                let sq = Square::from_rank_and_file(rank, file);
-                position.get_piece_at(sq).map_or(false, |p| {
-                    p.get_piece_type() == Pawn && p.get_color() == pawn_color
-                })
-            })
-            .count();
-
-        if file_pawns > 1 {
-            structure_score -= 10 * (file_pawns as Score - 1) as Score; // Example penalty for each doubled pawn
-        }
-
-        // Evaluate passed pawns (no opposing pawns blocking or attacking the path to promotion)
-        if position.is_passed_pawn(pawn_square) {
-            structure_score += 30; // Example bonus for passed pawns
-        }
-
-        // // Evaluate backward pawns (no friendly pawns protecting from behind)
-        // let backward = adjacent_files.iter().flatten().all(|&adj_file| {
-        //     (0..pawn_square.get_rank()).all(|rank| {
-        //         let sq = Square::from_rank_and_file(rank, adj_file);
-        //         position.get_piece_at(sq).map_or(true, |p| {
-        //             p.get_piece_type() != Pawn || p.get_color() != pawn_color
-        //         })
-        //     })
-        // });
-
-        // if backward {
-        //     structure_score -= 15; // Example penalty for backward pawns
-        // }
-
-        structure_score
-    }
-
     // Enhanced Piece Activity
     fn evaluate_piece_activity(position: &BoardPosition, _piece: Piece, square: Square) -> Score {
         let mut activity_score = 0;
@@ -256,6 +219,10 @@ impl EvaluatorNonNNUE {
 
 impl PositionEvaluation for EvaluatorNonNNUE {
     fn evaluate(&mut self, position: &BoardPosition) -> Score {
+        let hash = position.get_hash();
+        if let Some(score) = self.score_cache.get(hash) {
+            return score;
+        }
         let material_score = position.get_material_score();
         let mut score = material_score;
         for (piece, square) in position.iter() {
@@ -268,7 +235,68 @@ impl PositionEvaluation for EvaluatorNonNNUE {
                 -psqt_score
             } as Score;
         }
+        if self.use_pawn_structure {
+            score += self.get_pawn_structure(position).get_score();
+        }
+        self.score_cache.add(hash, score);
         score
         // Self::evaluate_raw(position)
     }
+
+    #[inline]
+    fn reset_variables(&mut self) {
+        self.score_cache.reset_variables();
+        self.pawn_hash_table.reset_variables();
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.score_cache.clear();
+        self.pawn_hash_table.clear();
+    }
+
+    #[inline]
+    fn print_info(&self) {
+        print_cache_table_info(
+            "Evaluation Cache Table",
+            self.score_cache.len(),
+            self.score_cache.get_size(),
+        );
+        self.pawn_hash_table.print_info();
+    }
+
+    #[inline]
+    fn set_evaluation_cache_size(&self, size: CacheTableSize) -> Result<()> {
+        self.score_cache.set_size(size)
+    }
+
+    #[inline]
+    fn set_pawn_hash_size(&self, size: CacheTableSize) -> Result<()> {
+        self.pawn_hash_table.set_size(size)
+    }
+
+    fn set_cache_sizes(
+        &self,
+        evaluation_cache_size: CacheTableSize,
+        pawn_hash_size: CacheTableSize,
+    ) -> Result<()> {
+        let score_cache_allocation = self.score_cache.allocate(evaluation_cache_size)?;
+        let pawn_hash_allocation = self.pawn_hash_table.allocate(pawn_hash_size)?;
+        self.score_cache
+            .resize_with_allocation(score_cache_allocation);
+        self.pawn_hash_table
+            .resize_with_allocation(pawn_hash_allocation);
+        Ok(())
+    }
+
+    #[inline]
+    fn get_memory_usage(&self) -> usize {
+        self.score_cache.get_memory_usage() + self.pawn_hash_table.get_memory_usage()
+    }
+}
+
+impl Default for EvaluatorNonNNUE {
+    fn default() -> Self {
+        Self::new(&BoardPosition::default())
+    }
 }
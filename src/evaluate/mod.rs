@@ -1,12 +1,18 @@
 use super::*;
 
+pub mod evaluate_antichess;
+pub mod evaluate_horde;
 #[cfg(feature = "nnue_reader")]
 pub mod evaluate_nnue;
 pub mod evaluate_non_nnue;
+pub mod pawn_structure;
 
+pub use evaluate_antichess::*;
+pub use evaluate_horde::*;
 #[cfg(feature = "nnue_reader")]
 pub use evaluate_nnue::*;
 pub use evaluate_non_nnue::*;
+pub use pawn_structure::*;
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Default, Debug)]
@@ -19,6 +25,14 @@ pub struct Evaluator {
 
 impl Evaluator {
     pub fn new(position: &BoardPosition) -> Self {
+        // The inner evaluator needs one king of each color, which is not always the case in the
+        // variants not using it.
+        if ALL_COLORS
+            .into_iter()
+            .any(|color| position.get_colored_piece_mask(King, color).popcnt() != 1)
+        {
+            return Self::default();
+        }
         Self {
             #[cfg(feature = "inbuilt_nnue")]
             inner_evaluator: EvaluatorNNUE::new(position),
@@ -28,6 +42,11 @@ impl Evaluator {
     }
 
     pub fn slow_evaluate(position: &BoardPosition) -> Score {
+        match position.get_variant() {
+            Variant::Antichess => return EvaluatorAntichess.evaluate(position),
+            Variant::Horde => return EvaluatorHorde.evaluate(position),
+            _ => (),
+        }
         #[cfg(feature = "inbuilt_nnue")]
         {
             EvaluatorNNUE::slow_evaluate(position)
@@ -41,7 +60,12 @@ impl Evaluator {
 
 impl PositionEvaluation for Evaluator {
     fn evaluate(&mut self, position: &BoardPosition) -> Score {
-        self.inner_evaluator.evaluate(position)
+        match position.get_variant() {
+            Variant::Antichess => return EvaluatorAntichess.evaluate(position),
+            Variant::Horde => return EvaluatorHorde.evaluate(position),
+            _ => (),
+        }
+        self.inner_evaluator.evaluate(position) + position.get_variant().evaluate(position)
     }
 
     fn reset_variables(&mut self) {
@@ -56,8 +80,32 @@ impl PositionEvaluation for Evaluator {
         self.inner_evaluator.print_info()
     }
 
+    fn set_evaluation_cache_size(&self, size: CacheTableSize) -> Result<()> {
+        self.inner_evaluator.set_evaluation_cache_size(size)
+    }
+
+    fn set_pawn_hash_size(&self, size: CacheTableSize) -> Result<()> {
+        self.inner_evaluator.set_pawn_hash_size(size)
+    }
+
+    fn set_cache_sizes(
+        &self,
+        evaluation_cache_size: CacheTableSize,
+        pawn_hash_size: CacheTableSize,
+    ) -> Result<()> {
+        self.inner_evaluator
+            .set_cache_sizes(evaluation_cache_size, pawn_hash_size)
+    }
+
+    fn get_memory_usage(&self) -> usize {
+        self.inner_evaluator.get_memory_usage()
+    }
+
     fn evaluate_flipped(&mut self, position: &BoardPosition) -> Score {
-        self.inner_evaluator.evaluate_flipped(position)
+        if position.get_variant() == Variant::Standard {
+            return self.inner_evaluator.evaluate_flipped(position);
+        }
+        position.score_flipped(self.evaluate(position))
     }
 
     fn evaluate_checkmate_in(&mut self, mate_distance: Ply) -> Score {
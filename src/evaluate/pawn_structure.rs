@@ -0,0 +1,248 @@
+use super::*;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PawnStructure {
+    score: Score,
+    passed_pawns: [BitBoard; 2],
+}
+
+impl PawnStructure {
+    pub fn new(position: &BoardPosition) -> Self {
+        let mut pawn_structure = Self {
+            score: 0,
+            passed_pawns: [BitBoard::EMPTY; 2],
+        };
+        for color in [White, Black] {
+            for square in position.get_colored_piece_mask(Pawn, color) {
+                let pawn_structure_score = Self::evaluate_pawn_structure(position, square);
+                pawn_structure.score += if color == White {
+                    pawn_structure_score
+                } else {
+                    -pawn_structure_score
+                };
+                if position.is_passed_pawn(square) {
+                    *get_item_unchecked_mut!(pawn_structure.passed_pawns, color.to_index()) ^=
+                        square.to_bitboard();
+                }
+            }
+        }
+        pawn_structure
+    }
+
+    /// Pawn structure score from white's perspective.
+    #[inline]
+    pub fn get_score(&self) -> Score {
+        self.score
+    }
+
+    #[inline]
+    pub fn get_passed_pawns(&self, color: Color) -> BitBoard {
+        *get_item_unchecked!(self.passed_pawns, color.to_index())
+    }
+
+    // Enhanced Pawn Structure
+    fn evaluate_pawn_structure(position: &BoardPosition, pawn_square: Square) -> Score {
+        let mut structure_score = 0;
+        let pawn = position.get_piece_at(pawn_square).unwrap();
+        let pawn_color = pawn.get_color();
+
+        // Evaluate isolated pawns (no friendly pawns on adjacent files)
+        let file = pawn_square.get_file();
+        let adjacent_files = [file.left(), file.right()];
+
+        let isolated = adjacent_files.iter().flatten().all(|&adj_file| {
+            ALL_RANKS.iter().all(|&rank| {
+                let sq = Square::from_rank_and_file(rank, adj_file);
+                position.get_piece_at(sq).map_or(true, |p| {
+                    p.get_piece_type() != Pawn || p.get_color() != pawn_color
+                })
+            })
+        });
+
+        if isolated {
+            structure_score -= 20; // Example penalty for isolated pawns
+        }
+
+        // Evaluate doubled pawns (multiple pawns of the same color on the same file)
+        let file_pawns = ALL_RANKS
+            .iter()
+            .filter(|&&rank| {
+                let sq = Square::from_rank_and_file(rank, file);
+                position.get_piece_at(sq).map_or(false, |p| {
+                    p.get_piece_type() == Pawn && p.get_color() == pawn_color
+                })
+            })
+            .count();
+
+        if file_pawns > 1 {
+            structure_score -= 10 * (file_pawns as Score - 1) as Score; // Example penalty for each doubled pawn
+        }
+
+        // Evaluate passed pawns (no opposing pawns blocking or attacking the path to promotion)
+        if position.is_passed_pawn(pawn_square) {
+            structure_score += 30; // Example bonus for passed pawns
+        }
+
+        structure_score
+    }
+}
+
+/// Compact form of [`PawnStructure`] stored in the pawn hash, passed pawns are stored as indices into the pawns of their color.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct PawnHashEntry {
+    score: Score,
+    passed_pawn_indices: [u16; 2],
+}
+
+impl PawnHashEntry {
+    /// Pawns of a color beyond which the passed pawns can no longer be stored as indices.
+    const MAX_NUM_PAWNS: u32 = u16::BITS;
+
+    fn from_pawn_structure(
+        position: &BoardPosition,
+        pawn_structure: PawnStructure,
+    ) -> Option<Self> {
+        let mut passed_pawn_indices = [0; 2];
+        for color in [White, Black] {
+            let pawns = position.get_colored_piece_mask(Pawn, color);
+            if pawns.popcnt() > Self::MAX_NUM_PAWNS {
+                return None;
+            }
+            for (index, square) in pawns.enumerate() {
+                if pawn_structure.get_passed_pawns(color).contains(square) {
+                    passed_pawn_indices[color.to_index()] |= 1 << index;
+                }
+            }
+        }
+        Some(Self {
+            score: pawn_structure.get_score(),
+            passed_pawn_indices,
+        })
+    }
+
+    fn to_pawn_structure(self, position: &BoardPosition) -> PawnStructure {
+        let mut passed_pawns = [BitBoard::EMPTY; 2];
+        for color in [White, Black] {
+            for (index, square) in position.get_colored_piece_mask(Pawn, color).enumerate() {
+                if self.passed_pawn_indices[color.to_index()] >> index & 1 != 0 {
+                    passed_pawns[color.to_index()] ^= square.to_bitboard();
+                }
+            }
+        }
+        PawnStructure {
+            score: self.score,
+            passed_pawns,
+        }
+    }
+}
+
+impl Compress for PawnHashEntry {
+    type CompressedItem = u64;
+
+    fn compress(self) -> Self::CompressedItem {
+        (self.score.compress() & 0xFFFF)
+            | (self.passed_pawn_indices[0] as u64) << 16
+            | (self.passed_pawn_indices[1] as u64) << 32
+    }
+}
+
+impl Decompress<PawnHashEntry> for u64 {
+    fn decompress(self) -> PawnHashEntry {
+        PawnHashEntry {
+            score: self.decompress(),
+            passed_pawn_indices: [(self >> 16) as u16, (self >> 32) as u16],
+        }
+    }
+}
+
+/// Pawn structure of the positions keyed by their pawn hash, shared between the threads.
+#[derive(Debug)]
+pub struct PawnHashTable {
+    table: CacheTable<PawnHashEntry>,
+}
+
+impl PawnHashTable {
+    #[inline]
+    pub fn new(size: CacheTableSize) -> Self {
+        Self {
+            table: CacheTable::new(size),
+        }
+    }
+
+    pub fn probe(&self, position: &BoardPosition) -> PawnStructure {
+        let hash = position.get_pawn_hash();
+        if let Some(entry) = self.table.get(hash) {
+            return entry.to_pawn_structure(position);
+        }
+        let pawn_structure = PawnStructure::new(position);
+        if let Some(entry) = PawnHashEntry::from_pawn_structure(position, pawn_structure) {
+            self.table.add(hash, entry);
+        }
+        pawn_structure
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    #[inline]
+    pub fn get_size(&self) -> CacheTableSize {
+        self.table.get_size()
+    }
+
+    #[inline]
+    pub fn get_num_cells_filled(&self) -> usize {
+        self.table.get_num_cells_filled()
+    }
+
+    #[inline]
+    pub fn set_size(&self, size: CacheTableSize) -> Result<()> {
+        self.table.set_size(size)
+    }
+
+    #[inline]
+    pub(crate) fn allocate(
+        &self,
+        size: CacheTableSize,
+    ) -> Result<CacheTableAllocation<PawnHashEntry>> {
+        self.table.allocate(size)
+    }
+
+    #[inline]
+    pub(crate) fn resize_with_allocation(&self, allocation: CacheTableAllocation<PawnHashEntry>) {
+        self.table.resize_with_allocation(allocation)
+    }
+
+    #[inline]
+    pub fn get_memory_usage(&self) -> usize {
+        self.table.get_memory_usage()
+    }
+
+    #[inline]
+    pub fn clear(&self) {
+        self.table.clear();
+    }
+
+    #[inline]
+    pub fn reset_variables(&self) {
+        self.table.reset_variables();
+    }
+
+    #[inline]
+    pub fn print_info(&self) {
+        print_cache_table_info("Pawn Hash Table", self.len(), self.get_size());
+    }
+}
+
+impl Default for PawnHashTable {
+    fn default() -> Self {
+        Self::new(PAWN_HASH_TABLE_SIZE)
+    }
+}
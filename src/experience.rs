@@ -0,0 +1,273 @@
+use super::*;
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::BufWriter;
+use std::sync::{Mutex, MutexGuard};
+
+/// Locks of the experience files, so that the engines sharing a file, like the clones of an engine, never write to it
+/// or compact it at the same time.
+static EXPERIENCE_FILE_LOCKS: LazyLock<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> =
+    LazyLock::new(Default::default);
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ExperienceEntry {
+    best_move: Move,
+    depth: Depth,
+    score: Score,
+}
+
+impl ExperienceEntry {
+    #[inline]
+    pub fn new(best_move: Move, depth: Depth, score: Score) -> Self {
+        Self {
+            best_move,
+            depth,
+            score,
+        }
+    }
+
+    #[inline]
+    pub fn get_best_move(&self) -> Move {
+        self.best_move
+    }
+
+    #[inline]
+    pub fn get_depth(&self) -> Depth {
+        self.depth
+    }
+
+    /// Score from the perspective of the side to move.
+    #[inline]
+    pub fn get_score(&self) -> Score {
+        self.score
+    }
+}
+
+impl Compress for ExperienceEntry {
+    type CompressedItem = u64;
+
+    fn compress(self) -> Self::CompressedItem {
+        self.best_move.compress() as u64
+            | (self.score.compress() & 0xFFFF) << 16
+            | (self.depth as u8 as u64) << 32
+    }
+}
+
+impl Decompress<Option<ExperienceEntry>> for u64 {
+    fn decompress(self) -> Option<ExperienceEntry> {
+        let best_move: Option<Move> = (self as u16).decompress();
+        Some(ExperienceEntry {
+            best_move: best_move?,
+            depth: (self >> 32) as u8 as Depth,
+            score: (self >> 16).decompress(),
+        })
+    }
+}
+
+/// Positions searched in the past games, stored in an append only file of records.
+#[derive(Clone, Debug)]
+pub struct Experience {
+    path: PathBuf,
+    entries: IdentityHashMap<u64, ExperienceEntry>,
+    game_positions: Vec<(u64, Color)>,
+}
+
+impl Experience {
+    const HEADER_SIZE: u64 = 8;
+    const RECORD_SIZE: u64 = 16;
+
+    /// Lock of the file shared by all the experiences of this process using it.
+    fn get_file_lock(&self) -> Arc<Mutex<()>> {
+        let path = std::path::absolute(&self.path).unwrap_or_else(|_| self.path.clone());
+        EXPERIENCE_FILE_LOCKS
+            .lock()
+            .unwrap()
+            .entry(path)
+            .or_default()
+            .clone()
+    }
+
+    fn lock_file(file_lock: &Mutex<()>) -> MutexGuard<'_, ()> {
+        // The file is still consistent if a writer panicked, as the records are only appended.
+        file_lock.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Loads the experience file at `path`, the file is created when the first position is stored.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut experience = Self {
+            path,
+            entries: IdentityHashMap::default(),
+            game_positions: Vec::new(),
+        };
+        let file_lock = experience.get_file_lock();
+        let _guard = Self::lock_file(&file_lock);
+        let file = match fs::File::open(&experience.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(experience),
+            Err(err) => return Err(err.into()),
+        };
+        let error = |err_msg: &str| TimecatError::InvalidExperienceFile {
+            path: experience.path.display().to_string(),
+            err_msg: err_msg.to_string(),
+        };
+        let file_size = file.metadata()?.len();
+        if file_size < Self::HEADER_SIZE || (file_size - Self::HEADER_SIZE) % Self::RECORD_SIZE != 0
+        {
+            return Err(error("file size is invalid"));
+        }
+        let mut reader = BufReader::new(file);
+        let mut header = [0; Self::HEADER_SIZE as usize];
+        reader.read_exact(&mut header)?;
+        if header[..4] != EXPERIENCE_FILE_MAGIC {
+            return Err(error("not an experience file"));
+        }
+        if u32::from_le_bytes(header[4..].try_into().unwrap()) != EXPERIENCE_FILE_VERSION {
+            return Err(error("unsupported file version"));
+        }
+        let num_records = (file_size - Self::HEADER_SIZE) / Self::RECORD_SIZE;
+        let mut record = [0; Self::RECORD_SIZE as usize];
+        for _ in 0..num_records {
+            reader.read_exact(&mut record)?;
+            let hash = u64::from_le_bytes(record[..8].try_into().unwrap());
+            let entry: Option<ExperienceEntry> =
+                u64::from_le_bytes(record[8..].try_into().unwrap()).decompress();
+            experience
+                .entries
+                .insert(hash, entry.ok_or_else(|| error("invalid record"))?);
+        }
+        if num_records as usize > 2 * experience.entries.len() {
+            experience.write_all_records()?;
+        }
+        Ok(experience)
+    }
+
+    #[inline]
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline]
+    pub fn get(&self, hash: u64) -> Option<ExperienceEntry> {
+        self.entries.get(&hash).copied()
+    }
+
+    /// Experience of the position if its best move is still legal.
+    pub fn probe(&self, position: &BoardPosition) -> Option<ExperienceEntry> {
+        self.get(position.get_hash())
+            .filter(|entry| position.is_legal(&entry.get_best_move()))
+    }
+
+    fn write_header(writer: &mut impl Write) -> std::io::Result<()> {
+        writer.write_all(&EXPERIENCE_FILE_MAGIC)?;
+        writer.write_all(&EXPERIENCE_FILE_VERSION.to_le_bytes())
+    }
+
+    fn write_record(writer: &mut impl Write, hash: u64, entry: ExperienceEntry) -> Result<()> {
+        writer.write_all(&hash.to_le_bytes())?;
+        writer.write_all(&entry.compress().to_le_bytes())?;
+        Ok(())
+    }
+
+    fn append_records(&self, hashes: impl IntoIterator<Item = u64>) -> Result<()> {
+        let file_lock = self.get_file_lock();
+        let _guard = Self::lock_file(&file_lock);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let is_new_file = file.metadata()?.len() == 0;
+        let mut writer = BufWriter::new(file);
+        if is_new_file {
+            Self::write_header(&mut writer)?;
+        }
+        for hash in hashes {
+            if let Some(entry) = self.get(hash) {
+                Self::write_record(&mut writer, hash, entry)?;
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Rewrites the file keeping only the latest record of every position known to this experience.
+    pub fn compact(&self) -> Result<()> {
+        let file_lock = self.get_file_lock();
+        let _guard = Self::lock_file(&file_lock);
+        self.write_all_records()
+    }
+
+    fn write_all_records(&self) -> Result<()> {
+        let mut writer = BufWriter::new(fs::File::create(&self.path)?);
+        Self::write_header(&mut writer)?;
+        for (&hash, &entry) in &self.entries {
+            Self::write_record(&mut writer, hash, entry)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Stores the result of a search of the position, shallower searches never replace deeper ones.
+    pub fn record(
+        &mut self,
+        position: &BoardPosition,
+        depth: Depth,
+        score: Score,
+        best_move: Move,
+    ) -> Result<()> {
+        let hash = position.get_hash();
+        self.game_positions.push((hash, position.turn()));
+        if depth < EXPERIENCE_MIN_DEPTH
+            || self
+                .get(hash)
+                .is_some_and(|entry| entry.get_depth() > depth)
+        {
+            return Ok(());
+        }
+        self.entries
+            .insert(hash, ExperienceEntry::new(best_move, depth, score));
+        self.append_records([hash])
+    }
+
+    /// Forgets the positions searched in the current game without adjusting them.
+    #[inline]
+    pub fn start_new_game(&mut self) {
+        self.game_positions.clear();
+    }
+
+    /// Adjusts the scores of the positions searched in the game towards its result.
+    pub fn on_game_end(&mut self, result: GameResult) -> Result<()> {
+        let game_positions = std::mem::take(&mut self.game_positions);
+        if result.is_in_progress() {
+            return Ok(());
+        }
+        let mut adjusted_hashes = Vec::new();
+        for (hash, turn) in game_positions.into_iter().unique() {
+            let Some(entry) = self.entries.get_mut(&hash) else {
+                continue;
+            };
+            if is_checkmate(entry.score) {
+                continue;
+            }
+            entry.score = match result.winner() {
+                Some(winner) if winner == turn => entry.score + EXPERIENCE_RESULT_ADJUSTMENT,
+                Some(_) => entry.score - EXPERIENCE_RESULT_ADJUSTMENT,
+                None => entry.score - entry.score / 4,
+            }
+            .clamp(-CHECKMATE_THRESHOLD + 1, CHECKMATE_THRESHOLD - 1);
+            adjusted_hashes.push(hash);
+        }
+        self.append_records(adjusted_hashes)
+    }
+}
@@ -0,0 +1,105 @@
+use super::*;
+
+/// Splits a total memory budget in MB between the transposition table, the evaluation cache and the pawn hash table by the given ratios.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MemoryManager {
+    budget: usize,
+    transposition_table_ratio: usize,
+    evaluation_cache_ratio: usize,
+    pawn_hash_ratio: usize,
+}
+
+impl MemoryManager {
+    pub const fn new(budget: usize) -> Self {
+        let [transposition_table_ratio, evaluation_cache_ratio, pawn_hash_ratio] =
+            MEMORY_BUDGET_RATIOS;
+        Self {
+            budget,
+            transposition_table_ratio,
+            evaluation_cache_ratio,
+            pawn_hash_ratio,
+        }
+    }
+
+    #[inline]
+    pub fn get_budget(&self) -> usize {
+        self.budget
+    }
+
+    pub fn set_ratios(
+        mut self,
+        transposition_table_ratio: usize,
+        evaluation_cache_ratio: usize,
+        pawn_hash_ratio: usize,
+    ) -> Self {
+        self.transposition_table_ratio = transposition_table_ratio;
+        self.evaluation_cache_ratio = evaluation_cache_ratio;
+        self.pawn_hash_ratio = pawn_hash_ratio;
+        self
+    }
+
+    fn get_share(&self, ratio: usize) -> CacheTableSize {
+        let total_ratio =
+            self.transposition_table_ratio + self.evaluation_cache_ratio + self.pawn_hash_ratio;
+        CacheTableSize::Exact(
+            self.budget
+                .saturating_mul(ratio)
+                .checked_div(total_ratio)
+                .unwrap_or_default(),
+        )
+    }
+
+    #[inline]
+    pub fn get_transposition_table_size(&self) -> CacheTableSize {
+        self.get_share(self.transposition_table_ratio)
+    }
+
+    #[inline]
+    pub fn get_evaluation_cache_size(&self) -> CacheTableSize {
+        self.get_share(self.evaluation_cache_ratio)
+    }
+
+    #[inline]
+    pub fn get_pawn_hash_size(&self) -> CacheTableSize {
+        self.get_share(self.pawn_hash_ratio)
+    }
+
+    /// Resizes the tables of the engine, which are left unchanged if the budget cannot be allocated.
+    #[inline]
+    pub fn apply(&self, engine: &impl ChessEngine) -> Result<()> {
+        engine.set_memory_budget(*self)
+    }
+
+    /// Resizes the given tables, for the engines and the owners of tables shared by several engines like the
+    /// [`EnginePool`].
+    ///
+    /// The new transposition table is allocated before the evaluator allocates its caches, and swapped in only if they
+    /// could be allocated too, so that no table is resized for a budget which does not fit.
+    pub fn apply_to_tables(
+        &self,
+        transposition_table: &TranspositionTable,
+        evaluator: &impl PositionEvaluation,
+    ) -> Result<()> {
+        let error = |err| match err {
+            TimecatError::MemoryAllocationFailed { .. } => TimecatError::MemoryAllocationFailed {
+                size: CacheTableSize::Exact(self.budget),
+            },
+            err => err,
+        };
+        let transposition_table_allocation = transposition_table
+            .allocate(self.get_transposition_table_size())
+            .map_err(error)?;
+        evaluator
+            .set_cache_sizes(self.get_evaluation_cache_size(), self.get_pawn_hash_size())
+            .map_err(error)?;
+        transposition_table.resize_with_allocation(transposition_table_allocation);
+        Ok(())
+    }
+}
+
+impl Default for MemoryManager {
+    fn default() -> Self {
+        Self::new(DEFAULT_MEMORY_BUDGET)
+    }
+}
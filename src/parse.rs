@@ -27,9 +27,30 @@ pub enum UserCommand {
     Help,
     Perft(Depth),
     Go(SearchConfig),
+    PrintSearchStatistics {
+        json: bool,
+    },
+    ClusterWorker(ClusterAddress),
+    ClusterGo {
+        workers: Vec<ClusterAddress>,
+        config: SearchConfig,
+    },
+    ClusterQuit(Vec<ClusterAddress>),
+    SaveHash(String),
+    LoadHash(String),
+    Resume(String),
+    DumpSearchTree {
+        depth: Depth,
+        path: String,
+        config: SearchTreeConfig,
+    },
     PushMoves(String),
     PopMoves(u16),
     SetFen(String),
+    SetPosition {
+        fen: String,
+        moves: Vec<String>,
+    },
     #[cfg(feature = "colored")]
     SetColor(bool),
     SetUCIOption {
@@ -100,8 +121,13 @@ impl UserCommand {
                     user_input: "setoption name Clear Hash".to_string(),
                 }
                 .run_command(engine, uci_state_manager)?;
-                Self::SetFen(STARTING_POSITION_FEN.to_string())
-                    .run_command(engine, uci_state_manager)?;
+                Self::SetFen(
+                    GLOBAL_TIMECAT_STATE
+                        .get_variant()
+                        .get_starting_fen()
+                        .to_string(),
+                )
+                .run_command(engine, uci_state_manager)?;
             }
             Self::IsReady => println_wasm!("{}", "readyok".colorize(SUCCESS_MESSAGE_STYLE)),
             Self::Stop => {
@@ -112,12 +138,39 @@ impl UserCommand {
             Self::Help => println_wasm!("{}", Self::generate_help_message()),
             &Self::Perft(depth) => GoAndPerft::run_perft_command(engine, depth)?,
             Self::Go(config) => GoAndPerft::run_search(engine, config)?,
+            &Self::PrintSearchStatistics { json } => {
+                let search_statistics = engine
+                    .get_search_statistics()
+                    .ok_or(SearchStatisticsNotFound)?;
+                if json {
+                    println_wasm!("{}", search_statistics.to_json());
+                } else {
+                    search_statistics.print_info();
+                }
+            }
+            Self::ClusterWorker(address) => Cluster::run_worker(engine, address)?,
+            Self::ClusterGo { workers, config } => Cluster::run_search(engine, workers, config)?,
+            Self::ClusterQuit(workers) => {
+                ClusterCoordinator::new(workers.to_owned()).quit_workers()?
+            }
+            Self::SaveHash(path) => engine.save_hash(path)?,
+            Self::LoadHash(path) => engine.load_hash(path)?,
+            Self::Resume(path) => {
+                engine.resume_from_checkpoint(path)?;
+                GoAndPerft::run_search(engine, const { &SearchConfig::new_infinite() })?
+            }
+            Self::DumpSearchTree {
+                depth,
+                path,
+                config,
+            } => GoAndPerft::run_search_tree_dump(engine, *depth, path, config)?,
             Self::PushMoves(user_input) => {
                 let binding = Parser::sanitize_string(user_input);
                 Push::push_moves(engine, &binding.split_whitespace().collect_vec())?
             }
             &Self::PopMoves(num_moves) => Pop::pop_moves(engine, num_moves)?,
             Self::SetFen(fen) => Set::set_board_fen(engine, fen)?,
+            Self::SetPosition { fen, moves } => Position::set_position(engine, fen, moves)?,
             #[cfg(feature = "colored")]
             &Self::SetColor(b) => Set::set_color(b)?,
             Self::SetUCIOption { user_input } => {
@@ -170,7 +223,65 @@ impl GoAndPerft {
         Ok(())
     }
 
-    fn run_search(engine: &mut impl ChessEngine, config: &SearchConfig) -> Result<()> {
+    /// The path is taken from the raw input, as [`Parser::sanitize_string`] replaces some of its characters.
+    fn parse_search_tree_dump(raw_input: &str) -> Result<Vec<UserCommand>> {
+        let path = raw_input
+            .split_whitespace()
+            .nth(1)
+            .ok_or(UnknownCommand)?
+            .to_string();
+        let options =
+            Parser::sanitize_string(Parser::get_raw_argument(raw_input, 2).unwrap_or_default());
+        let mut depth = None;
+        let mut config = SearchTreeConfig::default();
+        let mut iter = options.split_whitespace();
+        while let Some(command) = iter.next() {
+            match command.to_lowercase().as_str() {
+                "depth" => depth = Some(iter.next().ok_or(UnknownCommand)?.parse()?),
+                "maxply" => {
+                    config = config.set_max_ply(iter.next().ok_or(UnknownCommand)?.parse()?)
+                }
+                "maxnodes" => {
+                    config = config.set_max_nodes(iter.next().ok_or(UnknownCommand)?.parse()?)
+                }
+                "moves" => {
+                    config = config.set_moves_filter(
+                        iter.by_ref()
+                            .map(Move::from_str)
+                            .collect::<Result<Vec<_>>>()?,
+                    )
+                }
+                _ => return Err(UnknownCommand),
+            }
+        }
+        UserCommand::DumpSearchTree {
+            depth: depth.ok_or(UnknownCommand)?,
+            path,
+            config,
+        }
+        .into()
+    }
+
+    fn run_search_tree_dump(
+        engine: &mut impl ChessEngine,
+        depth: Depth,
+        path: &str,
+        config: &SearchTreeConfig,
+    ) -> Result<()> {
+        engine.set_search_tree_config(Some(config.clone()));
+        let result = Self::run_search(engine, &SearchConfig::new_depth(depth));
+        engine.set_search_tree_config(None);
+        result?;
+        let search_tree = engine.get_search_tree().ok_or(SearchTreeNotFound)?;
+        search_tree.write_to_file(path, SearchTreeFormat::from_path(path))?;
+        force_println_info(
+            "Search Tree",
+            format!("{} nodes written to {path:?}", search_tree.len()),
+        );
+        Ok(())
+    }
+
+    fn validate_search_moves(engine: &impl ChessEngine, config: &SearchConfig) -> Result<()> {
         if let Some(moves_to_search) = config.get_moves_to_search() {
             let legal_moves = engine.get_board().generate_legal_moves();
             if moves_to_search
@@ -186,15 +297,27 @@ impl GoAndPerft {
                 });
             }
         }
+        Ok(())
+    }
+
+    fn run_search(engine: &mut impl ChessEngine, config: &SearchConfig) -> Result<()> {
+        Self::validate_search_moves(engine, config)?;
         if GLOBAL_TIMECAT_STATE.is_in_console_mode() {
             println_wasm!("{}\n", engine.get_board());
         }
         let clock = Instant::now();
         let response = engine.go_verbose(config);
+        Self::print_search_result(engine, &response, clock.elapsed())
+    }
+
+    fn print_search_result(
+        engine: &impl ChessEngine,
+        response: &SearchInfo,
+        elapsed_time: Duration,
+    ) -> Result<()> {
         let best_move = response.get_best_move().ok_or(BestMoveNotFound {
             fen: engine.get_board().get_fen(),
         })?;
-        let elapsed_time = clock.elapsed();
         let pv_string = get_pv_string(engine.get_board().get_position(), response.get_pv());
         if GLOBAL_TIMECAT_STATE.is_in_console_mode() {
             println_wasm!();
@@ -247,13 +370,91 @@ impl GoAndPerft {
     }
 }
 
+struct Cluster;
+
+impl Cluster {
+    /// Worker addresses at the start of the raw words, like `tcp:127.0.0.1:7000`, `tcp:[::1]:7000`, `tcp:localhost:7000`
+    /// or `unix:/tmp/a.sock`, which cannot be sanitized as they contain colons.
+    fn parse_workers<'a>(words: &'a [&'a str]) -> Result<(Vec<ClusterAddress>, &'a [&'a str])> {
+        let num_workers = words
+            .iter()
+            .take_while(|word| {
+                word.split_once(':').is_some_and(|(protocol, _)| {
+                    ["tcp", "unix"]
+                        .iter()
+                        .any(|name| protocol.eq_ignore_ascii_case(name))
+                })
+            })
+            .count();
+        let workers = words[..num_workers]
+            .iter()
+            .map(|word| word.parse())
+            .collect::<Result<Vec<_>>>()?;
+        if workers.is_empty() {
+            return Err(NoClusterWorkers);
+        }
+        Ok((workers, &words[num_workers..]))
+    }
+
+    fn parse_sub_commands(raw_input: &str) -> Result<Vec<UserCommand>> {
+        let words = raw_input
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|word| !word.is_empty())
+            .collect_vec();
+        let second_command = words.get(1).ok_or(UnknownCommand)?.to_lowercase();
+        let (workers, remaining_words) = Self::parse_workers(&words[2..])?;
+        let remaining_commands = Parser::sanitize_string(&remaining_words.join(" "));
+        let remaining_commands = remaining_commands.split_whitespace().collect_vec();
+        match second_command.as_str() {
+            "worker" if workers.len() == 1 && remaining_commands.is_empty() => {
+                UserCommand::ClusterWorker(workers.into_iter().next().unwrap()).into()
+            }
+            "go" => {
+                let mut go_commands = vec!["go"];
+                go_commands.extend_from_slice(&remaining_commands);
+                UserCommand::ClusterGo {
+                    workers,
+                    config: SearchConfig::try_from(go_commands)?,
+                }
+                .into()
+            }
+            "quit" if remaining_commands.is_empty() => UserCommand::ClusterQuit(workers).into(),
+            _ => Err(UnknownCommand),
+        }
+    }
+
+    fn run_worker(engine: &mut impl ChessEngine, address: &ClusterAddress) -> Result<()> {
+        let worker = ClusterWorker::bind(address)?;
+        force_println_info("Cluster worker listening on", worker.get_local_address()?);
+        worker.serve(engine)
+    }
+
+    fn run_search(
+        engine: &mut impl ChessEngine,
+        workers: &[ClusterAddress],
+        config: &SearchConfig,
+    ) -> Result<()> {
+        GoAndPerft::validate_search_moves(engine, config)?;
+        let clock = Instant::now();
+        let response = ClusterCoordinator::new(workers.to_vec()).go(engine.get_board(), config)?;
+        response.print_info();
+        GoAndPerft::print_search_result(engine, &response, clock.elapsed())
+    }
+}
+
 struct Set;
 
 impl Set {
     fn extract_board_fen(commands: &[&str]) -> Result<Vec<UserCommand>> {
         let fen = commands[3..].join(" ");
         if fen == "startpos" {
-            return UserCommand::SetFen(STARTING_POSITION_FEN.to_string()).into();
+            return UserCommand::SetFen(
+                GLOBAL_TIMECAT_STATE
+                    .get_variant()
+                    .get_starting_fen()
+                    .to_string(),
+            )
+            .into();
         }
         UserCommand::SetFen(fen).into()
     }
@@ -288,8 +489,9 @@ impl Set {
         }
     }
 
+    /// The fen is set as a position of the variant selected globally, which is only the default of the commands.
     fn set_board_fen(engine: &mut impl ChessEngine, fen: &str) -> Result<()> {
-        engine.set_fen(fen)?;
+        engine.set_fen_and_variant(fen, GLOBAL_TIMECAT_STATE.get_variant())?;
         if GLOBAL_TIMECAT_STATE.is_in_console_mode() {
             println_wasm!("{}", engine.get_board());
         }
@@ -322,6 +524,7 @@ impl Push {
             engine.get_board_mut().push(valid_or_null_move)?;
             println_info("Pushed move", move_text);
         }
+        engine.on_game_end(engine.get_board().result());
         Ok(())
     }
 }
@@ -401,30 +604,37 @@ impl Position {
             return Err(UnknownCommand);
         }
         let second_command = commands.get(1).ok_or(UnknownCommand)?.to_lowercase();
-        let mut user_commands = Vec::with_capacity(2);
-        user_commands.push(match second_command.as_str() {
-            "startpos" => UserCommand::SetFen(STARTING_POSITION_FEN.to_string()),
-            "fen" => {
-                let fen = commands
-                    .iter()
-                    .skip(2)
-                    .take_while(|&&s| s != "moves")
-                    .join(" ");
-                UserCommand::SetFen(fen)
-            }
+        let fen = match second_command.as_str() {
+            "startpos" => GLOBAL_TIMECAT_STATE
+                .get_variant()
+                .get_starting_fen()
+                .to_string(),
+            "fen" => commands
+                .iter()
+                .skip(2)
+                .take_while(|&&s| s != "moves")
+                .join(" "),
             _ => return Err(UnknownCommand),
-        });
-        let move_texts_joined = commands
+        };
+        let moves = commands
             .iter()
             .skip_while(|&&s| s != "moves")
             .skip(1)
-            .join(" ");
-        if !move_texts_joined.is_empty() {
-            user_commands.push(UserCommand::PushMoves(format!(
-                "push moves {move_texts_joined}"
-            )));
+            .map(|s| s.to_string())
+            .collect();
+        UserCommand::SetPosition { fen, moves }.into()
+    }
+
+    fn set_position(engine: &mut impl ChessEngine, fen: &str, moves: &[String]) -> Result<()> {
+        let mut board = Board::from_fen_and_variant(fen, GLOBAL_TIMECAT_STATE.get_variant())?;
+        for move_text in moves {
+            board.push(board.parse_move(move_text)?)?;
         }
-        Ok(user_commands)
+        engine.set_position(board)?;
+        if GLOBAL_TIMECAT_STATE.is_in_console_mode() {
+            println_wasm!("{}", engine.get_board());
+        }
+        Ok(())
     }
 }
 
@@ -441,7 +651,21 @@ impl Parser {
         user_input
     }
 
-    fn parse_single_command(single_input: &str) -> Result<Vec<UserCommand>> {
+    /// Text following the first `num_words` words of the raw input, for the arguments like paths which can contain
+    /// the characters replaced by [`Self::sanitize_string`].
+    fn get_raw_argument(raw_input: &str, num_words: usize) -> Option<&str> {
+        let mut argument = raw_input.trim();
+        for _ in 0..num_words {
+            argument = argument
+                .trim_start_matches(|c: char| !c.is_whitespace())
+                .trim_start();
+        }
+        Some(argument).filter(|argument| !argument.is_empty())
+    }
+
+    fn parse_single_command(raw_single_input: &str) -> Result<Vec<UserCommand>> {
+        let single_input = Self::sanitize_string(raw_single_input);
+        let single_input = single_input.as_str();
         match single_input.to_lowercase().as_str() {
             "q" | "quit" | "quit()" | "quit(0)" | "exit" | "exit()" | "exit(0)" => {
                 UserCommand::TerminateEngine.into()
@@ -476,14 +700,35 @@ impl Parser {
             "eval" => Err(TimecatError::FeatureNotEnabled {
                 s: "inbuilt nnue".to_string(),
             }),
-            "reset board" => UserCommand::SetFen(STARTING_POSITION_FEN.to_owned()).into(),
+            "reset board" => UserCommand::SetFen(
+                GLOBAL_TIMECAT_STATE
+                    .get_variant()
+                    .get_starting_fen()
+                    .to_owned(),
+            )
+            .into(),
             "stop" => UserCommand::Stop.into(),
+            "stats" => UserCommand::PrintSearchStatistics { json: false }.into(),
+            "stats json" => UserCommand::PrintSearchStatistics { json: true }.into(),
             "help" => UserCommand::Help.into(),
             _ => {
                 let commands = single_input.split_whitespace().collect_vec();
                 let first_command = commands.first().ok_or(UnknownCommand)?.to_lowercase();
                 match first_command.as_str() {
                     "go" => GoAndPerft::parse_sub_commands(&commands),
+                    "searchtree" => GoAndPerft::parse_search_tree_dump(raw_single_input),
+                    "cluster" => Cluster::parse_sub_commands(raw_single_input),
+                    "save_hash" | "load_hash" | "resume" => {
+                        let path = Self::get_raw_argument(raw_single_input, 1)
+                            .ok_or(UnknownCommand)?
+                            .to_string();
+                        match first_command.as_str() {
+                            "save_hash" => UserCommand::SaveHash(path),
+                            "load_hash" => UserCommand::LoadHash(path),
+                            _ => UserCommand::Resume(path),
+                        }
+                        .into()
+                    }
                     "set" => Set::parse_sub_commands(&commands),
                     "setoption" => UserCommand::SetUCIOption {
                         user_input: single_input.to_string(),
@@ -510,10 +755,10 @@ impl Parser {
         if raw_input.trim().is_empty() {
             return Err(NoInput);
         }
-        Self::sanitize_string(raw_input)
+        raw_input
             .split("&&")
             .try_fold(Vec::new(), |mut vec, input| {
-                vec.extend(Self::parse_single_command(input.trim())?);
+                vec.extend(Self::parse_single_command(input)?);
                 Ok(vec)
             })
     }
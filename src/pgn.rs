@@ -0,0 +1,624 @@
+use super::*;
+use std::io::BufRead;
+
+/// Evaluation stored in a `[%eval]` annotation.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum PgnEvaluation {
+    /// Evaluation in centipawns from white's point of view.
+    Centipawns(i32),
+    /// Number of moves to mate, negative when black is mating.
+    Mate(i32),
+}
+
+impl FromStr for PgnEvaluation {
+    type Err = TimecatError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let error = || TimecatError::CustomError {
+            err_msg: format!("Got invalid PGN evaluation {s:?}! Please try again!"),
+        };
+        // The evaluation can be followed by the depth, like in 0.25,20.
+        let evaluation = s.split(',').next().unwrap_or_default().trim();
+        if let Some(mate) = evaluation.strip_prefix('#') {
+            return mate.parse().map(Self::Mate).map_err(|_| error());
+        }
+        let pawns: f64 = evaluation.parse().map_err(|_| error())?;
+        if !pawns.is_finite() {
+            return Err(error());
+        }
+        Ok(Self::Centipawns((pawns * 100.0).round() as i32))
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PgnMove {
+    san: String,
+    nags: Vec<u8>,
+    comments: Vec<String>,
+    clock: Option<Duration>,
+    evaluation: Option<PgnEvaluation>,
+    variations: Vec<Vec<PgnMove>>,
+    line: usize,
+    column: usize,
+}
+
+impl PgnMove {
+    fn new(san: String, line: usize, column: usize) -> Self {
+        Self {
+            san,
+            nags: Vec::new(),
+            comments: Vec::new(),
+            clock: None,
+            evaluation: None,
+            variations: Vec::new(),
+            line,
+            column,
+        }
+    }
+
+    /// Stores the comment, taking out the `[%clk]` and `[%eval]` annotations.
+    fn add_comment(&mut self, comment: &str) {
+        let mut text = String::new();
+        let mut rest = comment;
+        while let Some(start) = rest.find("[%") {
+            let Some(length) = rest[start..].find(']') else {
+                break;
+            };
+            let command = rest[start + 2..start + length].trim();
+            let (name, args) = command
+                .split_once(char::is_whitespace)
+                .unwrap_or((command, ""));
+            let is_parsed = match name {
+                "clk" => parse_pgn_clock(args).map(|clock| self.clock = Some(clock)),
+                "eval" => args
+                    .parse()
+                    .ok()
+                    .map(|evaluation| self.evaluation = Some(evaluation)),
+                _ => None,
+            }
+            .is_some();
+            text += &rest[..start];
+            if !is_parsed {
+                text += &rest[start..=start + length];
+            }
+            rest = &rest[start + length + 1..];
+        }
+        text += rest;
+        let text = text.trim();
+        if !text.is_empty() {
+            self.comments.push(text.to_string());
+        }
+    }
+
+    #[inline]
+    pub fn get_san(&self) -> &str {
+        &self.san
+    }
+
+    /// Numeric annotation glyphs, with the `!` and `?` suffixes converted to their NAGs.
+    #[inline]
+    pub fn get_nags(&self) -> &[u8] {
+        &self.nags
+    }
+
+    /// Comments following the move, or preceding it for the first move of a variation.
+    #[inline]
+    pub fn get_comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    /// Remaining time from the `[%clk]` annotation.
+    #[inline]
+    pub fn get_clock(&self) -> Option<Duration> {
+        self.clock
+    }
+
+    #[inline]
+    pub fn get_evaluation(&self) -> Option<PgnEvaluation> {
+        self.evaluation
+    }
+
+    /// Alternatives to this move, each played from the position before it.
+    #[inline]
+    pub fn get_variations(&self) -> &[Vec<PgnMove>] {
+        &self.variations
+    }
+
+    #[inline]
+    pub fn get_line(&self) -> usize {
+        self.line
+    }
+
+    #[inline]
+    pub fn get_column(&self) -> usize {
+        self.column
+    }
+}
+
+/// A game read from a PGN, with its moves kept in SAN until they are replayed on a [`Board`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct PgnGame {
+    tags: Vec<(String, String)>,
+    comments: Vec<String>,
+    moves: Vec<PgnMove>,
+    result: Option<GameResult>,
+}
+
+impl PgnGame {
+    /// Tags in the order they appear.
+    #[inline]
+    pub fn get_tags(&self) -> &[(String, String)] {
+        &self.tags
+    }
+
+    pub fn get_tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag_name, _)| tag_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[inline]
+    pub fn get_event(&self) -> Option<&str> {
+        self.get_tag("Event")
+    }
+
+    #[inline]
+    pub fn get_site(&self) -> Option<&str> {
+        self.get_tag("Site")
+    }
+
+    #[inline]
+    pub fn get_date(&self) -> Option<&str> {
+        self.get_tag("Date")
+    }
+
+    #[inline]
+    pub fn get_round(&self) -> Option<&str> {
+        self.get_tag("Round")
+    }
+
+    #[inline]
+    pub fn get_white(&self) -> Option<&str> {
+        self.get_tag("White")
+    }
+
+    #[inline]
+    pub fn get_black(&self) -> Option<&str> {
+        self.get_tag("Black")
+    }
+
+    /// Result from the token ending the movetext, or from the `Result` tag if there is none.
+    pub fn get_result(&self) -> GameResult {
+        self.result
+            .or_else(|| self.get_tag("Result").and_then(parse_pgn_result))
+            .unwrap_or(GameResult::InProgress)
+    }
+
+    /// Starting position from the `FEN` tag, unless the `SetUp` tag is set to 0.
+    pub fn get_starting_fen(&self) -> Option<&str> {
+        if self.get_tag("SetUp") == Some("0") {
+            return None;
+        }
+        self.get_tag("FEN")
+    }
+
+    /// Variant from the `Variant` tag, where Chess960 is played with the standard rules.
+    pub fn get_variant(&self) -> Result<Variant> {
+        if self.is_chess960() {
+            return Ok(Variant::Standard);
+        }
+        self.get_tag("Variant")
+            .filter(|variant| !variant.eq_ignore_ascii_case("From Position"))
+            .map_or(Ok(Variant::Standard), Variant::from_str)
+    }
+
+    pub fn is_chess960(&self) -> bool {
+        self.get_tag("Variant").is_some_and(|variant| {
+            matches!(
+                variant.to_lowercase().replace(['-', '_', ' '], "").as_str(),
+                "chess960" | "fischerandom" | "960"
+            )
+        })
+    }
+
+    /// Comments before the first move.
+    #[inline]
+    pub fn get_comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    /// Moves of the main line.
+    #[inline]
+    pub fn get_moves(&self) -> &[PgnMove] {
+        &self.moves
+    }
+
+    pub fn get_starting_board(&self) -> Result<Board> {
+        let variant = self.get_variant()?;
+        let mut position_builder = BoardPositionBuilder::from_str(
+            self.get_starting_fen()
+                .unwrap_or(variant.get_starting_fen()),
+        )?;
+        position_builder.variant(variant);
+        if self.is_chess960() {
+            position_builder.chess960(true);
+        }
+        Ok(BoardPosition::try_from(&mut position_builder)?.into())
+    }
+
+    /// Replays the main line from the starting position.
+    pub fn to_board(&self) -> Result<Board> {
+        let mut board = self.get_starting_board()?;
+        for pgn_move in &self.moves {
+            board
+                .push_san(pgn_move.get_san())
+                .map_err(|_| TimecatError::InvalidPgn {
+                    line: pgn_move.get_line(),
+                    column: pgn_move.get_column(),
+                    err_msg: format!("cannot play the move {}", pgn_move.get_san()),
+                })?;
+        }
+        Ok(board)
+    }
+}
+
+impl FromStr for PgnGame {
+    type Err = TimecatError;
+
+    /// Parses the first game of the PGN.
+    fn from_str(s: &str) -> Result<Self> {
+        PgnReader::new(s.as_bytes())
+            .next()
+            .unwrap_or(Err(TimecatError::NoInput))
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum PgnToken {
+    Tag(String, String),
+    MoveNumber,
+    San(String),
+    Nag(u8),
+    Comment(String),
+    VariationStart,
+    VariationEnd,
+    Result(GameResult),
+}
+
+/// Token with the line and the column it starts at.
+type PgnTokenAndPosition = (PgnToken, usize, usize);
+
+/// Streaming reader yielding the games of a PGN one at a time.
+///
+/// After an error the rest of the game is skipped, so that the reading continues from the next one.
+#[derive(Debug)]
+pub struct PgnReader<R: BufRead> {
+    reader: R,
+    line: Vec<char>,
+    line_number: usize,
+    column: usize,
+    is_eof: bool,
+    peeked_token: Option<PgnTokenAndPosition>,
+    is_in_movetext: bool,
+}
+
+impl PgnReader<BufReader<fs::File>> {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(BufReader::new(fs::File::open(path)?)))
+    }
+}
+
+impl<R: BufRead> PgnReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: Vec::new(),
+            line_number: 0,
+            column: 0,
+            is_eof: false,
+            peeked_token: None,
+            is_in_movetext: false,
+        }
+    }
+
+    fn error(line: usize, column: usize, err_msg: impl Into<String>) -> TimecatError {
+        TimecatError::InvalidPgn {
+            line,
+            column,
+            err_msg: err_msg.into(),
+        }
+    }
+
+    fn peek_char(&mut self) -> Result<Option<char>> {
+        while self.column >= self.line.len() {
+            if self.is_eof {
+                return Ok(None);
+            }
+            let mut line = String::new();
+            if self
+                .reader
+                .read_line(&mut line)
+                .inspect_err(|_| self.is_eof = true)?
+                == 0
+            {
+                self.is_eof = true;
+                return Ok(None);
+            }
+            let line = line.trim_end_matches(['\n', '\r']);
+            let line = if self.line_number == 0 {
+                line.trim_start_matches('\u{feff}')
+            } else {
+                line
+            };
+            self.line = line.chars().chain(['\n']).collect();
+            self.line_number += 1;
+            self.column = 0;
+        }
+        Ok(Some(self.line[self.column]))
+    }
+
+    fn read_while(&mut self, predicate: impl Fn(char) -> bool) -> Result<String> {
+        let mut s = String::new();
+        while let Some(c) = self.peek_char()?.filter(|&c| predicate(c)) {
+            s.push(c);
+            self.column += 1;
+        }
+        Ok(s)
+    }
+
+    fn read_tag(&mut self) -> std::result::Result<PgnToken, &'static str> {
+        let line = &self.line;
+        let mut column = self.column;
+        let skip_spaces = |column: &mut usize| {
+            while line[*column] == ' ' || line[*column] == '\t' {
+                *column += 1;
+            }
+        };
+        skip_spaces(&mut column);
+        let name_start = column;
+        while line[column].is_ascii_alphanumeric() || line[column] == '_' {
+            column += 1;
+        }
+        if column == name_start {
+            return Err("the tag has no name");
+        }
+        let name = line[name_start..column].iter().collect();
+        skip_spaces(&mut column);
+        if line[column] != '"' {
+            return Err("the tag has no value");
+        }
+        column += 1;
+        let mut value = String::new();
+        loop {
+            match line[column] {
+                '"' => break,
+                '\n' => return Err("the tag value is not closed"),
+                '\\' if matches!(line[column + 1], '"' | '\\') => {
+                    value.push(line[column + 1]);
+                    column += 2;
+                }
+                c => {
+                    value.push(c);
+                    column += 1;
+                }
+            }
+        }
+        column += 1;
+        skip_spaces(&mut column);
+        if line[column] != ']' {
+            return Err("the tag is not closed");
+        }
+        self.column = column + 1;
+        Ok(PgnToken::Tag(name, value))
+    }
+
+    fn read_token(&mut self) -> Result<Option<PgnTokenAndPosition>> {
+        if let Some(token) = self.peeked_token.take() {
+            return Ok(Some(token));
+        }
+        loop {
+            let Some(c) = self.peek_char()? else {
+                return Ok(None);
+            };
+            let (line, column) = (self.line_number, self.column + 1);
+            // Lines starting with % are escaped.
+            if c == '%' && self.column == 0 {
+                self.column = self.line.len();
+                continue;
+            }
+            self.column += 1;
+            let token = match c {
+                c if c.is_whitespace() || c == '.' => continue,
+                '[' => self.read_tag().map_err(|err_msg| {
+                    self.column = self.line.len();
+                    Self::error(line, column, err_msg)
+                })?,
+                '{' => {
+                    let comment = self.read_while(|c| c != '}')?;
+                    if self.peek_char()?.is_none() {
+                        return Err(Self::error(line, column, "the comment is not closed"));
+                    }
+                    self.column += 1;
+                    PgnToken::Comment(comment)
+                }
+                ';' => PgnToken::Comment(self.read_while(|c| c != '\n')?),
+                '(' => PgnToken::VariationStart,
+                ')' => PgnToken::VariationEnd,
+                '*' => PgnToken::Result(GameResult::InProgress),
+                '$' => PgnToken::Nag(
+                    self.read_while(|c| c.is_ascii_digit())?
+                        .parse()
+                        .map_err(|_| Self::error(line, column, "the NAG is not valid"))?,
+                ),
+                '!' | '?' => {
+                    let suffix = c.to_string() + &self.read_while(|c| c == '!' || c == '?')?;
+                    PgnToken::Nag(match suffix.as_str() {
+                        "!" => 1,
+                        "?" => 2,
+                        "!!" => 3,
+                        "??" => 4,
+                        "!?" => 5,
+                        "?!" => 6,
+                        _ => return Err(Self::error(line, column, "the annotation is not valid")),
+                    })
+                }
+                c if c.is_ascii_alphanumeric() || c == '-' || c == '@' => {
+                    let symbol = c.to_string()
+                        + &self
+                            .read_while(|c| c.is_ascii_alphanumeric() || "+#=:-/@_".contains(c))?;
+                    if let Some(result) = parse_pgn_result(&symbol) {
+                        PgnToken::Result(result)
+                    } else if symbol.chars().all(|c| c.is_ascii_digit()) {
+                        PgnToken::MoveNumber
+                    } else {
+                        PgnToken::San(symbol)
+                    }
+                }
+                c => {
+                    return Err(Self::error(
+                        line,
+                        column,
+                        format!("unexpected character {c:?}"),
+                    ))
+                }
+            };
+            return Ok(Some((token, line, column)));
+        }
+    }
+
+    fn read_game(&mut self) -> Result<Option<PgnGame>> {
+        self.is_in_movetext = false;
+        let mut game = PgnGame::default();
+        let mut is_empty = true;
+        // The main line followed by the variations being read, with the positions they start at.
+        let mut lines: Vec<(Vec<PgnMove>, usize, usize)> = vec![(Vec::new(), 1, 1)];
+        let mut variation_comments: Vec<String> = Vec::new();
+        while let Some((token, line, column)) = self.read_token()? {
+            if let PgnToken::Tag(name, value) = token {
+                if self.is_in_movetext {
+                    self.peeked_token = Some((PgnToken::Tag(name, value), line, column));
+                    break;
+                }
+                game.tags.push((name, value));
+                is_empty = false;
+                continue;
+            }
+            self.is_in_movetext = true;
+            is_empty = false;
+            let is_in_main_line = lines.len() == 1;
+            let moves = &mut lines.last_mut().unwrap().0;
+            match token {
+                PgnToken::Tag(..) | PgnToken::MoveNumber => (),
+                PgnToken::San(san) => {
+                    let mut pgn_move = PgnMove::new(san, line, column);
+                    for comment in variation_comments.drain(..) {
+                        pgn_move.add_comment(&comment);
+                    }
+                    moves.push(pgn_move);
+                }
+                PgnToken::Nag(nag) => moves
+                    .last_mut()
+                    .ok_or_else(|| Self::error(line, column, "the NAG does not follow a move"))?
+                    .nags
+                    .push(nag),
+                PgnToken::Comment(comment) => match moves.last_mut() {
+                    Some(pgn_move) => pgn_move.add_comment(&comment),
+                    None if is_in_main_line => {
+                        let comment = comment.trim();
+                        if !comment.is_empty() {
+                            game.comments.push(comment.to_string());
+                        }
+                    }
+                    None => variation_comments.push(comment),
+                },
+                PgnToken::VariationStart => {
+                    if moves.is_empty() {
+                        return Err(Self::error(
+                            line,
+                            column,
+                            "the variation does not follow a move",
+                        ));
+                    }
+                    lines.push((Vec::new(), line, column));
+                }
+                PgnToken::VariationEnd => {
+                    if lines.len() == 1 {
+                        return Err(Self::error(line, column, "no variation to close"));
+                    }
+                    let (variation, _, _) = lines.pop().unwrap();
+                    variation_comments.clear();
+                    if !variation.is_empty() {
+                        let moves = &mut lines.last_mut().unwrap().0;
+                        moves.last_mut().unwrap().variations.push(variation);
+                    }
+                }
+                PgnToken::Result(result) => {
+                    game.result = Some(result);
+                    break;
+                }
+            }
+        }
+        if is_empty {
+            return Ok(None);
+        }
+        if let Some(&(_, line, column)) = lines.get(1) {
+            return Err(Self::error(line, column, "the variation is not closed"));
+        }
+        game.moves = lines.pop().unwrap().0;
+        Ok(Some(game))
+    }
+
+    /// Skips the tokens until the end of the game being read.
+    fn skip_game(&mut self) {
+        loop {
+            match self.read_token() {
+                Ok(None) | Ok(Some((PgnToken::Result(_), _, _))) => return,
+                Ok(Some((token @ PgnToken::Tag(..), line, column))) => {
+                    if self.is_in_movetext {
+                        self.peeked_token = Some((token, line, column));
+                        return;
+                    }
+                }
+                Ok(Some(_)) => self.is_in_movetext = true,
+                Err(_) => (),
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_game() {
+            Ok(game) => game.map(Ok),
+            Err(err) => {
+                self.skip_game();
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+fn parse_pgn_result(s: &str) -> Option<GameResult> {
+    match s {
+        "1-0" => Some(GameResult::Win(White)),
+        "0-1" => Some(GameResult::Win(Black)),
+        "1/2-1/2" => Some(GameResult::Draw),
+        "*" => Some(GameResult::InProgress),
+        _ => None,
+    }
+}
+
+/// Parses a clock like 1:02:03.5, with the hours and the minutes being optional.
+fn parse_pgn_clock(s: &str) -> Option<Duration> {
+    let mut seconds = 0.0;
+    for part in s.trim().split(':') {
+        seconds = seconds * 60.0 + part.parse::<f64>().ok()?;
+    }
+    Duration::try_from_secs_f64(seconds).ok()
+}
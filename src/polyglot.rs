@@ -114,7 +114,8 @@ impl PolyglotBookReader {
                         .map_err(|_| TimecatError::BadPolyglotFile)?,
                 );
                 if hash == target_hash {
-                    let valid_or_null_move = polyglot_move_int_to_move(move_int)?;
+                    let valid_or_null_move = board
+                        .convert_king_takes_rook_castling(polyglot_move_int_to_move(move_int)?);
                     moves.push(WeightedMove::new(valid_or_null_move, weight as MoveWeight));
                     idx += 1;
                 } else {
@@ -165,7 +166,8 @@ impl PolyglotBook for PolyglotBookReader {
                         .try_into()
                         .map_err(|_| TimecatError::BadPolyglotFile)?,
                 );
-                let valid_or_null_move = polyglot_move_int_to_move(move_int)?;
+                let valid_or_null_move =
+                    board.convert_king_takes_rook_castling(polyglot_move_int_to_move(move_int)?);
                 Ok(WeightedMove::new(valid_or_null_move, weight as MoveWeight))
             })
             .transpose()
@@ -273,7 +275,12 @@ impl PolyglotBookHashMap {
             .map(|entries| {
                 entries
                     .iter()
-                    .map(|entry| entry.get_weighted_move())
+                    .map(|entry| {
+                        WeightedMove::new(
+                            board.convert_king_takes_rook_castling(entry.move_),
+                            entry.weight as MoveWeight,
+                        )
+                    })
                     .collect_vec()
             })
             .unwrap_or_default()
@@ -304,9 +311,14 @@ impl PolyglotBook for PolyglotBookHashMap {
     #[inline]
     fn get_best_weighted_move(&self, board: &Board) -> Option<WeightedMove> {
         //TODO: optimize
-        self.entries_map
-            .get(&board.get_hash())
-            .and_then(|entries| entries.first().map(|entry| entry.get_weighted_move()))
+        self.entries_map.get(&board.get_hash()).and_then(|entries| {
+            entries.first().map(|entry| {
+                WeightedMove::new(
+                    board.convert_king_takes_rook_castling(entry.move_),
+                    entry.weight as MoveWeight,
+                )
+            })
+        })
     }
 }
 
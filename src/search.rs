@@ -41,6 +41,15 @@ impl PVTable {
         self.set_length(ply, *get_item_unchecked!(self.length, ply + 1));
     }
 
+    /// Replaces the principal variation of the root, used when a search is resumed.
+    pub fn set_root_pv(&mut self, pv: &[Move]) {
+        let pv = &pv[..pv.len().min(MAX_PLY)];
+        for (ply, &move_) in pv.iter().enumerate() {
+            *get_item_unchecked_mut!(self.table, 0, ply) = Some(move_);
+        }
+        self.set_length(0, pv.len());
+    }
+
     #[inline]
     pub fn set_length(&mut self, ply: Ply, length: usize) {
         *get_item_unchecked_mut!(self.length, ply) = length;
@@ -70,9 +79,15 @@ pub struct Searcher<P: PositionEvaluation> {
     score: Score,
     depth_completed: Depth,
     is_outside_aspiration_window: bool,
+    iterations: Vec<SearchIteration>,
     clock: Instant,
+    last_checkpoint_time: Instant,
+    checkpoint_writer: CheckpointWriter,
+    progress_to_resume: Option<SearchProgress>,
     stop_command: Arc<AtomicBool>,
     properties: EngineProperties,
+    statistics: Option<SearchStatistics>,
+    search_tree: Option<SearchTree>,
 }
 
 impl<P: PositionEvaluation> Searcher<P> {
@@ -85,6 +100,7 @@ impl<P: PositionEvaluation> Searcher<P> {
         selective_depth: Arc<AtomicUsize>,
         stop_command: Arc<AtomicBool>,
         properties: EngineProperties,
+        move_sorter: MoveSorter,
     ) -> Self {
         Self {
             id,
@@ -94,15 +110,26 @@ impl<P: PositionEvaluation> Searcher<P> {
             transposition_table,
             pv_table: PVTable::new(),
             best_moves: Vec::new(),
-            move_sorter: MoveSorter::new(),
+            move_sorter,
             num_nodes_searched,
             selective_depth,
             ply: 0,
             score: 0,
             depth_completed: 0,
             is_outside_aspiration_window: false,
+            iterations: Vec::new(),
             clock: Instant::now(),
+            last_checkpoint_time: Instant::now(),
+            checkpoint_writer: CheckpointWriter::default(),
+            progress_to_resume: None,
             stop_command,
+            statistics: (id == 0 && properties.collect_search_statistics())
+                .then(SearchStatistics::new),
+            search_tree: properties
+                .get_search_tree_config()
+                .filter(|_| id == 0)
+                .cloned()
+                .map(SearchTree::new),
             properties,
         }
     }
@@ -157,6 +184,59 @@ impl<P: PositionEvaluation> Searcher<P> {
         &self.move_sorter
     }
 
+    /// Makes the next search continue from the iterations of `progress` instead of starting from depth 1.
+    #[inline]
+    pub fn resume_from(&mut self, progress: SearchProgress) {
+        self.progress_to_resume = Some(progress);
+    }
+
+    fn restore_progress(&mut self, progress: SearchProgress) {
+        self.depth_completed = progress.get_depth_completed();
+        self.num_nodes_searched
+            .store(progress.get_num_nodes_searched(), MEMORY_ORDERING);
+        self.clock = Instant::now()
+            .checked_sub(progress.get_time_elapsed())
+            .unwrap_or(self.clock);
+        self.iterations = progress.into_iterations();
+        if let Some(iteration) = self.iterations.last() {
+            self.score = self.initial_position.score_flipped(iteration.get_score());
+            self.pv_table.set_root_pv(iteration.get_pv());
+        }
+    }
+
+    fn save_checkpoint_if_due(&mut self) {
+        if !self.is_main_threaded() {
+            return;
+        }
+        let Some(path) = self.properties.get_checkpoint_path() else {
+            return;
+        };
+        if self.last_checkpoint_time.elapsed() < self.properties.get_checkpoint_interval()
+            || self.checkpoint_writer.is_writing()
+        {
+            return;
+        }
+        let path = path.to_string();
+        let checkpoint = Checkpoint::new(
+            self.board.clone(),
+            self.move_sorter.clone(),
+            SearchProgress::new(
+                self.iterations.clone(),
+                self.get_num_nodes_searched(),
+                self.get_time_elapsed(),
+            ),
+        );
+        // The files are written on another thread, the search only waits for the copy of its state.
+        self.checkpoint_writer
+            .write(checkpoint, path, self.transposition_table.clone());
+        self.last_checkpoint_time = Instant::now();
+    }
+
+    #[inline]
+    pub fn take_move_sorter(&mut self) -> MoveSorter {
+        std::mem::take(&mut self.move_sorter)
+    }
+
     #[inline]
     pub fn get_ply(&self) -> Ply {
         self.ply
@@ -230,11 +310,62 @@ impl<P: PositionEvaluation> Searcher<P> {
         self.is_outside_aspiration_window
     }
 
+    #[inline]
+    pub fn get_iterations(&self) -> &[SearchIteration] {
+        &self.iterations
+    }
+
     #[inline]
     pub fn get_search_info(&self) -> SearchInfo {
         self.into()
     }
 
+    #[inline]
+    pub fn get_search_statistics(&self) -> Option<&SearchStatistics> {
+        self.statistics.as_ref()
+    }
+
+    #[inline]
+    fn update_statistics(&mut self, func: impl FnOnce(&mut SearchStatistics)) {
+        if let Some(statistics) = self.statistics.as_mut() {
+            func(statistics);
+        }
+    }
+
+    #[inline]
+    pub fn get_search_tree(&self) -> Option<&SearchTree> {
+        self.search_tree.as_ref()
+    }
+
+    #[inline]
+    pub fn take_search_tree(&mut self) -> Option<SearchTree> {
+        self.search_tree.take()
+    }
+
+    #[inline]
+    fn update_search_tree(&mut self, func: impl FnOnce(&mut SearchTree)) {
+        if let Some(search_tree) = self.search_tree.as_mut() {
+            func(search_tree);
+        }
+    }
+
+    #[inline]
+    fn open_search_tree_node(
+        &mut self,
+        depth: Depth,
+        alpha: Score,
+        beta: Score,
+        is_quiescence: bool,
+    ) {
+        if self.search_tree.is_some() {
+            let last_move = self.board.get_last_stack_move();
+            let ply = self.ply;
+            self.update_search_tree(|search_tree| {
+                search_tree.open_node(last_move, ply, depth, alpha, beta, is_quiescence)
+            });
+        }
+    }
+
     #[inline]
     pub fn stop_search_at_every_node(
         &mut self,
@@ -313,8 +444,7 @@ impl<P: PositionEvaluation> Searcher<P> {
                     .unwrap_or_else(|| self.board.generate_legal_moves().collect_vec()),
                 &self.transposition_table,
                 0,
-                self.transposition_table
-                    .read_best_move(self.board.get_hash()),
+                self.transposition_table.read_legal_best_move(&self.board),
                 self.get_best_move(),
             )
             .map(|WeightedMove { move_, .. }| {
@@ -350,11 +480,13 @@ impl<P: PositionEvaluation> Searcher<P> {
             self.selective_depth.store(0, MEMORY_ORDERING);
         }
         if self.board.is_game_over() {
-            return if self.board.is_checkmate() {
-                Some(-self.evaluator.evaluate_checkmate_in(0))
-            } else {
-                Some(self.evaluator.evaluate_draw())
-            };
+            return Some(match self.board.result() {
+                GameResult::Win(color) if color == self.board.turn() => {
+                    self.evaluator.evaluate_checkmate_in(0)
+                }
+                GameResult::Win(_) => -self.evaluator.evaluate_checkmate_in(0),
+                _ => self.evaluator.evaluate_draw(),
+            });
         }
         if !(depth > 1 && self.is_main_threaded()) {
             controller = None;
@@ -373,11 +505,12 @@ impl<P: PositionEvaluation> Searcher<P> {
             }
             let clock = Instant::now();
             self.push_unchecked(move_);
+            let child_depth = plies_to_fractional_depth(depth - 1);
             if move_index == 0
-                || -self.alpha_beta(depth - 1, -alpha - 1, -alpha, controller.as_deref_mut())?
+                || -self.alpha_beta(child_depth, -alpha - 1, -alpha, controller.as_deref_mut())?
                     > alpha
             {
-                score = -self.alpha_beta(depth - 1, -beta, -alpha, controller.as_deref_mut())?;
+                score = -self.alpha_beta(child_depth, -beta, -alpha, controller.as_deref_mut())?;
             }
             self.pop();
             if print_move_info && self.is_main_threaded() {
@@ -418,20 +551,41 @@ impl<P: PositionEvaluation> Searcher<P> {
         Some(alpha)
     }
 
-    fn get_lmr_reduction(depth: Depth, move_index: usize, is_pv_node: bool) -> Depth {
+    fn get_lmr_reduction(
+        depth: FractionalDepth,
+        move_index: usize,
+        is_pv_node: bool,
+    ) -> FractionalDepth {
+        let depth_in_plies = depth as f64 / ONE_PLY as f64;
         let mut reduction =
-            LMR_BASE_REDUCTION + (depth as f64).ln() * (move_index as f64).ln() / LMR_MOVE_DIVIDER;
+            LMR_BASE_REDUCTION + depth_in_plies.ln() * (move_index as f64).ln() / LMR_MOVE_DIVIDER;
         // let mut reduction = (depth as f64 - 1.0).max(0.0).sqrt() + (move_index as f64 - 1.0).max(0.0).sqrt();
         if is_pv_node {
             // reduction /= 3.0;
             reduction *= 2.0 / 3.0;
         }
-        reduction.round() as Depth
+        (reduction * ONE_PLY as f64).round() as FractionalDepth
     }
 
     fn alpha_beta(
         &mut self,
-        mut depth: Depth,
+        depth: FractionalDepth,
+        alpha: Score,
+        beta: Score,
+        controller: Option<&mut impl SearchControl<Self>>,
+    ) -> Option<Score> {
+        if self.search_tree.is_none() {
+            return self.alpha_beta_internal(depth, alpha, beta, controller);
+        }
+        self.open_search_tree_node(fractional_depth_to_plies(depth), alpha, beta, false);
+        let score = self.alpha_beta_internal(depth, alpha, beta, controller);
+        self.update_search_tree(|search_tree| search_tree.close_node(score));
+        score
+    }
+
+    fn alpha_beta_internal(
+        &mut self,
+        mut depth: FractionalDepth,
         mut alpha: Score,
         mut beta: Score,
         mut controller: Option<&mut impl SearchControl<Self>>,
@@ -439,6 +593,9 @@ impl<P: PositionEvaluation> Searcher<P> {
         self.pv_table.set_length(self.ply, self.ply);
         let mate_score = self.evaluator.evaluate_checkmate_in(self.ply);
         let draw_score = self.evaluator.evaluate_draw();
+        if let Some(score) = self.get_variant_end_score() {
+            return Some(score);
+        }
         if self.board.is_other_draw() {
             return Some(draw_score);
         }
@@ -447,22 +604,27 @@ impl<P: PositionEvaluation> Searcher<P> {
             alpha = alpha.max(-mate_score);
             beta = beta.min(mate_score - 1);
             if alpha >= beta {
+                self.update_search_tree(|search_tree| {
+                    search_tree.add_pruning(SearchTreePruning::MateDistancePruning)
+                });
                 return Some(alpha);
             }
         }
         let checkers = self.board.get_checkers();
-        if depth > 10 {
-            depth += checkers.popcnt() as Depth;
+        if depth > 10 * ONE_PLY {
+            depth += checkers.popcnt() as FractionalDepth * ONE_PLY;
         }
-        let min_depth = self.move_sorter.is_following_pv() as Depth;
+        let min_depth = self.move_sorter.is_following_pv() as FractionalDepth * ONE_PLY;
         depth = depth.max(min_depth);
+        let depth_in_plies = fractional_depth_to_plies(depth);
         let is_pv_node = alpha != beta - 1;
         let key = self.board.get_hash();
         let best_move = if is_pv_node && self.is_main_threaded() {
             self.transposition_table.read_best_move(key)
         } else {
-            let (optional_data, best_move) = self.transposition_table.read(key, depth, self.ply);
-            if let Some((score, flag)) = optional_data {
+            let (optional_data, best_move) =
+                self.transposition_table.read(key, depth_in_plies, self.ply);
+            let optional_tt_score = optional_data.and_then(|(score, flag)| {
                 // match flag {
                 //     HashExact => return Some(score),
                 //     HashAlpha => alpha = alpha.max(score),
@@ -472,18 +634,21 @@ impl<P: PositionEvaluation> Searcher<P> {
                 //     return Some(alpha);
                 // }
                 match flag {
-                    EntryFlagHash::Exact => return Some(score),
-                    EntryFlagHash::Alpha => {
-                        if score <= alpha {
-                            return Some(score);
-                        }
-                    }
-                    EntryFlagHash::Beta => {
-                        if score >= beta {
-                            return Some(score);
-                        }
-                    }
+                    EntryFlagHash::Exact => Some(score),
+                    EntryFlagHash::Alpha => (score <= alpha).then_some(score),
+                    EntryFlagHash::Beta => (score >= beta).then_some(score),
                 }
+            });
+            let is_tt_hit = optional_data.is_some() || best_move.is_some();
+            self.update_statistics(|statistics| {
+                statistics.on_tt_probe(is_tt_hit, optional_tt_score.is_some())
+            });
+            self.update_search_tree(|search_tree| search_tree.set_tt_hit(is_tt_hit));
+            if optional_tt_score.is_some() {
+                self.update_search_tree(|search_tree| {
+                    search_tree.add_pruning(SearchTreePruning::TranspositionTableCutoff)
+                });
+                return optional_tt_score;
             }
             best_move
         };
@@ -494,45 +659,59 @@ impl<P: PositionEvaluation> Searcher<P> {
         if self.stop_search_at_every_node(controller.as_deref_mut()) {
             return None;
         }
-        if depth == 0 {
+        if depth < ONE_PLY {
             return Some(self.quiescence(alpha, beta));
         }
         if self.is_main_threaded() && is_pv_node {
             self.selective_depth.fetch_max(self.ply, MEMORY_ORDERING);
         }
         self.num_nodes_searched.fetch_add(1, MEMORY_ORDERING);
+        let ply = self.ply;
+        self.update_statistics(|statistics| statistics.on_node(ply));
         let not_in_check = checkers.is_empty();
         let mut futility_pruning = false;
         if not_in_check && !DISABLE_ALL_PRUNINGS {
             // static evaluation
             let static_evaluation = self.evaluate_flipped();
-            if depth < 3 && !is_pv_node && !is_checkmate(beta) {
-                let eval_margin = ((6 * PAWN_VALUE) / 5) * depth as Score;
+            self.update_search_tree(|search_tree| {
+                search_tree.set_static_evaluation(static_evaluation)
+            });
+            if depth < 3 * ONE_PLY && !is_pv_node && !is_checkmate(beta) {
+                let eval_margin = (((6 * PAWN_VALUE) / 5) * depth / ONE_PLY) as Score;
                 let new_score = static_evaluation - eval_margin;
                 if new_score >= beta {
+                    self.update_search_tree(|search_tree| {
+                        search_tree.add_pruning(SearchTreePruning::StaticEvaluationPruning)
+                    });
                     return Some(new_score);
                 }
             }
             // razoring
-            const RAZORING_DEPTH: Depth = 3;
+            const RAZORING_DEPTH: FractionalDepth = 3 * ONE_PLY;
             if !is_pv_node && depth <= RAZORING_DEPTH && !is_checkmate(beta) {
                 let mut score = static_evaluation + const { (5 * PAWN_VALUE) / 4 };
                 if score < beta {
-                    if depth == 1 {
+                    if depth < 2 * ONE_PLY {
                         let new_score = self.quiescence(alpha, beta);
+                        self.update_search_tree(|search_tree| {
+                            search_tree.add_pruning(SearchTreePruning::Razoring)
+                        });
                         return Some(new_score.max(score));
                     }
                     score += const { (7 * PAWN_VALUE) / 4 };
                     if score < beta && depth < RAZORING_DEPTH {
                         let new_score = self.quiescence(alpha, beta);
                         if new_score < beta {
+                            self.update_search_tree(|search_tree| {
+                                search_tree.add_pruning(SearchTreePruning::Razoring)
+                            });
                             return Some(new_score.max(score));
                         }
                     }
                 }
             }
             // null move pruning
-            if depth >= NULL_MOVE_MIN_DEPTH
+            if depth >= NULL_MOVE_MIN_DEPTH as FractionalDepth * ONE_PLY
                 && static_evaluation >= beta
                 && self.board.has_non_pawn_material()
             {
@@ -540,19 +719,23 @@ impl<P: PositionEvaluation> Searcher<P> {
                 //     + (depth.max(NULL_MOVE_MIN_DEPTH) as f64 / NULL_MOVE_DEPTH_DIVIDER as f64)
                 //         .round() as Depth;
                 // let reduced_depth = depth - r - 1;
-                let r = 1920 + (depth as i32) * 2368;
-                let reduced_depth = (((depth as u32) * 4096 - (r as u32)) / 4096) as Depth;
+                let r = 1920 * ONE_PLY as i32 + (depth as i32) * 2368;
+                let reduced_depth = ((depth as i32 * 4096 - r) / 4096).max(0) as FractionalDepth;
                 self.push_unchecked(ValidOrNullMove::NullMove);
                 let score =
                     -self.alpha_beta(reduced_depth, -beta, -beta + 1, controller.as_deref_mut())?;
                 self.pop();
+                self.update_statistics(|statistics| statistics.on_null_move_search(score >= beta));
                 if score >= beta {
+                    self.update_search_tree(|search_tree| {
+                        search_tree.add_pruning(SearchTreePruning::NullMovePruning)
+                    });
                     return Some(beta);
                 }
             }
             // futility pruning condition
-            if depth < 4 && alpha < mate_score {
-                let futility_margin = match depth {
+            if depth < 4 * ONE_PLY && alpha < mate_score {
+                let futility_margin = match depth_in_plies {
                     0 => 0,
                     1 => PAWN_VALUE,
                     2 => const { Knight.evaluate() },
@@ -563,65 +746,73 @@ impl<P: PositionEvaluation> Searcher<P> {
             }
         }
         let mut flag = EntryFlagHash::Alpha;
-        let weighted_moves = self.move_sorter.get_weighted_moves_sorted(
+        let tt_move = best_move.or_else(|| self.transposition_table.read_best_move(key));
+        let pv_move = self.get_nth_pv_move(self.ply);
+        let mut move_picker = MovePicker::new(
+            &mut self.move_sorter,
             &self.board,
-            self.board.generate_legal_moves(),
-            &self.transposition_table,
             self.ply,
-            best_move,
-            self.get_nth_pv_move(self.ply),
+            tt_move,
+            pv_move,
         );
-        if weighted_moves.is_empty() {
-            return if not_in_check {
-                Some(draw_score)
-            } else {
-                Some(-mate_score)
-            };
-        }
-        for (move_index, WeightedMove { move_, .. }) in weighted_moves.enumerate() {
+        let mut num_moves = 0;
+        while let Some(move_) = move_picker.next_move(&self.board, &self.move_sorter) {
+            let move_index = num_moves;
+            num_moves += 1;
             let not_capture_move = !self.board.is_capture(move_);
             let not_an_interesting_position = not_capture_move
                 && not_in_check
                 && move_.get_promotion().is_none()
                 && !self.move_sorter.is_killer_move(move_, self.ply);
             if move_index != 0 && futility_pruning && not_an_interesting_position {
+                self.update_search_tree(|search_tree| {
+                    search_tree.add_pruning(SearchTreePruning::FutilityPruning { move_ })
+                });
                 continue;
             }
             let mut safe_to_apply_lmr = move_index >= FULL_DEPTH_SEARCH_LMR
-                && depth >= REDUCTION_LIMIT_LMR
+                && depth >= REDUCTION_LIMIT_LMR as FractionalDepth * ONE_PLY
                 && self.properties.use_lmr()
                 && not_an_interesting_position;
             self.push_unchecked(move_);
             safe_to_apply_lmr &= !self.board.is_check();
             let mut score: Score;
             if move_index == 0 {
-                score = -self.alpha_beta(depth - 1, -beta, -alpha, controller.as_deref_mut())?;
+                score =
+                    -self.alpha_beta(depth - ONE_PLY, -beta, -alpha, controller.as_deref_mut())?;
             } else {
                 if safe_to_apply_lmr {
                     let lmr_reduction = Self::get_lmr_reduction(depth, move_index, is_pv_node);
+                    self.update_search_tree(|search_tree| {
+                        search_tree.add_pruning(SearchTreePruning::LateMoveReduction {
+                            move_,
+                            reduction: lmr_reduction,
+                        })
+                    });
                     score = if depth > lmr_reduction {
                         -self.alpha_beta(
-                            depth - 1 - lmr_reduction,
+                            depth - ONE_PLY - lmr_reduction,
                             -alpha - 1,
                             -alpha,
                             controller.as_deref_mut(),
                         )?
                     } else {
                         alpha + 1
-                    }
+                    };
+                    self.update_statistics(|statistics| statistics.on_lmr_search(score > alpha));
                 } else {
                     score = alpha + 1;
                 }
                 if score > alpha {
                     score = -self.alpha_beta(
-                        depth - 1,
+                        depth - ONE_PLY,
                         -alpha - 1,
                         -alpha,
                         controller.as_deref_mut(),
                     )?;
                     if score > alpha && score < beta {
                         score = -self.alpha_beta(
-                            depth - 1,
+                            depth - ONE_PLY,
                             -beta,
                             -alpha,
                             controller.as_deref_mut(),
@@ -635,12 +826,14 @@ impl<P: PositionEvaluation> Searcher<P> {
                 self.pv_table.update_table(self.ply, move_);
                 alpha = score;
                 if not_capture_move {
-                    self.move_sorter.add_history_move(move_, &self.board, depth);
+                    self.move_sorter
+                        .add_history_move(move_, &self.board, depth_in_plies);
                 }
                 if score >= beta {
+                    self.update_statistics(|statistics| statistics.on_beta_cutoff(move_index));
                     self.transposition_table.write(
                         key,
-                        depth,
+                        depth_in_plies,
                         self.ply,
                         beta,
                         EntryFlagHash::Beta,
@@ -648,15 +841,25 @@ impl<P: PositionEvaluation> Searcher<P> {
                     );
                     if not_capture_move {
                         self.move_sorter.update_killer_moves(move_, self.ply);
+                        self.move_sorter.update_counter_move(move_, &self.board);
                     }
                     return Some(beta);
                 }
             }
         }
+        if num_moves == 0 {
+            return if !not_in_check {
+                Some(-mate_score)
+            } else if self.board.get_variant().is_stalemate_win() {
+                Some(mate_score)
+            } else {
+                Some(draw_score)
+            };
+        }
         if !self.stop_search_at_every_node(controller) {
             self.transposition_table.write(
                 key,
-                depth,
+                depth_in_plies,
                 self.ply,
                 alpha,
                 flag,
@@ -666,11 +869,35 @@ impl<P: PositionEvaluation> Searcher<P> {
         Some(alpha)
     }
 
-    fn quiescence(&mut self, mut alpha: Score, beta: Score) -> Score {
+    /// Score of the position if the game has been ended by the rules of the variant.
+    fn get_variant_end_score(&mut self) -> Option<Score> {
+        let status = self.board.get_variant_status()?;
+        let mate_score = self.evaluator.evaluate_checkmate_in(self.ply);
+        Some(match status.get_winner(self.board.turn()) {
+            Some(color) if color == self.board.turn() => mate_score,
+            Some(_) => -mate_score,
+            None => self.evaluator.evaluate_draw(),
+        })
+    }
+
+    fn quiescence(&mut self, alpha: Score, beta: Score) -> Score {
+        if self.search_tree.is_none() {
+            return self.quiescence_internal(alpha, beta);
+        }
+        self.open_search_tree_node(0, alpha, beta, true);
+        let score = self.quiescence_internal(alpha, beta);
+        self.update_search_tree(|search_tree| search_tree.close_node(Some(score)));
+        score
+    }
+
+    fn quiescence_internal(&mut self, mut alpha: Score, beta: Score) -> Score {
         if self.ply == MAX_PLY - 1 {
             return self.evaluate_flipped();
         }
         self.pv_table.set_length(self.ply, self.ply);
+        if let Some(score) = self.get_variant_end_score() {
+            return score;
+        }
         if self.board.is_other_draw() {
             return self.evaluator.evaluate_draw();
         }
@@ -679,7 +906,10 @@ impl<P: PositionEvaluation> Searcher<P> {
             self.selective_depth.fetch_max(self.ply, MEMORY_ORDERING);
         }
         self.num_nodes_searched.fetch_add(1, MEMORY_ORDERING);
+        let ply = self.ply;
+        self.update_statistics(|statistics| statistics.on_quiescence_node(ply));
         let evaluation = self.evaluate_flipped();
+        self.update_search_tree(|search_tree| search_tree.set_static_evaluation(evaluation));
         if evaluation >= beta {
             return beta;
         }
@@ -707,6 +937,9 @@ impl<P: PositionEvaluation> Searcher<P> {
                 delta += piece.evaluate() - PAWN_VALUE;
             }
             if score + delta < alpha {
+                self.update_search_tree(|search_tree| {
+                    search_tree.add_pruning(SearchTreePruning::DeltaPruning)
+                });
                 return alpha;
             }
         }
@@ -725,21 +958,27 @@ impl<P: PositionEvaluation> Searcher<P> {
         controller.on_receiving_search_config(config, self);
         let mut alpha = -INFINITY;
         let mut beta = INFINITY;
+        let mut aspiration_re_searches = Vec::new();
+        let mut failed_iteration = None;
         self.depth_completed = 0;
+        self.iterations.clear();
+        self.last_checkpoint_time = Instant::now();
+        if let Some(progress) = self.progress_to_resume.take() {
+            self.restore_progress(progress);
+        }
         while self.depth_completed < Depth::MAX
             && !self.stop_command.load(MEMORY_ORDERING)
             && !controller.stop_search_at_root_node(self)
         {
             let last_score = self.score;
-            self.score = self
-                .search(
-                    self.depth_completed + 1,
-                    alpha,
-                    beta,
-                    Some(&mut controller),
-                    verbose,
-                )
-                .unwrap_or(last_score);
+            let depth = self.depth_completed + 1;
+            self.update_search_tree(|search_tree| {
+                search_tree.clear();
+                search_tree.open_node(None, 0, depth, alpha, beta, false);
+            });
+            let optional_score = self.search(depth, alpha, beta, Some(&mut controller), verbose);
+            self.update_search_tree(|search_tree| search_tree.close_node(optional_score));
+            self.score = optional_score.unwrap_or(last_score);
             let search_info = self.get_search_info();
             if verbose && self.is_main_threaded() {
                 search_info.print_info();
@@ -750,6 +989,31 @@ impl<P: PositionEvaluation> Searcher<P> {
                 if verbose && self.is_main_threaded() {
                     search_info.print_warning_message(alpha, beta);
                 }
+                if optional_score.is_some() {
+                    let (window_alpha, window_beta) = if self.initial_position.turn() == White {
+                        (alpha, beta)
+                    } else {
+                        (-beta, -alpha)
+                    };
+                    let re_search = AspirationReSearch::new(
+                        window_alpha,
+                        window_beta,
+                        self.initial_position.score_flipped(self.score),
+                        self.get_num_nodes_searched(),
+                        self.get_time_elapsed(),
+                    );
+                    // Reported if the search stops before the re-search completes.
+                    failed_iteration = Some(SearchIteration::new(
+                        depth,
+                        re_search.get_score(),
+                        re_search.get_bound(),
+                        self.get_num_nodes_searched(),
+                        self.get_time_elapsed(),
+                        search_info.get_pv().to_vec(),
+                        aspiration_re_searches.clone(),
+                    ));
+                    aspiration_re_searches.push(re_search);
+                }
                 alpha = -INFINITY;
                 beta = INFINITY;
                 self.score = last_score;
@@ -762,8 +1026,28 @@ impl<P: PositionEvaluation> Searcher<P> {
             };
             alpha = self.score - cutoff;
             beta = self.score + cutoff;
+            if optional_score.is_some() {
+                failed_iteration = None;
+                self.iterations.push(SearchIteration::new(
+                    depth,
+                    self.initial_position.score_flipped(self.score),
+                    EntryFlagHash::Exact,
+                    self.get_num_nodes_searched(),
+                    self.get_time_elapsed(),
+                    search_info.get_pv().to_vec(),
+                    std::mem::take(&mut aspiration_re_searches),
+                ));
+                self.save_checkpoint_if_due();
+                self.update_statistics(|statistics| statistics.on_iteration_completion());
+            }
             self.depth_completed += 1;
         }
+        self.iterations.extend(failed_iteration);
+        self.checkpoint_writer.wait();
+        let transposition_table = self.transposition_table.clone();
+        self.update_statistics(|statistics| {
+            statistics.update_transposition_table_statistics(&transposition_table)
+        });
     }
 }
 
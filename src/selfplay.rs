@@ -75,6 +75,7 @@ pub fn self_play(
             println_info("Nodes per second", nps);
         }
     }
+    engine.on_game_end(engine.get_board().result());
     let mean = time_taken_vec.iter().sum::<f64>() / time_taken_vec.len() as f64;
     let std_err = (time_taken_vec
         .iter()
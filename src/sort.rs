@@ -87,6 +87,8 @@ pub struct MoveSorter {
     killer_moves: [SerdeWrapper<[Option<Move>; NUM_KILLER_MOVES]>; MAX_PLY],
     #[cfg_attr(feature = "serde", serde(with = "SerdeHandler"))]
     history_move_scores: [SerdeWrapper<[MoveWeight; 64]>; 12],
+    #[cfg_attr(feature = "serde", serde(with = "SerdeHandler"))]
+    counter_moves: [SerdeWrapper<[Option<Move>; 64]>; 12],
     follow_pv: bool,
     score_pv: bool,
 }
@@ -101,6 +103,22 @@ impl MoveSorter {
             .fill(const { SerdeWrapper::new([None; NUM_KILLER_MOVES]) });
         self.history_move_scores
             .fill(const { SerdeWrapper::new([0; 64]) });
+        self.counter_moves
+            .fill(const { SerdeWrapper::new([None; 64]) });
+        self.follow_pv = false;
+        self.score_pv = false;
+    }
+
+    /// Carries the tables over to a search starting `num_plies` deeper in the same game.
+    pub fn age(&mut self, num_plies: Ply) {
+        let num_plies = num_plies.min(MAX_PLY);
+        self.killer_moves.rotate_left(num_plies);
+        self.killer_moves[MAX_PLY - num_plies..]
+            .fill(const { SerdeWrapper::new([None; NUM_KILLER_MOVES]) });
+        self.history_move_scores
+            .iter_mut()
+            .flat_map(|scores| scores.iter_mut())
+            .for_each(|score| *score /= 2);
         self.follow_pv = false;
         self.score_pv = false;
     }
@@ -116,20 +134,51 @@ impl MoveSorter {
         get_item_unchecked!(self.killer_moves, ply).contains(&Some(move_))
     }
 
+    #[inline]
+    pub fn get_killer_moves(&self, ply: Ply) -> [Option<Move>; NUM_KILLER_MOVES] {
+        **get_item_unchecked!(self.killer_moves, ply)
+    }
+
+    /// Remembers `counter_move` as the refutation of the last move played in `board`.
+    pub fn update_counter_move(&mut self, counter_move: Move, board: &Board) {
+        let Some(previous_move) = board
+            .get_last_stack_move()
+            .and_then(|valid_or_null_move| valid_or_null_move.into_inner().copied())
+        else {
+            return;
+        };
+        let Some(piece) = board.get_piece_at(previous_move.get_dest()) else {
+            return;
+        };
+        *get_item_unchecked_mut!(
+            self.counter_moves,
+            piece.to_index(),
+            previous_move.get_dest().to_index()
+        ) = Some(counter_move);
+    }
+
+    pub fn get_counter_move(&self, board: &Board) -> Option<Move> {
+        let previous_move = *board.get_last_stack_move()?.into_inner()?;
+        let piece = board.get_piece_at(previous_move.get_dest())?;
+        *get_item_unchecked!(
+            self.counter_moves,
+            piece.to_index(),
+            previous_move.get_dest().to_index()
+        )
+    }
+
     pub fn add_history_move(&mut self, history_move: Move, position: &BoardPosition, depth: Depth) {
         let depth = (depth as MoveWeight).pow(2);
-        let src = history_move.get_source();
         let dest = history_move.get_dest();
-        let piece = position.get_piece_at(src).unwrap();
+        let piece = position.get_moving_piece(history_move).unwrap();
         *get_item_unchecked_mut!(self.history_move_scores, piece.to_index(), dest.to_index()) +=
             depth;
     }
 
     #[inline]
     pub fn get_history_score(&self, history_move: Move, position: &BoardPosition) -> MoveWeight {
-        let src = history_move.get_source();
         let dest = history_move.get_dest();
-        let piece = position.get_piece_at(src).unwrap();
+        let piece = position.get_moving_piece(history_move).unwrap();
         *get_item_unchecked!(self.history_move_scores, piece.to_index(), dest.to_index())
     }
 
@@ -194,10 +243,7 @@ impl MoveSorter {
     fn mvv_lva(move_: Move, position: &BoardPosition) -> MoveWeight {
         *get_item_unchecked!(
             MVV_LVA,
-            position
-                .get_piece_type_at(move_.get_source())
-                .unwrap()
-                .to_index(),
+            position.get_moving_piece_type(move_).unwrap().to_index(),
             position
                 .get_piece_type_at(move_.get_dest())
                 .unwrap_or(Pawn)
@@ -279,7 +325,7 @@ impl MoveSorter {
         let move_made_position = position.make_move_new(move_);
         // check
         let checkers = move_made_position.get_checkers();
-        let moving_piece = position.get_piece_type_at(move_.get_source()).unwrap();
+        let moving_piece = position.get_moving_piece_type(move_).unwrap();
         if !checkers.is_empty() {
             return -700000 + 10 * checkers.popcnt() as MoveWeight - moving_piece as MoveWeight;
         }
@@ -405,8 +451,215 @@ impl Default for MoveSorter {
         Self {
             killer_moves: const { [SerdeWrapper::new([None; NUM_KILLER_MOVES]); MAX_PLY] },
             history_move_scores: const { [SerdeWrapper::new([0; 64]); 12] },
+            counter_moves: const { [SerdeWrapper::new([None; 64]); 12] },
             follow_pv: false,
             score_pv: false,
         }
     }
 }
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum MovePickerStage {
+    PvMove,
+    TTMove,
+    GenerateCaptures,
+    GoodCaptures,
+    Killers,
+    CounterMove,
+    GenerateQuiets,
+    Quiets,
+    BadCaptures,
+    Done,
+}
+
+/// Yields the moves of a position stage by stage, generating the moves of a stage only when it is reached.
+#[derive(Clone, Debug)]
+pub struct MovePicker {
+    stage: MovePickerStage,
+    ply: Ply,
+    pv_move: Option<Move>,
+    tt_move: Option<Move>,
+    picked_moves: ArrayVec<Move, { NUM_KILLER_MOVES + 3 }>,
+    killer_index: usize,
+    good_captures: WeightedMoveListSorter,
+    bad_captures: Vec<WeightedMove>,
+    quiets: WeightedMoveListSorter,
+}
+
+impl MovePicker {
+    pub fn new(
+        move_sorter: &mut MoveSorter,
+        position: &BoardPosition,
+        ply: Ply,
+        tt_move: Option<Move>,
+        pv_move: Option<Move>,
+    ) -> Self {
+        let pv_move = if move_sorter.follow_pv {
+            let pv_move = pv_move.filter(|&move_| Self::is_valid(position, move_));
+            move_sorter.follow_pv = pv_move.is_some();
+            pv_move
+        } else {
+            None
+        };
+        move_sorter.score_pv = false;
+        Self {
+            stage: MovePickerStage::PvMove,
+            ply,
+            pv_move,
+            tt_move,
+            picked_moves: ArrayVec::new(),
+            killer_index: 0,
+            good_captures: WeightedMoveListSorter::from_iter([]),
+            bad_captures: Vec::new(),
+            quiets: WeightedMoveListSorter::from_iter([]),
+        }
+    }
+
+    #[inline]
+    pub fn get_stage(&self) -> MovePickerStage {
+        self.stage
+    }
+
+    #[inline]
+    fn is_valid(position: &BoardPosition, move_: Move) -> bool {
+        position.is_legal(&move_)
+    }
+
+    #[inline]
+    fn is_quiet(position: &BoardPosition, move_: Move) -> bool {
+        move_.get_promotion().is_none() && !position.is_capture(move_)
+    }
+
+    #[inline]
+    fn get_capture_targets(position: &BoardPosition) -> BitBoard {
+        let mut targets = position.opponent_occupied();
+        if let Some(ep_square) = position.ep_square() {
+            targets ^= ep_square.to_bitboard();
+        }
+        targets
+    }
+
+    fn try_pick(&mut self, position: &BoardPosition, move_: Option<Move>) -> Option<Move> {
+        let move_ = move_?;
+        if self.picked_moves.contains(&move_) || !Self::is_valid(position, move_) {
+            return None;
+        }
+        self.picked_moves.push(move_);
+        Some(move_)
+    }
+
+    fn generate_captures(&mut self, position: &BoardPosition) {
+        let captures =
+            position.generate_masked_legal_moves(BB_ALL, Self::get_capture_targets(position));
+        let promotions = position.generate_masked_legal_moves(
+            position.get_colored_piece_mask(Pawn, position.turn()),
+            BB_BACKRANKS & !position.occupied(),
+        );
+        let mut good_captures = Vec::new();
+        for move_ in captures.chain(promotions) {
+            if self.picked_moves.contains(&move_) {
+                continue;
+            }
+            let see_score = if position.is_capture(move_) {
+                MoveSorter::see_capture(move_.get_dest(), position)
+            } else {
+                0
+            };
+            let promotion_score = move_
+                .get_promotion()
+                .map_or(0, |piece_type| piece_type.evaluate() - PAWN_VALUE);
+            let weighted_move = WeightedMove::new(
+                move_,
+                (see_score + promotion_score) as MoveWeight
+                    + MoveSorter::mvv_lva(move_, position) as MoveWeight,
+            );
+            if see_score < 0 || matches!(move_.get_promotion(), Some(Knight | Bishop | Rook)) {
+                self.bad_captures.push(weighted_move);
+            } else {
+                good_captures.push(weighted_move);
+            }
+        }
+        self.good_captures = WeightedMoveListSorter::from_iter(good_captures);
+    }
+
+    fn generate_quiets(&mut self, position: &BoardPosition, move_sorter: &MoveSorter) {
+        self.quiets = position
+            .generate_masked_legal_moves(BB_ALL, !Self::get_capture_targets(position))
+            .filter(|&move_| move_.get_promotion().is_none() && !self.picked_moves.contains(&move_))
+            .map(|move_| WeightedMove::new(move_, move_sorter.get_history_score(move_, position)))
+            .collect();
+    }
+
+    /// Next move to be searched, `board` needs to be at the position the picker was created for.
+    pub fn next_move(&mut self, board: &Board, move_sorter: &MoveSorter) -> Option<Move> {
+        loop {
+            match self.stage {
+                MovePickerStage::PvMove => {
+                    self.stage = MovePickerStage::TTMove;
+                    if let Some(move_) = self.try_pick(board, self.pv_move) {
+                        return Some(move_);
+                    }
+                }
+                MovePickerStage::TTMove => {
+                    self.stage = MovePickerStage::GenerateCaptures;
+                    if let Some(move_) = self.try_pick(board, self.tt_move) {
+                        return Some(move_);
+                    }
+                }
+                MovePickerStage::GenerateCaptures => {
+                    self.generate_captures(board);
+                    self.stage = MovePickerStage::GoodCaptures;
+                }
+                MovePickerStage::GoodCaptures => {
+                    if let Some(WeightedMove { move_, .. }) = self.good_captures.next() {
+                        return Some(move_);
+                    }
+                    self.stage = MovePickerStage::Killers;
+                }
+                MovePickerStage::Killers => {
+                    let Some(&killer_move) = move_sorter
+                        .get_killer_moves(self.ply)
+                        .get(self.killer_index)
+                    else {
+                        self.stage = MovePickerStage::CounterMove;
+                        continue;
+                    };
+                    self.killer_index += 1;
+                    let killer_move = killer_move.filter(|&move_| Self::is_quiet(board, move_));
+                    if let Some(move_) = self.try_pick(board, killer_move) {
+                        return Some(move_);
+                    }
+                }
+                MovePickerStage::CounterMove => {
+                    self.stage = MovePickerStage::GenerateQuiets;
+                    let counter_move = move_sorter
+                        .get_counter_move(board)
+                        .filter(|&move_| Self::is_quiet(board, move_));
+                    if let Some(move_) = self.try_pick(board, counter_move) {
+                        return Some(move_);
+                    }
+                }
+                MovePickerStage::GenerateQuiets => {
+                    self.generate_quiets(board, move_sorter);
+                    self.stage = MovePickerStage::Quiets;
+                }
+                MovePickerStage::Quiets => {
+                    if let Some(WeightedMove { move_, .. }) = self.quiets.next() {
+                        return Some(move_);
+                    }
+                    self.bad_captures
+                        .sort_by_key(|weighted_move| weighted_move.weight);
+                    self.stage = MovePickerStage::BadCaptures;
+                }
+                MovePickerStage::BadCaptures => {
+                    if let Some(WeightedMove { move_, .. }) = self.bad_captures.pop() {
+                        return Some(move_);
+                    }
+                    self.stage = MovePickerStage::Done;
+                }
+                MovePickerStage::Done => return None,
+            }
+        }
+    }
+}
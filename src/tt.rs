@@ -56,22 +56,76 @@ impl Default for TranspositionTableData {
 pub struct TranspositionTableEntry {
     optional_data: Option<TranspositionTableData>,
     best_move: Option<Move>,
+    generation: u8,
 }
 
 impl TranspositionTableEntry {
-    fn new(optional_data: Option<TranspositionTableData>, best_move: Option<Move>) -> Self {
+    fn new(
+        optional_data: Option<TranspositionTableData>,
+        best_move: Option<Move>,
+        generation: u8,
+    ) -> Self {
         Self {
             optional_data,
             best_move,
+            generation,
         }
     }
+
+    #[inline]
+    pub fn get_generation(&self) -> u8 {
+        self.generation
+    }
+
+    /// Entries with deeper results and from more recent searches are kept over the others.
+    #[inline]
+    fn get_replacement_priority(self, current_generation: u8) -> i32 {
+        let depth = self.optional_data.map_or(-1, |data| data.depth) as i32;
+        let age = current_generation.wrapping_sub(self.generation) as i32;
+        depth - TT_AGE_PENALTY * age
+    }
+}
+
+/// Packs the best move in the bits 0-15, the generation in the bits 16-23, the data flag in the bits 24-26, the depth in the bits 32-39 and the score in the bits 40-55.
+impl Compress for TranspositionTableEntry {
+    type CompressedItem = u64;
+
+    fn compress(self) -> Self::CompressedItem {
+        let mut compressed_entry = self.best_move.compress() as Self::CompressedItem;
+        compressed_entry |= (self.generation as Self::CompressedItem) << 16;
+        if let Some(data) = self.optional_data {
+            compressed_entry |= (1 | (data.flag as Self::CompressedItem) << 1) << 24;
+            compressed_entry |= (data.depth as u8 as Self::CompressedItem) << 32;
+            compressed_entry |= (data.score as u16 as Self::CompressedItem) << 40;
+        }
+        compressed_entry
+    }
+}
+
+impl Decompress<TranspositionTableEntry> for u64 {
+    fn decompress(self) -> TranspositionTableEntry {
+        let optional_data = (self >> 24 & 1 == 1).then_some(TranspositionTableData {
+            depth: (self >> 32) as u8 as Depth,
+            score: (self >> 40) as u16 as Score,
+            flag: match self >> 25 & 3 {
+                0 => EntryFlagHash::Exact,
+                1 => EntryFlagHash::Alpha,
+                _ => EntryFlagHash::Beta,
+            },
+        });
+        TranspositionTableEntry::new(
+            optional_data,
+            (self as u16).decompress(),
+            (self >> 16) as u8,
+        )
+    }
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[repr(transparent)]
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct TranspositionTable {
     table: CacheTable<TranspositionTableEntry>,
+    generation: AtomicU8,
 }
 
 impl TranspositionTable {
@@ -82,15 +136,44 @@ impl TranspositionTable {
 
     #[inline]
     fn generate_new_table(cache_table_size: CacheTableSize) -> CacheTable<TranspositionTableEntry> {
-        CacheTable::new(cache_table_size)
+        CacheTable::new_with_bucket_size(cache_table_size, TT_BUCKET_SIZE)
     }
 
     pub fn new(cache_table_size: CacheTableSize) -> Self {
         Self {
             table: Self::generate_new_table(cache_table_size),
+            generation: AtomicU8::new(0),
         }
     }
 
+    #[inline]
+    pub fn get_generation(&self) -> u8 {
+        self.generation.load(MEMORY_ORDERING)
+    }
+
+    /// Marks the start of a new search, entries from the older searches become preferred for replacement.
+    #[inline]
+    pub fn increment_generation(&self) {
+        self.generation.fetch_add(1, MEMORY_ORDERING);
+    }
+
+    /// Percentage of the sampled entries written during the current search.
+    pub fn get_hash_full(&self) -> f64 {
+        let generation = self.get_generation();
+        let num_samples = self.table.len().min(TT_HASH_FULL_SAMPLE_SIZE);
+        if num_samples == 0 {
+            return 0.0;
+        }
+        let num_current_entries = self
+            .table
+            .iter()
+            .take(num_samples)
+            .flatten()
+            .filter(|entry| entry.get_entry().generation == generation)
+            .count();
+        (num_current_entries as f64 / num_samples as f64) * 100.0
+    }
+
     pub fn read(
         &self,
         key: u64,
@@ -125,6 +208,15 @@ impl TranspositionTable {
         self.table.get(key)?.best_move
     }
 
+    /// Best move stored for the position, ignored if it is illegal there as the entry then belongs to a colliding key.
+    /// The legality check generates moves, so it is only meant for moves that are played or reported, as the moves
+    /// used for ordering are compared against the generated ones anyway.
+    #[inline]
+    pub fn read_legal_best_move(&self, position: &BoardPosition) -> Option<Move> {
+        self.read_best_move(position.get_hash())
+            .filter(|best_move| position.is_legal(best_move))
+    }
+
     pub fn write(
         &self,
         key: u64,
@@ -148,37 +240,190 @@ impl TranspositionTable {
                 -mate_score
             };
         }
+        let generation = self.get_generation();
         let old_optional_entry = self.table.get(key);
         let optional_data = if save_score {
-            let old_optional_data = old_optional_entry.and_then(|tt_entry| tt_entry.optional_data);
-            if old_optional_data.map_or(-1, |data| data.depth) < depth {
-                Some(TranspositionTableData { depth, score, flag })
-            } else {
-                old_optional_data
+            // The result of an older search is kept only if it is deep enough to make up for its age.
+            match old_optional_entry.filter(|tt_entry| {
+                tt_entry.optional_data.is_some()
+                    && tt_entry.get_replacement_priority(generation) >= depth as i32
+            }) {
+                Some(tt_entry) => tt_entry.optional_data,
+                None => Some(TranspositionTableData { depth, score, flag }),
             }
         } else {
             None
         };
-        self.table.add(
+        self.table.add_with_priority(
             key,
             TranspositionTableEntry::new(
                 optional_data,
                 best_move.or(old_optional_entry.and_then(|tt_entry| tt_entry.best_move)),
+                generation,
             ),
+            |tt_entry| tt_entry.get_replacement_priority(generation),
         );
     }
 
+    /// Stores the move to be searched first in the position without any search result, the result already stored
+    /// for the position is kept along with its age.
+    pub fn write_best_move(&self, key: u64, best_move: Move) {
+        let generation = self.get_generation();
+        let (optional_data, entry_generation) = match self.table.get(key) {
+            Some(tt_entry) if tt_entry.optional_data.is_some() => {
+                (tt_entry.optional_data, tt_entry.generation)
+            }
+            _ => (None, generation),
+        };
+        self.table.add_with_priority(
+            key,
+            TranspositionTableEntry::new(optional_data, Some(best_move), entry_generation),
+            |tt_entry| tt_entry.get_replacement_priority(generation),
+        );
+    }
+
+    /// Fingerprint of the zobrist keys, tables saved with different keys cannot be reused.
+    fn get_hashing_fingerprint() -> u64 {
+        BoardPosition::from_fen("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1")
+            .map_or(0, |position| position.get_hash())
+            ^ BoardPosition::default().get_hash()
+    }
+
     #[inline]
-    pub fn clear_best_moves(&self) {
-        self.table
-            .get_table()
-            .write()
-            .unwrap()
-            .iter_mut()
-            .flatten()
-            .for_each(|entry| {
-                entry.get_entry_mut().best_move = None;
+    fn update_checksum(checksum: u64, word: u64) -> u64 {
+        (checksum ^ word)
+            .wrapping_mul(0x100000001B3)
+            .rotate_left(29)
+    }
+
+    fn encode_size(size: CacheTableSize) -> (u8, u64) {
+        match size {
+            CacheTableSize::Max(size) => (0, size as u64),
+            CacheTableSize::Min(size) => (1, size as u64),
+            CacheTableSize::Round(size) => (2, size as u64),
+            CacheTableSize::Exact(size) => (3, size as u64),
+        }
+    }
+
+    fn decode_size(kind: u8, size: u64) -> Option<CacheTableSize> {
+        let size = size as usize;
+        Some(match kind {
+            0 => CacheTableSize::Max(size),
+            1 => CacheTableSize::Min(size),
+            2 => CacheTableSize::Round(size),
+            3 => CacheTableSize::Exact(size),
+            _ => return None,
+        })
+    }
+
+    fn get_header_words(&self) -> [u64; 7] {
+        let (size_kind, size) = Self::encode_size(self.get_size());
+        [
+            u32::from_le_bytes(TT_FILE_MAGIC) as u64 | (TT_FILE_VERSION as u64) << 32,
+            TT_ENTRY_LAYOUT_VERSION as u64,
+            Self::get_hashing_fingerprint(),
+            self.get_bucket_size() as u64,
+            self.len() as u64,
+            size_kind as u64 | (self.get_generation() as u64) << 8,
+            size,
+        ]
+    }
+
+    /// Writes the table to a versioned and checksummed binary file.
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut writer = std::io::BufWriter::new(fs::File::create(path)?);
+        let mut checksum = 0;
+        let header_words = self.get_header_words();
+        let slot_words = self.iter_raw_slots().flat_map(|(key, data)| [key, data]);
+        for word in header_words.into_iter().chain(slot_words) {
+            checksum = Self::update_checksum(checksum, word);
+            writer.write_all(&word.to_le_bytes())?;
+        }
+        writer.write_all(&checksum.to_le_bytes())?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn read_word(reader: &mut impl Read) -> std::io::Result<u64> {
+        let mut bytes = [0; 8];
+        reader.read_exact(&mut bytes)?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    /// Replaces the table with the one saved by [`Self::save_to_file`], the table is left untouched if the file is invalid.
+    ///
+    /// The file is read once into newly allocated slots, which are swapped in only after the checksum is verified.
+    pub fn load_from_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let error = |err_msg: &str| TimecatError::InvalidHashFile {
+            path: path.display().to_string(),
+            err_msg: err_msg.to_string(),
+        };
+        let mut reader = BufReader::new(fs::File::open(path)?);
+        let mut read_word = || Self::read_word(&mut reader).map_err(|_| error("file is truncated"));
+        let mut header_words = [0; 7];
+        for word in header_words.iter_mut() {
+            *word = read_word()?;
+        }
+        if header_words[0] as u32 != u32::from_le_bytes(TT_FILE_MAGIC) {
+            return Err(error("not a hash file"));
+        }
+        if (header_words[0] >> 32) as u32 != TT_FILE_VERSION {
+            return Err(error("unsupported file version"));
+        }
+        if header_words[1] != TT_ENTRY_LAYOUT_VERSION as u64 {
+            return Err(TimecatError::IncompatibleHashFileLayout {
+                path: path.display().to_string(),
+                layout_version: header_words[1],
             });
+        }
+        if header_words[2] != Self::get_hashing_fingerprint() {
+            return Err(error("incompatible hashing"));
+        }
+        let size = Self::decode_size(header_words[5] as u8, header_words[6])
+            .ok_or_else(|| error("invalid table size"))?;
+        if header_words[3] as usize != self.get_bucket_size() {
+            return Err(error("incompatible bucket size"));
+        }
+        let num_buckets =
+            (size.to_num_entries::<TranspositionTableEntry>() / self.get_bucket_size()).max(1);
+        if num_buckets * self.get_bucket_size() != header_words[4] as usize {
+            return Err(error("incompatible entry size"));
+        }
+        let mut allocation = self.allocate(size)?;
+        let mut checksum = header_words
+            .iter()
+            .fold(0, |checksum, &word| Self::update_checksum(checksum, word));
+        for index in 0..allocation.len() {
+            let key = read_word()?;
+            let data = read_word()?;
+            checksum = Self::update_checksum(Self::update_checksum(checksum, key), data);
+            allocation.set_raw_slot(index, key, data);
+        }
+        if read_word()? != checksum {
+            return Err(error("checksum mismatch"));
+        }
+        self.table.replace_with_allocation(allocation);
+        self.generation
+            .store((header_words[5] >> 8) as u8, MEMORY_ORDERING);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn clear_best_moves(&self) {
+        self.table.update_entries(|mut entry| {
+            entry.best_move = None;
+            entry
+        });
+    }
+}
+
+impl Clone for TranspositionTable {
+    fn clone(&self) -> Self {
+        Self {
+            table: self.table.clone(),
+            generation: AtomicU8::new(self.get_generation()),
+        }
     }
 }
 
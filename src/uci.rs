@@ -124,6 +124,25 @@ impl<T: ChessEngine> UCIOption<T> {
         UCIOption::new(name, UCIOptionType::Check { default, function })
     }
 
+    fn new_combo(
+        name: &str,
+        default: &str,
+        options: impl IntoIterator<Item = impl ToString>,
+        function: fn(&mut T, &str) -> Result<()>,
+    ) -> Self {
+        UCIOption::new(
+            name,
+            UCIOptionType::Combo {
+                default: default.to_string(),
+                options: options
+                    .into_iter()
+                    .map(|option| option.to_string())
+                    .collect(),
+                function,
+            },
+        )
+    }
+
     fn new_button(name: &str, function: fn(&mut T) -> Result<()>) -> Self {
         UCIOption::new(name, UCIOptionType::Button { function })
     }
@@ -325,7 +344,7 @@ fn get_uci_state_manager<T: ChessEngine>() -> Vec<UCIOption<T>> {
             {
                 |engine, value| {
                     let size = CacheTableSize::Exact(value as usize);
-                    engine.set_transposition_table_size(size);
+                    engine.set_transposition_table_size(size)?;
                     print_uci_info(
                         "Transposition table is set to size to",
                         size.to_memory_size_in_mb::<TranspositionTableEntry>(),
@@ -334,6 +353,55 @@ fn get_uci_state_manager<T: ChessEngine>() -> Vec<UCIOption<T>> {
                 }
             },
         ),
+        UCIOption::new_spin(
+            "Eval Cache",
+            SpinValue::new(
+                CACHE_TABLE_SIZE,
+                CacheTableSize::Exact(1),
+                CacheTableSize::Exact(1 << 16),
+            ),
+            |engine, value| {
+                let size = CacheTableSize::Exact(value as usize);
+                engine.set_evaluation_cache_size(size)?;
+                print_uci_info(
+                    "Evaluation cache is set to size to",
+                    size.to_memory_size_in_mb::<Score>(),
+                );
+                Ok(())
+            },
+        ),
+        UCIOption::new_spin(
+            "Pawn Hash",
+            SpinValue::new(
+                PAWN_HASH_TABLE_SIZE,
+                CacheTableSize::Exact(1),
+                CacheTableSize::Exact(1 << 16),
+            ),
+            |engine, value| {
+                let size = CacheTableSize::Exact(value as usize);
+                engine.set_pawn_hash_size(size)?;
+                print_uci_info(
+                    "Pawn hash table is set to size to",
+                    size.to_memory_size_in_mb::<PawnHashEntry>(),
+                );
+                Ok(())
+            },
+        ),
+        UCIOption::new_spin(
+            "Memory",
+            SpinValue::new(DEFAULT_MEMORY_BUDGET, 1, 1 << 25),
+            |engine, value| {
+                MemoryManager::new(value as usize).apply(engine)?;
+                print_uci_info(
+                    "Memory usage is set to",
+                    format!(
+                        "{:.2} MB",
+                        engine.get_memory_usage() as f64 / 2_usize.pow(20) as f64
+                    ),
+                );
+                Ok(())
+            },
+        ),
         UCIOption::new_button("Clear Hash", |engine| {
             engine.clear_hash();
             print_uci_info::<&str>("All hash tables are cleared!", None);
@@ -364,6 +432,69 @@ fn get_uci_state_manager<T: ChessEngine>() -> Vec<UCIOption<T>> {
                 Ok(())
             },
         ),
+        UCIOption::new_string(
+            "Experience File",
+            TIMECAT_DEFAULTS.experience_path.to_string(),
+            |engine, experience_path| {
+                engine.set_experience_file(experience_path)?;
+                print_uci_info(
+                    "Experience File is set to",
+                    format!("{:?}", experience_path),
+                );
+                Ok(())
+            },
+        ),
+        UCIOption::new_check(
+            "Experience Enabled",
+            TIMECAT_DEFAULTS.use_experience,
+            |engine, b| {
+                engine.set_using_experience(b)?;
+                print_uci_info("Experience usage is set to", b);
+                Ok(())
+            },
+        ),
+        UCIOption::new_string("Checkpoint File", "None".to_string(), |engine, path| {
+            let path = Some(path).filter(|path| !path.eq_ignore_ascii_case("none"));
+            engine.set_checkpoint_file(path)?;
+            print_uci_info("Checkpoint File is set to", format!("{:?}", path));
+            Ok(())
+        }),
+        UCIOption::new_spin(
+            "Checkpoint Interval",
+            SpinValue::new(
+                TIMECAT_DEFAULTS.checkpoint_interval.as_secs() as usize,
+                0,
+                60 * 60 * 24,
+            ),
+            |engine, value| {
+                let interval = Duration::from_secs(value as u64);
+                engine.set_checkpoint_interval(interval);
+                print_uci_info("Checkpoint Interval is set to", interval.stringify());
+                Ok(())
+            },
+        ),
+        UCIOption::new_check("Verify Hash", TIMECAT_DEFAULTS.verify_hash, |_, b| {
+            GLOBAL_TIMECAT_STATE.set_hash_verification(b);
+            Ok(())
+        }),
+        UCIOption::new_check("UCI_Chess960", TIMECAT_DEFAULTS.chess960_mode, |_, b| {
+            GLOBAL_TIMECAT_STATE.set_chess960_mode(b);
+            Ok(())
+        }),
+        UCIOption::new_combo(
+            "UCI_Variant",
+            TIMECAT_DEFAULTS.variant.get_uci_name(),
+            ALL_VARIANTS.map(|variant| variant.get_uci_name()),
+            |_, value| {
+                GLOBAL_TIMECAT_STATE.set_variant(value.parse()?);
+                Ok(())
+            },
+        ),
+        UCIOption::new_check("Search Statistics", false, |engine, b| {
+            engine.set_collecting_search_statistics(b);
+            print_uci_info("Search statistics collection is set to", b);
+            Ok(())
+        }),
         // UCIOption::new_check(
         //     "OwnBook",
         //     TIMECAT_DEFAULTS.use_own_book,
@@ -372,14 +503,6 @@ fn get_uci_state_manager<T: ChessEngine>() -> Vec<UCIOption<T>> {
         //         print_uci_info("Own Book Usage is set to", b);
         //     },
         // ),
-        // UCIOption::new_check(
-        //     "UCI_Chess960",
-        //     TIMECAT_DEFAULTS.chess960_mode,
-        //     |engine, b| {
-        //         chess960_mode.store(b, MEMORY_ORDERING);
-        //         print_uci_info("Chess 960 mode is set to", b);
-        //     },
-        // ),
     ];
     options
 }
@@ -1,4 +1,7 @@
 use super::*;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicPtr, AtomicU64, Ordering as AtomicOrdering};
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -30,7 +33,7 @@ impl<T> CacheTableEntry<T> {
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum CacheTableSize {
     Max(usize),
     Min(usize),
@@ -77,13 +80,13 @@ impl CacheTableSize {
 
     #[inline]
     pub const fn get_entry_size<T>() -> usize {
-        size_of::<Option<CacheTableEntry<T>>>()
+        size_of::<CacheTableSlot>()
     }
 
     pub fn to_num_entries_and_entry_size<T>(self) -> (usize, usize) {
         let mut size = self.unwrap();
         let entry_size = Self::get_entry_size::<T>();
-        size *= 2_usize.pow(20);
+        size = size.saturating_mul(2_usize.pow(20));
         size /= entry_size;
         if self.is_exact() {
             return (size, entry_size);
@@ -140,57 +143,199 @@ macro_rules! update_variables {
     };
 }
 
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// One entry packed in two atomic words, the key word stores the hash xored with the data word so that torn reads fail the verification.
 #[derive(Debug, Default)]
-pub struct CacheTable<T> {
-    table: RwLock<Box<[Option<CacheTableEntry<T>>]>>,
-    size: RwLock<CacheTableSize>,
-    mask: AtomicUsize,
-    is_safe_to_do_bitwise_and: AtomicBool,
-    num_cells_filled: AtomicUsize,
-    #[cfg(feature = "extras")]
-    num_overwrites: AtomicUsize,
-    #[cfg(feature = "extras")]
-    num_collisions: AtomicUsize,
-    #[cfg(feature = "extras")]
-    zero_hit: AtomicUsize,
+struct CacheTableSlot {
+    key: AtomicU64,
+    data: AtomicU64,
 }
 
-impl<T: Copy + PartialEq> CacheTable<T> {
+impl CacheTableSlot {
     #[inline]
-    fn generate_table(size: CacheTableSize) -> Box<[Option<CacheTableEntry<T>>]> {
-        vec![None; size.to_num_entries::<T>()].into_boxed_slice()
+    fn load<T>(&self) -> Option<CacheTableEntry<T>>
+    where
+        u64: Decompress<T>,
+    {
+        let data = self.data.load(MEMORY_ORDERING);
+        let hash = NonZeroU64::new(self.key.load(MEMORY_ORDERING) ^ data)?;
+        Some(CacheTableEntry::new(hash, data.decompress()))
     }
 
+    #[inline]
+    fn store<T: Compress<CompressedItem = u64>>(&self, hash: NonZeroU64, entry: T) {
+        let data = entry.compress();
+        self.data.store(data, MEMORY_ORDERING);
+        self.key.store(hash.get() ^ data, MEMORY_ORDERING);
+    }
+
+    #[inline]
+    fn clear(&self) {
+        self.data.store(0, MEMORY_ORDERING);
+        self.key.store(0, MEMORY_ORDERING);
+    }
+}
+
+#[derive(Debug)]
+struct CacheTableSlots {
+    slots: Box<[CacheTableSlot]>,
+    mask: usize,
+    is_safe_to_do_bitwise_and: bool,
+}
+
+impl CacheTableSlots {
     #[inline]
     const fn is_safe_to_do_bitwise_and(size: usize) -> bool {
         size.count_ones() == 1 && size > 1
     }
 
+    /// Allocates the slots, returning [`None`] instead of aborting if the memory is not available.
+    fn try_new(num_entries: usize, bucket_size: usize) -> Option<Self> {
+        let num_buckets = (num_entries / bucket_size).max(1);
+        let is_safe_to_do_bitwise_and = Self::is_safe_to_do_bitwise_and(num_buckets);
+        let num_slots = num_buckets.checked_mul(bucket_size)?;
+        let mut slots = Vec::new();
+        slots.try_reserve_exact(num_slots).ok()?;
+        slots.resize_with(num_slots, CacheTableSlot::default);
+        Some(Self {
+            slots: slots.into_boxed_slice(),
+            mask: if is_safe_to_do_bitwise_and {
+                num_buckets - 1
+            } else {
+                num_buckets
+            },
+            is_safe_to_do_bitwise_and,
+        })
+    }
+
     #[inline]
-    const fn into_inner(table: &[Option<CacheTableEntry<T>>]) -> usize {
-        if Self::is_safe_to_do_bitwise_and(table.len()) {
-            table.len() - 1
+    fn get_index(&self, hash: u64) -> usize {
+        if self.is_safe_to_do_bitwise_and {
+            hash as usize & self.mask
         } else {
-            table.len()
+            hash as usize % self.mask
         }
     }
+}
+
+const NUM_READER_COUNTERS: usize = 64;
+
+/// Counter on its own cache line so that threads pinning the table do not contend with each other.
+#[repr(align(64))]
+#[derive(Debug, Default)]
+struct ReaderCounter(AtomicUsize);
+
+thread_local! {
+    static READER_COUNTER_INDEX: usize = {
+        static NEXT_INDEX: AtomicUsize = AtomicUsize::new(0);
+        NEXT_INDEX.fetch_add(1, MEMORY_ORDERING) % NUM_READER_COUNTERS
+    };
+}
+
+/// Keeps the slots alive while they are accessed, `set_size` waits for all the guards to be dropped before freeing the replaced slots.
+struct CacheTableSlotsGuard<'a> {
+    inner: &'a CacheTableSlots,
+    counter: &'a ReaderCounter,
+}
+
+impl Deref for CacheTableSlotsGuard<'_> {
+    type Target = CacheTableSlots;
 
     #[inline]
-    fn reset_mask(&self, table: &[Option<CacheTableEntry<T>>]) {
-        self.mask.store(Self::into_inner(table), MEMORY_ORDERING);
-        self.is_safe_to_do_bitwise_and.store(
-            Self::is_safe_to_do_bitwise_and(table.len()),
-            MEMORY_ORDERING,
-        );
+    fn deref(&self) -> &Self::Target {
+        self.inner
     }
+}
 
+impl Drop for CacheTableSlotsGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.counter.0.fetch_sub(1, AtomicOrdering::Release);
+    }
+}
+
+/// Slots allocated by [`CacheTable::allocate`] which are not used by the table yet, so that they can be filled or
+/// allocated together with other tables before being swapped in.
+#[derive(Debug)]
+pub struct CacheTableAllocation<T> {
+    slots: Box<CacheTableSlots>,
+    size: CacheTableSize,
+    num_cells_filled: usize,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> CacheTableAllocation<T> {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.slots.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.num_cells_filled == 0
+    }
+
+    #[inline]
+    pub fn get_size(&self) -> CacheTableSize {
+        self.size
+    }
+
+    /// Overwrites the empty slot at `index` with raw words obtained from [`CacheTable::iter_raw_slots`] of a table
+    /// with the same layout.
+    pub fn set_raw_slot(&mut self, index: usize, key: u64, data: u64) {
+        let slot = get_item_unchecked!(self.slots.slots, index);
+        slot.data.store(data, MEMORY_ORDERING);
+        slot.key.store(key, MEMORY_ORDERING);
+        if key ^ data != 0 {
+            self.num_cells_filled += 1;
+        }
+    }
+}
+
+/// Lock-free hash table, every hash maps to a bucket of `bucket_size` consecutive entries.
+#[derive(Debug)]
+pub struct CacheTable<T> {
+    slots: AtomicPtr<CacheTableSlots>,
+    reader_counters: Box<[ReaderCounter]>,
+    size: RwLock<CacheTableSize>,
+    bucket_size: usize,
+    num_cells_filled: AtomicUsize,
+    #[cfg(feature = "extras")]
+    num_overwrites: AtomicUsize,
+    #[cfg(feature = "extras")]
+    num_collisions: AtomicUsize,
+    #[cfg(feature = "extras")]
+    zero_hit: AtomicUsize,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Copy + PartialEq + Compress<CompressedItem = u64>> CacheTable<T>
+where
+    u64: Decompress<T>,
+{
+    #[inline]
+    fn generate_slots(size: CacheTableSize, bucket_size: usize) -> Result<Box<CacheTableSlots>> {
+        CacheTableSlots::try_new(size.to_num_entries::<T>(), bucket_size)
+            .map(Box::new)
+            .ok_or(TimecatError::MemoryAllocationFailed { size })
+    }
+
+    #[inline]
     pub fn new(size: CacheTableSize) -> CacheTable<T> {
-        let cache_table = CacheTable {
-            table: RwLock::new(Self::generate_table(size)),
+        Self::new_with_bucket_size(size, 1)
+    }
+
+    /// Creates a table where every hash maps to a bucket of `bucket_size` consecutive entries.
+    pub fn new_with_bucket_size(size: CacheTableSize, bucket_size: usize) -> CacheTable<T> {
+        let bucket_size = bucket_size.max(1);
+        CacheTable {
+            slots: AtomicPtr::new(Box::into_raw(
+                Self::generate_slots(size, bucket_size).unwrap_or_else(|err| panic!("{err}")),
+            )),
+            reader_counters: (0..NUM_READER_COUNTERS)
+                .map(|_| ReaderCounter::default())
+                .collect(),
             size: RwLock::new(size),
-            mask: Default::default(),
-            is_safe_to_do_bitwise_and: Default::default(),
+            bucket_size,
             num_cells_filled: AtomicUsize::new(0),
             #[cfg(feature = "extras")]
             num_overwrites: AtomicUsize::new(0),
@@ -198,44 +343,82 @@ impl<T: Copy + PartialEq> CacheTable<T> {
             num_collisions: AtomicUsize::new(0),
             #[cfg(feature = "extras")]
             zero_hit: AtomicUsize::new(0),
-        };
-        cache_table.reset_mask(&cache_table.table.read().unwrap());
-        cache_table
+            _phantom: PhantomData,
+        }
     }
 
     #[inline]
-    fn get_index(&self, hash: u64) -> usize {
-        if self.is_safe_to_do_bitwise_and.load(MEMORY_ORDERING) {
-            hash as usize & self.mask.load(MEMORY_ORDERING)
-        } else {
-            hash as usize % self.mask.load(MEMORY_ORDERING)
-        }
+    fn get_slots(&self) -> CacheTableSlotsGuard<'_> {
+        let counter = get_item_unchecked!(
+            self.reader_counters,
+            READER_COUNTER_INDEX.with(|&index| index)
+        );
+        // The counter has to be incremented before loading the pointer for set_size to see it.
+        counter.0.fetch_add(1, AtomicOrdering::SeqCst);
+        // SAFETY: The slots are not freed by set_size while the counter is non zero.
+        let inner = unsafe { &*self.slots.load(AtomicOrdering::SeqCst) };
+        CacheTableSlotsGuard { inner, counter }
+    }
+
+    #[inline]
+    fn get_bucket<'a>(&self, slots: &'a CacheTableSlots, hash: NonZeroU64) -> &'a [CacheTableSlot] {
+        let start = slots.get_index(hash.get()) * self.bucket_size;
+        get_item_unchecked!(slots.slots, start..start + self.bucket_size)
+    }
+
+    /// Slot with the same hash, otherwise an empty slot, otherwise the slot with the lowest priority in the bucket.
+    fn get_replacement_slot<'a>(
+        &self,
+        slots: &'a CacheTableSlots,
+        hash: NonZeroU64,
+        priority: impl Fn(T) -> i32,
+    ) -> (&'a CacheTableSlot, Option<CacheTableEntry<T>>) {
+        let bucket = self.get_bucket(slots, hash);
+        let entries = bucket.iter().map(|slot| slot.load::<T>()).collect_vec();
+        let offset = entries
+            .iter()
+            .position(|e| e.is_some_and(|e| e.hash == hash))
+            .or_else(|| entries.iter().position(|e| e.is_none()))
+            .unwrap_or_else(|| {
+                entries
+                    .iter()
+                    .flatten()
+                    .position_min_by_key(|e| priority(e.entry))
+                    .unwrap_or_default()
+            });
+        (
+            get_item_unchecked!(bucket, offset),
+            *get_item_unchecked!(entries, offset),
+        )
     }
 
     #[inline]
     pub fn get(&self, hash: u64) -> Option<T> {
         let hash = NonZeroU64::new(hash).unwrap_or(DEFAULT_HASH);
-        let entry = (*get_item_unchecked!(self.table.read().unwrap(), self.get_index(hash.get())))?;
-        if entry.hash == hash {
-            Some(entry.entry)
-        } else {
-            None
-        }
+        let slots = self.get_slots();
+        self.get_bucket(&slots, hash)
+            .iter()
+            .filter_map(|slot| slot.load::<T>())
+            .find(|entry| entry.hash == hash)
+            .map(|entry| entry.entry)
     }
 
     #[inline]
     pub fn add(&self, hash: u64, entry: T) {
+        self.add_with_priority(hash, entry, |_| 0);
+    }
+
+    /// Adds the entry, evicting the entry with the lowest priority if the bucket is full.
+    #[inline]
+    pub fn add_with_priority(&self, hash: u64, entry: T, priority: impl Fn(T) -> i32) {
         let hash = NonZeroU64::new(hash).unwrap_or(DEFAULT_HASH);
-        let mut table = self.table.write().unwrap();
-        let e = get_item_unchecked_mut!(table, self.get_index(hash.get()));
+        let slots = self.get_slots();
+        let (slot, e_copy) = self.get_replacement_slot(&slots, hash, priority);
         #[cfg(not(feature = "extras"))]
-        if e.is_none() {
+        if e_copy.is_none() {
             self.num_cells_filled.fetch_add(1, MEMORY_ORDERING);
         }
-        #[cfg(feature = "extras")]
-        let e_copy = *e;
-        *e = Some(CacheTableEntry { hash, entry });
-        drop(table);
+        slot.store(hash, entry);
         #[cfg(feature = "extras")]
         update_variables!(self, e_copy, hash, entry);
     }
@@ -243,37 +426,68 @@ impl<T: Copy + PartialEq> CacheTable<T> {
     #[inline]
     pub fn replace_if<F: Fn(T) -> bool>(&self, hash: u64, entry: T, replace: F) {
         let hash = NonZeroU64::new(hash).unwrap_or(DEFAULT_HASH);
-        let mut table = self.table.write().unwrap();
-        let e = get_item_unchecked_mut!(table, self.get_index(hash.get()));
-        let to_replace = if let Some(entry) = e {
-            replace(entry.entry)
+        let slots = self.get_slots();
+        let (slot, e_copy) = self.get_replacement_slot(&slots, hash, |_| 0);
+        let to_replace = if let Some(e) = e_copy {
+            replace(e.entry)
         } else {
             true
         };
         if to_replace {
             #[cfg(not(feature = "extras"))]
-            if e.is_none() {
+            if e_copy.is_none() {
                 self.num_cells_filled.fetch_add(1, MEMORY_ORDERING);
             }
-            #[cfg(feature = "extras")]
-            let e_copy = *e;
-            *e = Some(CacheTableEntry { hash, entry });
-            drop(table);
+            slot.store(hash, entry);
             #[cfg(feature = "extras")]
             update_variables!(self, e_copy, hash, entry);
         }
     }
 
+    /// Reads the slots one by one, the slots are pinned only while each one is read so that the table can be resized
+    /// in between, in which case the remaining slots are read from the new slots.
     #[inline]
-    pub fn clear(&self) {
-        self.table.write().unwrap().fill(None);
-        self.num_cells_filled.store(0, MEMORY_ORDERING);
-        self.reset_variables()
+    fn iter_slots<'a, R>(
+        &'a self,
+        func: impl Fn(&CacheTableSlot) -> R + 'a,
+    ) -> impl Iterator<Item = R> + 'a {
+        (0..).map_while(move |index| self.get_slots().slots.get(index).map(&func))
+    }
+
+    /// Entries of all the slots in the table, `None` for the empty ones.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = Option<CacheTableEntry<T>>> + '_ {
+        self.iter_slots(|slot| slot.load())
     }
 
+    /// Raw key and data words of every slot, in the order of the slots.
     #[inline]
-    pub const fn get_table(&self) -> &RwLock<Box<[Option<CacheTableEntry<T>>]>> {
-        &self.table
+    pub fn iter_raw_slots(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.iter_slots(|slot| {
+            (
+                slot.key.load(MEMORY_ORDERING),
+                slot.data.load(MEMORY_ORDERING),
+            )
+        })
+    }
+
+    /// Replaces every stored entry in place with the result of `func`.
+    pub fn update_entries(&self, func: impl Fn(T) -> T) {
+        for slot in self.get_slots().slots.iter() {
+            if let Some(entry) = slot.load::<T>() {
+                slot.store(entry.hash, func(entry.entry));
+            }
+        }
+    }
+
+    #[inline]
+    pub fn clear(&self) {
+        self.get_slots()
+            .slots
+            .iter()
+            .for_each(CacheTableSlot::clear);
+        self.num_cells_filled.store(0, MEMORY_ORDERING);
+        self.reset_variables()
     }
 
     #[inline]
@@ -339,7 +553,7 @@ impl<T: Copy + PartialEq> CacheTable<T> {
 
     #[inline]
     pub fn len(&self) -> usize {
-        self.table.read().unwrap().len()
+        self.get_slots().slots.len()
     }
 
     #[inline]
@@ -352,34 +566,155 @@ impl<T: Copy + PartialEq> CacheTable<T> {
         *self.size.read().unwrap()
     }
 
-    pub fn set_size(&self, size: CacheTableSize) {
-        *self.size.write().unwrap() = size;
-        let current_table_copy = self.table.read().unwrap().clone();
-        *self.table.write().unwrap() = Self::generate_table(size);
-        self.reset_mask(&current_table_copy);
+    #[inline]
+    pub fn get_bucket_size(&self) -> usize {
+        self.bucket_size
+    }
+
+    /// Memory used by the slots of the table in bytes.
+    #[inline]
+    pub fn get_memory_usage(&self) -> usize {
+        self.len() * size_of::<CacheTableSlot>()
+    }
+
+    /// Allocates empty slots of the given size for this table without touching the slots in use.
+    pub fn allocate(&self, size: CacheTableSize) -> Result<CacheTableAllocation<T>> {
+        Ok(CacheTableAllocation {
+            slots: Self::generate_slots(size, self.bucket_size)?,
+            size,
+            num_cells_filled: 0,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Swaps in the allocated slots, returning the entries of the replaced ones.
+    ///
+    /// The replaced slots are freed once the threads accessing them are done.
+    fn swap_slots(&self, allocation: CacheTableAllocation<T>) -> Vec<CacheTableEntry<T>> {
+        *self.size.write().unwrap() = allocation.size;
+        let current_entries = self.iter().flatten().collect_vec();
+        let old_slots = self
+            .slots
+            .swap(Box::into_raw(allocation.slots), AtomicOrdering::SeqCst);
+        // Threads pinning a counter from now on can only see the new slots.
+        for counter in self.reader_counters.iter() {
+            while counter.0.load(AtomicOrdering::SeqCst) != 0 {
+                std::hint::spin_loop();
+            }
+        }
+        // SAFETY: The pointer was created by Box::into_raw and no thread is accessing it anymore.
+        drop(unsafe { Box::from_raw(old_slots) });
+        self.num_cells_filled
+            .store(allocation.num_cells_filled, MEMORY_ORDERING);
         self.reset_variables();
-        for entry in current_table_copy.iter().flatten() {
+        current_entries
+    }
+
+    /// Replaces the slots with the allocated ones, dropping the current entries.
+    #[inline]
+    pub fn replace_with_allocation(&self, allocation: CacheTableAllocation<T>) {
+        self.swap_slots(allocation);
+    }
+
+    /// Swaps in the allocated slots keeping the current entries which fit.
+    pub fn resize_with_allocation(&self, allocation: CacheTableAllocation<T>) {
+        for entry in self.swap_slots(allocation) {
             self.add(entry.hash.get(), entry.entry);
         }
     }
+
+    /// Resizes the table keeping the entries which fit, the table is left unchanged if the memory cannot be allocated.
+    pub fn set_size(&self, size: CacheTableSize) -> Result<()> {
+        self.resize_with_allocation(self.allocate(size)?);
+        Ok(())
+    }
 }
 
-impl<T: Copy + PartialEq> Clone for CacheTable<T> {
+impl<T> Drop for CacheTable<T> {
+    fn drop(&mut self) {
+        // SAFETY: The pointer was created by Box::into_raw and the table is not accessible anymore.
+        drop(unsafe { Box::from_raw(*self.slots.get_mut()) });
+    }
+}
+
+impl<T: Copy + PartialEq + Compress<CompressedItem = u64>> Clone for CacheTable<T>
+where
+    u64: Decompress<T>,
+{
     fn clone(&self) -> Self {
-        CacheTable {
-            table: RwLock::new(self.table.read().unwrap().clone()),
-            size: RwLock::new(self.get_size()),
-            mask: AtomicUsize::new(self.mask.load(MEMORY_ORDERING)),
-            is_safe_to_do_bitwise_and: AtomicBool::new(
-                self.is_safe_to_do_bitwise_and.load(MEMORY_ORDERING),
-            ),
-            num_cells_filled: AtomicUsize::new(self.num_cells_filled.load(MEMORY_ORDERING)),
-            #[cfg(feature = "extras")]
-            num_overwrites: AtomicUsize::new(self.num_overwrites.load(MEMORY_ORDERING)),
-            #[cfg(feature = "extras")]
-            num_collisions: AtomicUsize::new(self.num_collisions.load(MEMORY_ORDERING)),
-            #[cfg(feature = "extras")]
-            zero_hit: AtomicUsize::new(self.zero_hit.load(MEMORY_ORDERING)),
+        let cache_table = Self::new_with_bucket_size(self.get_size(), self.bucket_size);
+        for (slot, entry) in cache_table.get_slots().slots.iter().zip(self.iter()) {
+            if let Some(entry) = entry {
+                slot.store(entry.hash, entry.entry);
+            }
+        }
+        cache_table
+            .num_cells_filled
+            .store(self.get_num_cells_filled(), MEMORY_ORDERING);
+        #[cfg(feature = "extras")]
+        {
+            cache_table
+                .num_overwrites
+                .store(self.get_num_overwrites(), MEMORY_ORDERING);
+            cache_table
+                .num_collisions
+                .store(self.get_num_collisions(), MEMORY_ORDERING);
+            cache_table
+                .zero_hit
+                .store(self.get_zero_hit(), MEMORY_ORDERING);
+        }
+        cache_table
+    }
+}
+
+impl<T: Copy + PartialEq + Compress<CompressedItem = u64>> Default for CacheTable<T>
+where
+    u64: Decompress<T>,
+{
+    fn default() -> Self {
+        Self::new(CacheTableSize::ZERO)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "CacheTable")]
+struct SerializableCacheTable<T> {
+    size: CacheTableSize,
+    bucket_size: usize,
+    entries: Vec<Option<CacheTableEntry<T>>>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Copy + PartialEq + Compress<CompressedItem = u64> + Serialize> Serialize for CacheTable<T>
+where
+    u64: Decompress<T>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        SerializableCacheTable {
+            size: self.get_size(),
+            bucket_size: self.bucket_size,
+            entries: self.iter().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Copy + PartialEq + Compress<CompressedItem = u64> + Deserialize<'de>> Deserialize<'de>
+    for CacheTable<T>
+where
+    u64: Decompress<T>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let serializable_cache_table = SerializableCacheTable::<T>::deserialize(deserializer)?;
+        let cache_table = Self::new_with_bucket_size(
+            serializable_cache_table.size,
+            serializable_cache_table.bucket_size,
+        );
+        for entry in serializable_cache_table.entries.into_iter().flatten() {
+            cache_table.add(entry.hash.get(), entry.entry);
         }
+        Ok(cache_table)
     }
 }
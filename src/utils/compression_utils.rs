@@ -34,7 +34,8 @@ impl Compress for Move {
         let mut compressed_move = 0;
         compressed_move ^= self.get_source().compress() << 6;
         compressed_move ^= self.get_dest().compress();
-        compressed_move ^= (self.get_promotion().compress() as Self::CompressedItem) << 12;
+        compressed_move ^=
+            (self.get_promotion().or(self.get_drop()).compress() as Self::CompressedItem) << 12;
         compressed_move
     }
 }
@@ -57,6 +58,15 @@ impl Compress for ValidOrNullMove {
     }
 }
 
+impl Compress for Score {
+    type CompressedItem = u64;
+
+    #[inline]
+    fn compress(self) -> Self::CompressedItem {
+        self as u16 as Self::CompressedItem
+    }
+}
+
 impl Decompress<Option<PieceType>> for u8 {
     #[inline]
     fn decompress(self) -> Option<PieceType> {
@@ -94,6 +104,13 @@ impl Decompress<Option<Move>> for u16 {
     }
 }
 
+impl Decompress<Score> for u64 {
+    #[inline]
+    fn decompress(self) -> Score {
+        self as u16 as Score
+    }
+}
+
 impl Decompress<ValidOrNullMove> for u16 {
     #[inline]
     fn decompress(self) -> ValidOrNullMove {
@@ -5,3 +5,15 @@ pub const fn is_checkmate(score: Score) -> bool {
     let abs_score = score.abs();
     abs_score > CHECKMATE_THRESHOLD && abs_score <= CHECKMATE_SCORE
 }
+
+/// Converts whole plies to the fractional depth units used by the search.
+#[inline]
+pub const fn plies_to_fractional_depth(depth: Depth) -> FractionalDepth {
+    depth as FractionalDepth * ONE_PLY
+}
+
+/// Whole plies of a fractional depth, as stored in the transposition table, the fraction of a ply is dropped.
+#[inline]
+pub const fn fractional_depth_to_plies(depth: FractionalDepth) -> Depth {
+    (depth / ONE_PLY) as Depth
+}
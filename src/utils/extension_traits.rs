@@ -111,6 +111,36 @@ pub trait PositionEvaluation: Clone + Send + 'static {
     #[inline]
     fn print_info(&self) {}
 
+    #[inline]
+    #[expect(unused_variables)]
+    fn set_evaluation_cache_size(&self, size: CacheTableSize) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    #[expect(unused_variables)]
+    fn set_pawn_hash_size(&self, size: CacheTableSize) -> Result<()> {
+        Ok(())
+    }
+
+    /// Resizes both caches, the evaluators having both of them allocate the new ones before resizing any, so that
+    /// they are left unchanged if the memory is not available.
+    #[inline]
+    fn set_cache_sizes(
+        &self,
+        evaluation_cache_size: CacheTableSize,
+        pawn_hash_size: CacheTableSize,
+    ) -> Result<()> {
+        self.set_evaluation_cache_size(evaluation_cache_size)?;
+        self.set_pawn_hash_size(pawn_hash_size)
+    }
+
+    /// Memory used by the caches of the evaluator in bytes.
+    #[inline]
+    fn get_memory_usage(&self) -> usize {
+        0
+    }
+
     #[inline]
     fn evaluate_flipped(&mut self, position: &BoardPosition) -> Score {
         position.score_flipped(self.evaluate(position))
@@ -141,7 +171,7 @@ pub trait ChessEngine {
 
     fn get_board(&self) -> &Board;
     fn get_board_mut(&mut self) -> &mut Board;
-    fn set_transposition_table_size(&self, size: CacheTableSize);
+    fn set_transposition_table_size(&self, size: CacheTableSize) -> Result<()>;
     fn set_num_threads(&mut self, num_threads: NonZeroUsize);
     fn set_move_overhead(&mut self, duration: Duration);
     fn get_opening_book(&self) -> Option<&dyn PolyglotBook>;
@@ -150,10 +180,52 @@ pub trait ChessEngine {
     fn set_termination(&self, b: bool);
     fn set_fen(&mut self, fen: &str) -> Result<()>;
     fn clear_hash(&mut self);
+
+    /// Sets the position from the fen as a position of the given variant, [`Self::set_fen`] keeps the variant of the
+    /// current board instead.
+    fn set_fen_and_variant(&mut self, fen: &str, variant: Variant) -> Result<()> {
+        *self.get_board_mut() = Board::new_variant(variant);
+        self.set_fen(fen)
+    }
+
+    #[inline]
+    #[expect(unused_variables)]
+    fn set_evaluation_cache_size(&self, size: CacheTableSize) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    #[expect(unused_variables)]
+    fn set_pawn_hash_size(&self, size: CacheTableSize) -> Result<()> {
+        Ok(())
+    }
+
+    /// Splits the memory budget between the hash tables and caches of the engine.
+    fn set_memory_budget(&self, memory_manager: MemoryManager) -> Result<()> {
+        self.set_transposition_table_size(memory_manager.get_transposition_table_size())?;
+        self.set_evaluation_cache_size(memory_manager.get_evaluation_cache_size())?;
+        self.set_pawn_hash_size(memory_manager.get_pawn_hash_size())
+    }
+
+    /// Memory used by the hash tables and caches of the engine in bytes.
+    #[inline]
+    fn get_memory_usage(&self) -> usize {
+        0
+    }
+
     fn evaluate_current_position(&mut self) -> Score;
     fn evaluate_current_position_flipped(&mut self) -> Score;
     fn go(&mut self, config: &SearchConfig, verbose: bool) -> SearchInfo;
 
+    /// Sets up a new game, engines may keep their search state if it continues the current one.
+    fn set_position(&mut self, board: Board) -> Result<()> {
+        self.set_fen_and_variant(&board.get_starting_board_fen(), board.get_variant())?;
+        for valid_or_null_move in board.get_all_stack_moves() {
+            self.get_board_mut().push(valid_or_null_move)?;
+        }
+        Ok(())
+    }
+
     #[inline]
     fn print_info(&self) {}
 
@@ -161,6 +233,69 @@ pub trait ChessEngine {
     #[expect(unused_variables)]
     fn set_optional_io_reader(&mut self, optional_io_reader: Self::IoReader) {}
 
+    #[inline]
+    fn get_search_statistics(&self) -> Option<&SearchStatistics> {
+        None
+    }
+
+    #[inline]
+    #[expect(unused_variables)]
+    fn set_collecting_search_statistics(&mut self, b: bool) {}
+
+    #[inline]
+    #[expect(unused_variables)]
+    fn set_experience_file(&mut self, path: &str) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    #[expect(unused_variables)]
+    fn set_using_experience(&mut self, b: bool) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called whenever the game being played may have ended, the result of a game in progress should be ignored and every game reported once.
+    #[inline]
+    #[expect(unused_variables)]
+    fn on_game_end(&mut self, result: GameResult) {}
+
+    #[inline]
+    #[expect(unused_variables)]
+    fn save_hash(&self, path: &str) -> Result<()> {
+        Err(TimecatError::TranspositionTableNotFound)
+    }
+
+    #[inline]
+    #[expect(unused_variables)]
+    fn load_hash(&mut self, path: &str) -> Result<()> {
+        Err(TimecatError::TranspositionTableNotFound)
+    }
+
+    #[inline]
+    #[expect(unused_variables)]
+    fn set_checkpoint_file(&mut self, path: Option<&str>) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    #[expect(unused_variables)]
+    fn set_checkpoint_interval(&mut self, interval: Duration) {}
+
+    #[inline]
+    #[expect(unused_variables)]
+    fn resume_from_checkpoint(&mut self, path: &str) -> Result<()> {
+        Err(TimecatError::CheckpointNotSupported)
+    }
+
+    #[inline]
+    fn get_search_tree(&self) -> Option<&SearchTree> {
+        None
+    }
+
+    #[inline]
+    #[expect(unused_variables)]
+    fn set_search_tree_config(&mut self, config: Option<SearchTreeConfig>) {}
+
     #[inline]
     fn get_opening_book_weighted_move(&self) -> Option<WeightedMove> {
         self.get_opening_book()?
@@ -107,3 +107,9 @@ impl FromStr for File {
         }
     }
 }
+
+impl fmt::Display for File {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", (b'a' + self.to_int()) as char)
+    }
+}
@@ -37,7 +37,12 @@ pub struct TimecatDefaults {
     pub book_path: Option<&'static str>,
     pub inbuilt_book_bytes: Option<&'static [u8]>,
     pub debug_mode: bool,
+    pub verify_hash: bool,
     pub chess960_mode: bool,
+    pub variant: Variant,
+    pub use_experience: bool,
+    pub experience_path: &'static str,
+    pub checkpoint_interval: Duration,
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -48,6 +53,9 @@ pub struct GlobalTimecatState {
     _console_mode: AtomicBool,
     _long_algebraic_notation: AtomicBool,
     _debug_mode: AtomicBool,
+    _verify_hash: AtomicBool,
+    _chess960_mode: AtomicBool,
+    _variant: AtomicU8,
 }
 
 impl Default for GlobalTimecatState {
@@ -64,6 +72,9 @@ impl GlobalTimecatState {
             _console_mode: AtomicBool::new(TIMECAT_DEFAULTS.console_mode),
             _long_algebraic_notation: AtomicBool::new(TIMECAT_DEFAULTS.long_algebraic_notation),
             _debug_mode: AtomicBool::new(TIMECAT_DEFAULTS.debug_mode),
+            _verify_hash: AtomicBool::new(TIMECAT_DEFAULTS.verify_hash),
+            _chess960_mode: AtomicBool::new(TIMECAT_DEFAULTS.chess960_mode),
+            _variant: AtomicU8::new(TIMECAT_DEFAULTS.variant.to_int()),
         }
     }
 
@@ -141,4 +152,39 @@ impl GlobalTimecatState {
         self._debug_mode.store(b, MEMORY_ORDERING);
         print_uci_info("Debug Mode is set to", b);
     }
+
+    /// Whether the hashes are recomputed from scratch and verified after every push and pop.
+    #[inline]
+    pub fn is_verifying_hash(&self) -> bool {
+        self._verify_hash.load(MEMORY_ORDERING)
+    }
+
+    pub fn set_hash_verification(&self, b: bool) {
+        self._verify_hash.store(b, MEMORY_ORDERING);
+        print_uci_info("Hash verification is set to", b);
+    }
+
+    /// Whether the positions set up from now on write castling as the king capturing its own rook, even when the pieces start on the standard squares.
+    #[inline]
+    pub fn is_in_chess960_mode(&self) -> bool {
+        self._chess960_mode.load(MEMORY_ORDERING)
+    }
+
+    pub fn set_chess960_mode(&self, b: bool) {
+        self._chess960_mode.store(b, MEMORY_ORDERING);
+        print_uci_info("Chess960 mode is set to", b);
+    }
+
+    /// The variant of the positions set up from now on.
+    /// Variant of the positions set up by the UCI and console commands, the boards created directly are standard ones
+    /// unless their variant is given.
+    #[inline]
+    pub fn get_variant(&self) -> Variant {
+        Variant::from_int(self._variant.load(MEMORY_ORDERING))
+    }
+
+    pub fn set_variant(&self, variant: Variant) {
+        self._variant.store(variant.to_int(), MEMORY_ORDERING);
+        print_uci_info("Variant is set to", variant);
+    }
 }
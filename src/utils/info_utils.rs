@@ -37,6 +37,28 @@ pub fn print_engine_version() {
     println_wasm!("{}", get_engine_version().colorize(SUCCESS_MESSAGE_STYLE));
 }
 
+pub fn print_memory_usage(memory_usage: usize) {
+    let mut to_print = format!(
+        "Hash tables and caches are taking {:.2} MB of memory.",
+        memory_usage as f64 / 2_usize.pow(20) as f64
+    );
+    if GLOBAL_TIMECAT_STATE.is_in_uci_mode() {
+        to_print = "info string ".to_string() + to_print.trim();
+    }
+    println_wasm!("{}", to_print.colorize(INFO_MESSAGE_STYLE));
+}
+
+pub fn print_slider_attack_method() {
+    let mut to_print = format!(
+        "Slider attacks are looked up using {}.",
+        SliderAttackMethod::get_selected()
+    );
+    if GLOBAL_TIMECAT_STATE.is_in_uci_mode() {
+        to_print = "info string ".to_string() + to_print.trim();
+    }
+    println_wasm!("{}", to_print.colorize(INFO_MESSAGE_STYLE));
+}
+
 pub fn print_cache_table_info(
     name: &str,
     table_len: impl fmt::Display,
@@ -16,6 +16,8 @@ pub mod psqt;
 pub mod pv_utils;
 pub mod ranks;
 pub mod repetition_table;
+pub mod search_statistics;
+pub mod search_tree;
 pub mod search_utils;
 pub mod serde_extension;
 pub mod square;
@@ -41,6 +43,8 @@ pub use psqt::*;
 pub use pv_utils::*;
 pub use ranks::*;
 pub use repetition_table::*;
+pub use search_statistics::*;
+pub use search_tree::*;
 pub use search_utils::*;
 pub use serde_extension::*;
 pub use square::*;
@@ -1,5 +1,8 @@
 use super::*;
 
+/// A move of a piece from one square to another, or a drop of a piece from the pocket in Crazyhouse.
+///
+/// A drop has no source square and is stored with the source square same as the destination square, which can never happen for the other moves.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
 pub struct Move {
@@ -26,6 +29,27 @@ impl Move {
         Ok(Self::new_unchecked(source, dest, promotion))
     }
 
+    #[inline]
+    pub const fn new_drop(piece_type: PieceType, dest: Square) -> Self {
+        Self::new_unchecked(dest, dest, Some(piece_type))
+    }
+
+    #[inline]
+    pub const fn is_drop(&self) -> bool {
+        self.source.to_int() == self.dest.to_int()
+    }
+
+    /// Piece type dropped from the pocket, if the move is a drop.
+    #[inline]
+    pub const fn get_drop(&self) -> Option<PieceType> {
+        if self.is_drop() {
+            self.promotion
+        } else {
+            None
+        }
+    }
+
+    /// The source square of the move, which is the destination square for a drop.
     #[inline]
     pub const fn get_source(&self) -> Square {
         self.source
@@ -38,7 +62,11 @@ impl Move {
 
     #[inline]
     pub const fn get_promotion(&self) -> Option<PieceType> {
-        self.promotion
+        if self.is_drop() {
+            None
+        } else {
+            self.promotion
+        }
     }
 
     #[inline]
@@ -48,9 +76,14 @@ impl Move {
 
     pub fn from_san(position: &BoardPosition, san: &str) -> Result<Self> {
         // TODO: Make the logic better
-        let san = san.trim().replace('0', "O");
+        // The check and checkmate suffixes are ignored, as they are not always written correctly.
+        let san = san.trim().trim_end_matches(['+', '#']).replace('0', "O");
+        // Pawn drops may be written without the piece.
+        let san = san
+            .strip_prefix('@')
+            .map_or(san.clone(), |dest| format!("P@{dest}"));
         for move_ in position.generate_legal_moves() {
-            if move_.san(position).unwrap() == san {
+            if move_.algebraic_without_suffix(position, false).unwrap() == san {
                 return Ok(move_);
             }
         }
@@ -73,6 +106,11 @@ impl Move {
         let source = self.get_source();
         let dest = self.get_dest();
 
+        // Drops.
+        if let Some(piece) = self.get_drop() {
+            return Ok(format!("{}@{dest}", piece.to_colored_piece_string(White)));
+        }
+
         // Castling.
         if position.is_castling(self) {
             return if dest.get_file() < source.get_file() {
@@ -200,6 +238,13 @@ impl FromStr for Move {
         if s.len() > 6 {
             return Err(error.clone());
         }
+        if let Some((piece, dest)) = s.split_once('@') {
+            let piece = PieceType::from_str(piece).map_err(|_| error.clone())?;
+            if piece == King {
+                return Err(error);
+            }
+            return Ok(Self::new_drop(piece, Square::from_str(dest)?));
+        }
         let source = Square::from_str(s.get(0..2).ok_or(error.clone())?)?;
         let dest = Square::from_str(s.get(2..4).ok_or(error.clone())?)?;
 
@@ -210,6 +255,7 @@ impl FromStr for Move {
                 'r' => Rook,
                 'n' => Knight,
                 'b' => Bishop,
+                'k' => King,
                 _ => return Err(error.clone()),
             });
         }
@@ -220,6 +266,9 @@ impl FromStr for Move {
 
 impl fmt::Display for Move {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(piece) = self.get_drop() {
+            return write!(f, "{}@{}", piece.to_colored_piece_string(White), self.dest);
+        }
         match self.promotion {
             Some(piece) => write!(f, "{}{}{}", self.source, self.dest, piece),
             None => write!(f, "{}{}", self.source, self.dest),
@@ -292,7 +341,7 @@ impl ValidOrNullMove {
 
     #[inline]
     pub fn get_promotion(&self) -> Option<PieceType> {
-        self.into_inner()?.promotion
+        self.into_inner()?.get_promotion()
     }
 
     pub fn from_san(position: &BoardPosition, san: &str) -> Result<Self> {
@@ -5,7 +5,7 @@ pub fn extract_pv_from_t_table(
     transposition_table: &TranspositionTable,
 ) -> Vec<Move> {
     let mut pv = Vec::new();
-    let best_move = transposition_table.read_best_move(position.get_hash());
+    let best_move = transposition_table.read_legal_best_move(position);
     if let Some(best_move) = best_move {
         pv.push(best_move);
         pv.append(&mut extract_pv_from_t_table(
@@ -0,0 +1,351 @@
+use super::*;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchStatistics {
+    nodes_per_ply: Vec<usize>,
+    nodes_per_iteration: Vec<usize>,
+    num_quiescence_nodes: usize,
+    num_beta_cutoffs: usize,
+    num_first_move_beta_cutoffs: usize,
+    num_tt_probes: usize,
+    num_tt_hits: usize,
+    num_tt_usable_hits: usize,
+    num_lmr_searches: usize,
+    num_lmr_re_searches: usize,
+    num_null_move_searches: usize,
+    num_null_move_cutoffs: usize,
+    tt_hash_full: Option<f64>,
+    tt_overwrites: Option<usize>,
+    tt_collisions: Option<usize>,
+    tt_zero_hit: Option<usize>,
+}
+
+impl SearchStatistics {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn reset_variables(&mut self) {
+        *self = Self::default();
+    }
+
+    #[inline]
+    pub fn on_node(&mut self, ply: Ply) {
+        if self.nodes_per_ply.len() <= ply {
+            self.nodes_per_ply.resize(ply + 1, 0);
+        }
+        *get_item_unchecked_mut!(self.nodes_per_ply, ply) += 1;
+    }
+
+    #[inline]
+    pub fn on_quiescence_node(&mut self, ply: Ply) {
+        self.on_node(ply);
+        self.num_quiescence_nodes += 1;
+    }
+
+    #[inline]
+    pub fn on_beta_cutoff(&mut self, move_index: usize) {
+        self.num_beta_cutoffs += 1;
+        if move_index == 0 {
+            self.num_first_move_beta_cutoffs += 1;
+        }
+    }
+
+    #[inline]
+    pub fn on_tt_probe(&mut self, is_hit: bool, is_usable: bool) {
+        self.num_tt_probes += 1;
+        self.num_tt_hits += is_hit as usize;
+        self.num_tt_usable_hits += is_usable as usize;
+    }
+
+    #[inline]
+    pub fn on_lmr_search(&mut self, is_re_searched: bool) {
+        self.num_lmr_searches += 1;
+        self.num_lmr_re_searches += is_re_searched as usize;
+    }
+
+    #[inline]
+    pub fn on_null_move_search(&mut self, is_successful: bool) {
+        self.num_null_move_searches += 1;
+        self.num_null_move_cutoffs += is_successful as usize;
+    }
+
+    pub fn on_iteration_completion(&mut self) {
+        let nodes_till_last_iteration = self.nodes_per_iteration.iter().sum::<usize>();
+        self.nodes_per_iteration
+            .push(self.get_num_nodes() - nodes_till_last_iteration);
+    }
+
+    pub fn update_transposition_table_statistics(&mut self, table: &TranspositionTable) {
+        self.tt_hash_full = Some(table.get_hash_full());
+        #[cfg(feature = "extras")]
+        {
+            self.tt_overwrites = Some(table.get_num_overwrites());
+            self.tt_collisions = Some(table.get_num_collisions());
+            self.tt_zero_hit = Some(table.get_zero_hit());
+        }
+    }
+
+    #[inline]
+    pub fn get_nodes_per_ply(&self) -> &[usize] {
+        &self.nodes_per_ply
+    }
+
+    #[inline]
+    pub fn get_nodes_per_iteration(&self) -> &[usize] {
+        &self.nodes_per_iteration
+    }
+
+    #[inline]
+    pub fn get_num_nodes(&self) -> usize {
+        self.nodes_per_ply.iter().sum()
+    }
+
+    #[inline]
+    pub fn get_num_quiescence_nodes(&self) -> usize {
+        self.num_quiescence_nodes
+    }
+
+    #[inline]
+    pub fn get_num_beta_cutoffs(&self) -> usize {
+        self.num_beta_cutoffs
+    }
+
+    #[inline]
+    pub fn get_num_first_move_beta_cutoffs(&self) -> usize {
+        self.num_first_move_beta_cutoffs
+    }
+
+    #[inline]
+    pub fn get_num_tt_probes(&self) -> usize {
+        self.num_tt_probes
+    }
+
+    #[inline]
+    pub fn get_num_tt_hits(&self) -> usize {
+        self.num_tt_hits
+    }
+
+    #[inline]
+    pub fn get_num_tt_usable_hits(&self) -> usize {
+        self.num_tt_usable_hits
+    }
+
+    #[inline]
+    pub fn get_num_lmr_searches(&self) -> usize {
+        self.num_lmr_searches
+    }
+
+    #[inline]
+    pub fn get_num_lmr_re_searches(&self) -> usize {
+        self.num_lmr_re_searches
+    }
+
+    #[inline]
+    pub fn get_num_null_move_searches(&self) -> usize {
+        self.num_null_move_searches
+    }
+
+    #[inline]
+    pub fn get_num_null_move_cutoffs(&self) -> usize {
+        self.num_null_move_cutoffs
+    }
+
+    #[inline]
+    fn get_rate(numerator: usize, denominator: usize) -> Option<f64> {
+        (denominator != 0).then(|| numerator as f64 / denominator as f64)
+    }
+
+    #[inline]
+    pub fn get_beta_cutoff_rate(&self) -> Option<f64> {
+        Self::get_rate(self.num_beta_cutoffs, self.get_num_nodes())
+    }
+
+    #[inline]
+    pub fn get_first_move_cutoff_rate(&self) -> Option<f64> {
+        Self::get_rate(self.num_first_move_beta_cutoffs, self.num_beta_cutoffs)
+    }
+
+    #[inline]
+    pub fn get_tt_hit_rate(&self) -> Option<f64> {
+        Self::get_rate(self.num_tt_hits, self.num_tt_probes)
+    }
+
+    #[inline]
+    pub fn get_tt_usable_hit_rate(&self) -> Option<f64> {
+        Self::get_rate(self.num_tt_usable_hits, self.num_tt_probes)
+    }
+
+    #[inline]
+    pub fn get_lmr_re_search_rate(&self) -> Option<f64> {
+        Self::get_rate(self.num_lmr_re_searches, self.num_lmr_searches)
+    }
+
+    #[inline]
+    pub fn get_null_move_success_rate(&self) -> Option<f64> {
+        Self::get_rate(self.num_null_move_cutoffs, self.num_null_move_searches)
+    }
+
+    #[inline]
+    pub fn get_quiescence_nodes_share(&self) -> Option<f64> {
+        Self::get_rate(self.num_quiescence_nodes, self.get_num_nodes())
+    }
+
+    /// Geometric mean of the growth of nodes between consecutive iterations.
+    pub fn get_effective_branching_factor(&self) -> Option<f64> {
+        let first = *self.nodes_per_iteration.iter().find(|&&nodes| nodes != 0)?;
+        let num_iterations = self
+            .nodes_per_iteration
+            .iter()
+            .skip_while(|&&nodes| nodes == 0)
+            .count();
+        if num_iterations < 2 {
+            return None;
+        }
+        let last = *self.nodes_per_iteration.last()?;
+        Some((last as f64 / first as f64).powf(1.0 / (num_iterations - 1) as f64))
+    }
+
+    #[inline]
+    fn stringify_optional_f64(value: Option<f64>) -> String {
+        value.map_or_else(|| "null".to_string(), |value| format!("{value:.4}"))
+    }
+
+    #[inline]
+    fn stringify_optional_usize(value: Option<usize>) -> String {
+        value.map_or_else(|| "null".to_string(), |value| value.to_string())
+    }
+
+    pub fn to_json(&self) -> String {
+        let fields = [
+            ("nodes", self.get_num_nodes().to_string()),
+            (
+                "nodes_per_ply",
+                format!("[{}]", self.nodes_per_ply.iter().join(",")),
+            ),
+            (
+                "nodes_per_iteration",
+                format!("[{}]", self.nodes_per_iteration.iter().join(",")),
+            ),
+            ("quiescence_nodes", self.num_quiescence_nodes.to_string()),
+            (
+                "quiescence_nodes_share",
+                Self::stringify_optional_f64(self.get_quiescence_nodes_share()),
+            ),
+            ("beta_cutoffs", self.num_beta_cutoffs.to_string()),
+            (
+                "beta_cutoff_rate",
+                Self::stringify_optional_f64(self.get_beta_cutoff_rate()),
+            ),
+            (
+                "first_move_beta_cutoffs",
+                self.num_first_move_beta_cutoffs.to_string(),
+            ),
+            (
+                "first_move_cutoff_rate",
+                Self::stringify_optional_f64(self.get_first_move_cutoff_rate()),
+            ),
+            ("tt_probes", self.num_tt_probes.to_string()),
+            ("tt_hits", self.num_tt_hits.to_string()),
+            ("tt_usable_hits", self.num_tt_usable_hits.to_string()),
+            (
+                "tt_hit_rate",
+                Self::stringify_optional_f64(self.get_tt_hit_rate()),
+            ),
+            (
+                "tt_usable_hit_rate",
+                Self::stringify_optional_f64(self.get_tt_usable_hit_rate()),
+            ),
+            ("lmr_searches", self.num_lmr_searches.to_string()),
+            ("lmr_re_searches", self.num_lmr_re_searches.to_string()),
+            (
+                "lmr_re_search_rate",
+                Self::stringify_optional_f64(self.get_lmr_re_search_rate()),
+            ),
+            (
+                "null_move_searches",
+                self.num_null_move_searches.to_string(),
+            ),
+            ("null_move_cutoffs", self.num_null_move_cutoffs.to_string()),
+            (
+                "null_move_success_rate",
+                Self::stringify_optional_f64(self.get_null_move_success_rate()),
+            ),
+            (
+                "effective_branching_factor",
+                Self::stringify_optional_f64(self.get_effective_branching_factor()),
+            ),
+            (
+                "tt_hash_full",
+                Self::stringify_optional_f64(self.tt_hash_full),
+            ),
+            (
+                "tt_overwrites",
+                Self::stringify_optional_usize(self.tt_overwrites),
+            ),
+            (
+                "tt_collisions",
+                Self::stringify_optional_usize(self.tt_collisions),
+            ),
+            (
+                "tt_zero_hit",
+                Self::stringify_optional_usize(self.tt_zero_hit),
+            ),
+        ];
+        format!(
+            "{{{}}}",
+            fields
+                .into_iter()
+                .map(|(key, value)| format!("{key:?}:{value}"))
+                .join(",")
+        )
+    }
+
+    pub fn print_info(&self) {
+        let format_rate = |rate: Option<f64>| {
+            rate.map_or(STRINGIFY_NONE.to_string(), |rate| {
+                format!("{:.2}%", rate * 100.0)
+            })
+        };
+        force_println_info("Nodes", self.get_num_nodes());
+        force_println_info(
+            "Nodes Per Ply",
+            format!("[{}]", self.nodes_per_ply.iter().join(", ")),
+        );
+        force_println_info(
+            "Nodes Per Iteration",
+            format!("[{}]", self.nodes_per_iteration.iter().join(", ")),
+        );
+        force_println_info(
+            "Quiescence Nodes Share",
+            format_rate(self.get_quiescence_nodes_share()),
+        );
+        force_println_info("Beta Cutoffs", self.num_beta_cutoffs);
+        force_println_info("Beta Cutoff Rate", format_rate(self.get_beta_cutoff_rate()));
+        force_println_info(
+            "First Move Cutoff Rate",
+            format_rate(self.get_first_move_cutoff_rate()),
+        );
+        force_println_info("TT Hit Rate", format_rate(self.get_tt_hit_rate()));
+        force_println_info(
+            "TT Usable Hit Rate",
+            format_rate(self.get_tt_usable_hit_rate()),
+        );
+        force_println_info(
+            "LMR Re-search Rate",
+            format_rate(self.get_lmr_re_search_rate()),
+        );
+        force_println_info(
+            "Null Move Success Rate",
+            format_rate(self.get_null_move_success_rate()),
+        );
+        force_println_info(
+            "Effective Branching Factor",
+            self.get_effective_branching_factor()
+                .map_or(STRINGIFY_NONE.to_string(), |ebf| format!("{ebf:.2}")),
+        );
+    }
+}
@@ -0,0 +1,390 @@
+use super::*;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum SearchTreeFormat {
+    Dot,
+    Json,
+}
+
+impl SearchTreeFormat {
+    pub fn from_path(path: &str) -> Self {
+        if path.to_lowercase().ends_with(".json") {
+            Self::Json
+        } else {
+            Self::Dot
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SearchTreeConfig {
+    max_ply: Ply,
+    max_nodes: usize,
+    moves_filter: Option<Vec<Move>>,
+}
+
+impl SearchTreeConfig {
+    #[inline]
+    pub fn new(max_ply: Ply, max_nodes: usize, moves_filter: Option<Vec<Move>>) -> Self {
+        Self {
+            max_ply,
+            max_nodes,
+            moves_filter,
+        }
+    }
+
+    #[inline]
+    pub fn get_max_ply(&self) -> Ply {
+        self.max_ply
+    }
+
+    #[inline]
+    pub fn get_max_nodes(&self) -> usize {
+        self.max_nodes
+    }
+
+    #[inline]
+    pub fn get_moves_filter(&self) -> Option<&[Move]> {
+        self.moves_filter.as_deref()
+    }
+
+    pub fn set_max_ply(mut self, max_ply: Ply) -> Self {
+        self.max_ply = max_ply;
+        self
+    }
+
+    pub fn set_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    pub fn set_moves_filter(mut self, moves_filter: impl Into<Option<Vec<Move>>>) -> Self {
+        self.moves_filter = moves_filter.into();
+        self
+    }
+}
+
+impl Default for SearchTreeConfig {
+    fn default() -> Self {
+        Self {
+            max_ply: 4,
+            max_nodes: 100_000,
+            moves_filter: None,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SearchTreePruning {
+    TranspositionTableCutoff,
+    MateDistancePruning,
+    StaticEvaluationPruning,
+    Razoring,
+    NullMovePruning,
+    FutilityPruning {
+        move_: Move,
+    },
+    LateMoveReduction {
+        move_: Move,
+        reduction: FractionalDepth,
+    },
+    DeltaPruning,
+}
+
+impl fmt::Display for SearchTreePruning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TranspositionTableCutoff => write!(f, "tt cutoff"),
+            Self::MateDistancePruning => write!(f, "mate distance pruning"),
+            Self::StaticEvaluationPruning => write!(f, "static evaluation pruning"),
+            Self::Razoring => write!(f, "razoring"),
+            Self::NullMovePruning => write!(f, "null move pruning"),
+            Self::FutilityPruning { move_ } => write!(f, "futility pruning {move_}"),
+            Self::LateMoveReduction { move_, reduction } => write!(
+                f,
+                "lmr {move_} by {:.2}",
+                *reduction as f64 / ONE_PLY as f64
+            ),
+            Self::DeltaPruning => write!(f, "delta pruning"),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SearchTreeNode {
+    parent: Option<usize>,
+    valid_or_null_move: Option<ValidOrNullMove>,
+    ply: Ply,
+    depth: Depth,
+    alpha: Score,
+    beta: Score,
+    is_quiescence: bool,
+    static_evaluation: Option<Score>,
+    tt_hit: bool,
+    prunings: Vec<SearchTreePruning>,
+    score: Option<Score>,
+}
+
+impl SearchTreeNode {
+    #[inline]
+    pub fn get_parent(&self) -> Option<usize> {
+        self.parent
+    }
+
+    #[inline]
+    pub fn get_move(&self) -> Option<ValidOrNullMove> {
+        self.valid_or_null_move
+    }
+
+    #[inline]
+    pub fn get_ply(&self) -> Ply {
+        self.ply
+    }
+
+    #[inline]
+    pub fn get_depth(&self) -> Depth {
+        self.depth
+    }
+
+    #[inline]
+    pub fn get_window(&self) -> (Score, Score) {
+        (self.alpha, self.beta)
+    }
+
+    #[inline]
+    pub fn is_quiescence(&self) -> bool {
+        self.is_quiescence
+    }
+
+    #[inline]
+    pub fn get_static_evaluation(&self) -> Option<Score> {
+        self.static_evaluation
+    }
+
+    #[inline]
+    pub fn is_tt_hit(&self) -> bool {
+        self.tt_hit
+    }
+
+    #[inline]
+    pub fn get_prunings(&self) -> &[SearchTreePruning] {
+        &self.prunings
+    }
+
+    /// Score returned by the node from the perspective of the side to move, `None` if the search was stopped.
+    #[inline]
+    pub fn get_score(&self) -> Option<Score> {
+        self.score
+    }
+
+    fn get_move_string(&self) -> String {
+        self.valid_or_null_move
+            .map_or_else(|| "root".to_string(), |move_| move_.to_string())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default)]
+pub struct SearchTree {
+    config: SearchTreeConfig,
+    nodes: Vec<SearchTreeNode>,
+    stack: Vec<Option<usize>>,
+}
+
+impl SearchTree {
+    pub fn new(config: SearchTreeConfig) -> Self {
+        Self {
+            config,
+            nodes: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn get_config(&self) -> &SearchTreeConfig {
+        &self.config
+    }
+
+    #[inline]
+    pub fn get_nodes(&self) -> &[SearchTreeNode] {
+        &self.nodes
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.stack.clear();
+    }
+
+    fn is_recordable(&self, valid_or_null_move: Option<ValidOrNullMove>, ply: Ply) -> bool {
+        if ply > self.config.max_ply || self.nodes.len() >= self.config.max_nodes {
+            return false;
+        }
+        if ply == 1 {
+            if let Some(moves_filter) = self.config.get_moves_filter() {
+                return valid_or_null_move
+                    .and_then(|valid_or_null_move| valid_or_null_move.into_inner().copied())
+                    .is_some_and(|move_| moves_filter.contains(&move_));
+            }
+        }
+        true
+    }
+
+    pub fn open_node(
+        &mut self,
+        valid_or_null_move: Option<ValidOrNullMove>,
+        ply: Ply,
+        depth: Depth,
+        alpha: Score,
+        beta: Score,
+        is_quiescence: bool,
+    ) {
+        let parent = match self.stack.last() {
+            Some(&Some(parent)) => Some(parent),
+            Some(None) => {
+                self.stack.push(None);
+                return;
+            }
+            None => None,
+        };
+        if !self.is_recordable(valid_or_null_move, ply) {
+            self.stack.push(None);
+            return;
+        }
+        self.stack.push(Some(self.nodes.len()));
+        self.nodes.push(SearchTreeNode {
+            parent,
+            valid_or_null_move,
+            ply,
+            depth,
+            alpha,
+            beta,
+            is_quiescence,
+            static_evaluation: None,
+            tt_hit: false,
+            prunings: Vec::new(),
+            score: None,
+        });
+    }
+
+    pub fn close_node(&mut self, score: Option<Score>) {
+        if let Some(node) = self.get_current_node_mut() {
+            node.score = score;
+        }
+        self.stack.pop();
+    }
+
+    #[inline]
+    fn get_current_node_mut(&mut self) -> Option<&mut SearchTreeNode> {
+        let index = (*self.stack.last()?)?;
+        self.nodes.get_mut(index)
+    }
+
+    #[inline]
+    pub fn set_static_evaluation(&mut self, static_evaluation: Score) {
+        if let Some(node) = self.get_current_node_mut() {
+            node.static_evaluation = Some(static_evaluation);
+        }
+    }
+
+    #[inline]
+    pub fn set_tt_hit(&mut self, tt_hit: bool) {
+        if let Some(node) = self.get_current_node_mut() {
+            node.tt_hit = tt_hit;
+        }
+    }
+
+    #[inline]
+    pub fn add_pruning(&mut self, pruning: SearchTreePruning) {
+        if let Some(node) = self.get_current_node_mut() {
+            node.prunings.push(pruning);
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut lines = vec![
+            "digraph SearchTree {".to_string(),
+            "    node [shape=box, fontname=\"monospace\"];".to_string(),
+        ];
+        for (index, node) in self.nodes.iter().enumerate() {
+            let mut label = vec![
+                node.get_move_string(),
+                format!("ply {} depth {}", node.ply, node.depth),
+                format!("window [{}, {}]", node.alpha, node.beta),
+            ];
+            if node.is_quiescence {
+                label.push("quiescence".to_string());
+            }
+            if let Some(static_evaluation) = node.static_evaluation {
+                label.push(format!("eval {static_evaluation}"));
+            }
+            if node.tt_hit {
+                label.push("tt hit".to_string());
+            }
+            label.extend(node.prunings.iter().map(|pruning| pruning.to_string()));
+            label.push(format!(
+                "score {}",
+                node.score
+                    .map_or(STRINGIFY_NONE.to_string(), |score| score.to_string())
+            ));
+            lines.push(format!("    n{index} [label={:?}];", label.join("\n")));
+            if let Some(parent) = node.parent {
+                lines.push(format!(
+                    "    n{parent} -> n{index} [label={:?}];",
+                    node.get_move_string()
+                ));
+            }
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    pub fn to_json(&self) -> String {
+        let stringify_optional = |value: Option<String>| value.unwrap_or("null".to_string());
+        let nodes = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| {
+                format!(
+                    "{{\"id\":{index},\"parent\":{},\"move\":{},\"ply\":{},\"depth\":{},\"alpha\":{},\"beta\":{},\"quiescence\":{},\"static_eval\":{},\"tt_hit\":{},\"prunings\":[{}],\"score\":{}}}",
+                    stringify_optional(node.parent.map(|parent| parent.to_string())),
+                    stringify_optional(node.valid_or_null_move.map(|move_| format!("{:?}", move_.to_string()))),
+                    node.ply,
+                    node.depth,
+                    node.alpha,
+                    node.beta,
+                    node.is_quiescence,
+                    stringify_optional(node.static_evaluation.map(|score| score.to_string())),
+                    node.tt_hit,
+                    node.prunings.iter().map(|pruning| format!("{:?}", pruning.to_string())).join(","),
+                    stringify_optional(node.score.map(|score| score.to_string())),
+                )
+            })
+            .join(",");
+        format!("{{\"nodes\":[{nodes}]}}")
+    }
+
+    pub fn write_to_file(&self, path: &str, format: SearchTreeFormat) -> Result<()> {
+        let contents = match format {
+            SearchTreeFormat::Dot => self.to_dot(),
+            SearchTreeFormat::Json => self.to_json(),
+        };
+        fs::write(path, contents)?;
+        Ok(())
+    }
+}
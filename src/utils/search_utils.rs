@@ -128,6 +128,49 @@ impl GoCommand {
     }
 }
 
+impl fmt::Display for GoCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ponder => write!(f, "go ponder"),
+            Self::Infinite => write!(f, "go infinite"),
+            Self::Limit {
+                depth,
+                nodes,
+                mate,
+                movetime,
+                time_clock,
+            } => {
+                let mut commands = vec!["go".to_string()];
+                if let Some(depth) = depth {
+                    commands.push(format!("depth {depth}"));
+                }
+                if let Some(nodes) = nodes {
+                    commands.push(format!("nodes {nodes}"));
+                }
+                if let Some(mate) = mate {
+                    commands.push(format!("mate {mate}"));
+                }
+                if let Some(movetime) = movetime {
+                    commands.push(format!("movetime {}", movetime.as_millis()));
+                }
+                if let Some(time_clock) = time_clock {
+                    commands.push(format!(
+                        "wtime {} btime {} winc {} binc {}",
+                        time_clock.wtime.as_millis(),
+                        time_clock.btime.as_millis(),
+                        time_clock.winc.as_millis(),
+                        time_clock.binc.as_millis(),
+                    ));
+                    if let Some(moves_to_go) = time_clock.moves_to_go {
+                        commands.push(format!("movestogo {moves_to_go}"));
+                    }
+                }
+                write!(f, "{}", commands.join(" "))
+            }
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Default, Debug)]
 struct LimitParser {
     depth: Option<Depth>,
@@ -248,6 +291,16 @@ impl Deref for SearchConfig {
     }
 }
 
+impl fmt::Display for SearchConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.go_command)?;
+        if let Some(moves) = self.get_moves_to_search() {
+            write!(f, " searchmoves {}", moves.iter().join(" "))?;
+        }
+        Ok(())
+    }
+}
+
 impl From<GoCommand> for SearchConfig {
     #[inline]
     fn from(go_command: GoCommand) -> Self {
@@ -357,6 +410,148 @@ impl FromStr for SearchConfig {
     }
 }
 
+/// Root search which returned a score outside the aspiration window and had to be repeated, the window and the score are
+/// from the perspective of white.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AspirationReSearch {
+    alpha: Score,
+    beta: Score,
+    score: Score,
+    bound: EntryFlagHash,
+    nodes: usize,
+    time_elapsed: Duration,
+}
+
+impl AspirationReSearch {
+    pub fn new(
+        alpha: Score,
+        beta: Score,
+        score: Score,
+        nodes: usize,
+        time_elapsed: Duration,
+    ) -> Self {
+        Self {
+            alpha,
+            beta,
+            score,
+            bound: if score <= alpha {
+                EntryFlagHash::Alpha
+            } else {
+                EntryFlagHash::Beta
+            },
+            nodes,
+            time_elapsed,
+        }
+    }
+
+    #[inline]
+    pub fn get_window(&self) -> (Score, Score) {
+        (self.alpha, self.beta)
+    }
+
+    #[inline]
+    pub fn get_score(&self) -> Score {
+        self.score
+    }
+
+    #[inline]
+    pub fn get_bound(&self) -> EntryFlagHash {
+        self.bound
+    }
+
+    #[inline]
+    pub fn get_num_nodes_searched(&self) -> usize {
+        self.nodes
+    }
+
+    #[inline]
+    pub fn get_time_elapsed(&self) -> Duration {
+        self.time_elapsed
+    }
+}
+
+/// Result of one iterative deepening depth, scores are from the perspective of white like [`SearchInfo::get_score`].
+///
+/// The last iteration is only a bound if the search stopped while re-searching a depth which failed outside the aspiration window.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchIteration {
+    depth: Depth,
+    score: Score,
+    bound: EntryFlagHash,
+    nodes: usize,
+    time_elapsed: Duration,
+    pv: Vec<Move>,
+    aspiration_re_searches: Vec<AspirationReSearch>,
+}
+
+impl SearchIteration {
+    pub fn new(
+        depth: Depth,
+        score: Score,
+        bound: EntryFlagHash,
+        nodes: usize,
+        time_elapsed: Duration,
+        pv: Vec<Move>,
+        aspiration_re_searches: Vec<AspirationReSearch>,
+    ) -> Self {
+        Self {
+            depth,
+            score,
+            bound,
+            nodes,
+            time_elapsed,
+            pv,
+            aspiration_re_searches,
+        }
+    }
+
+    #[inline]
+    pub fn get_depth(&self) -> Depth {
+        self.depth
+    }
+
+    #[inline]
+    pub fn get_best_move(&self) -> Option<Move> {
+        self.pv.first().copied()
+    }
+
+    #[inline]
+    pub fn get_score(&self) -> Score {
+        self.score
+    }
+
+    /// [`EntryFlagHash::Beta`] if the score is a lower bound, [`EntryFlagHash::Alpha`] if it is an upper bound,
+    /// [`EntryFlagHash::Exact`] otherwise.
+    #[inline]
+    pub fn get_bound(&self) -> EntryFlagHash {
+        self.bound
+    }
+
+    /// Nodes searched since the start of the search till the end of this iteration.
+    #[inline]
+    pub fn get_num_nodes_searched(&self) -> usize {
+        self.nodes
+    }
+
+    /// Time elapsed since the start of the search till the end of this iteration.
+    #[inline]
+    pub fn get_time_elapsed(&self) -> Duration {
+        self.time_elapsed
+    }
+
+    #[inline]
+    pub fn get_pv(&self) -> &[Move] {
+        &self.pv
+    }
+
+    #[inline]
+    pub fn get_aspiration_re_searches(&self) -> &[AspirationReSearch] {
+        &self.aspiration_re_searches
+    }
+}
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Default)]
 pub struct SearchInfoBuilder {
@@ -371,6 +566,7 @@ pub struct SearchInfoBuilder {
     collisions: Option<usize>,
     time_elapsed: Option<Duration>,
     pv: Vec<Move>,
+    iterations: Vec<SearchIteration>,
 }
 
 impl SearchInfoBuilder {
@@ -437,6 +633,11 @@ impl SearchInfoBuilder {
         self
     }
 
+    pub fn set_iterations(mut self, iterations: Vec<SearchIteration>) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
     pub fn build(self) -> SearchInfo {
         SearchInfo {
             position: self.position,
@@ -450,6 +651,7 @@ impl SearchInfoBuilder {
             collisions: self.collisions,
             time_elapsed: self.time_elapsed,
             pv: self.pv,
+            iterations: self.iterations,
         }
     }
 }
@@ -468,6 +670,7 @@ pub struct SearchInfo {
     collisions: Option<usize>,
     time_elapsed: Option<Duration>,
     pv: Vec<Move>,
+    iterations: Vec<SearchIteration>,
 }
 
 impl SearchInfo {
@@ -496,6 +699,7 @@ impl SearchInfo {
             zero_hit,
             time_elapsed,
             pv,
+            iterations: Vec::new(),
         }
     }
 
@@ -504,6 +708,11 @@ impl SearchInfo {
         self.current_depth
     }
 
+    #[inline]
+    pub fn get_seldepth(&self) -> Option<Ply> {
+        self.seldepth
+    }
+
     #[inline]
     pub fn get_num_nodes_searched(&self) -> Option<usize> {
         self.nodes
@@ -554,6 +763,12 @@ impl SearchInfo {
         self.time_elapsed
     }
 
+    /// Iterative deepening history, one entry for every completed depth.
+    #[inline]
+    pub fn get_iterations(&self) -> &[SearchIteration] {
+        &self.iterations
+    }
+
     #[inline]
     fn format_info<T: fmt::Display>(desc: &str, info: Option<T>) -> Option<String> {
         let info = info?;
@@ -640,6 +855,7 @@ impl<P: PositionEvaluation> From<&Searcher<P>> for SearchInfo {
             zero_hit,
             time_elapsed: Some(searcher.get_time_elapsed()),
             pv: searcher.get_pv().into_iter().copied().collect_vec(),
+            iterations: searcher.get_iterations().to_vec(),
         };
         search_info.score = search_info
             .score
@@ -1,10 +1,113 @@
 use super::*;
-#[cfg(target_feature = "bmi2")]
+#[cfg(target_arch = "x86_64")]
 use std::arch::x86_64::{_pdep_u64, _pext_u64};
 pub use Square::*;
 
 include!(concat!(env!("OUT_DIR"), "/magic.rs"));
 
+type SliderMovesFunction = fn(Square, BitBoard) -> BitBoard;
+
+/// The lookups picked once from the CPU, unless the engine is compiled for BMI2 and PEXT is always used.
+#[cfg(all(target_arch = "x86_64", not(target_feature = "bmi2")))]
+static SLIDER_ATTACK_LOOKUPS: LazyLock<(
+    SliderAttackMethod,
+    SliderMovesFunction,
+    SliderMovesFunction,
+)> = LazyLock::new(|| {
+    let method = SliderAttackMethod::detect();
+    (
+        method,
+        method.get_bishop_moves_function(),
+        method.get_rook_moves_function(),
+    )
+});
+
+/// How the attacks of the bishops and the rooks are looked up.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum SliderAttackMethod {
+    /// Multiplication by magic numbers, which works on every CPU.
+    Magic,
+    /// The PEXT instruction of BMI2, which is faster on the CPUs supporting it.
+    Pext,
+}
+
+impl SliderAttackMethod {
+    /// Whether the CPU running the engine supports the method.
+    pub fn is_supported(self) -> bool {
+        match self {
+            Self::Magic => true,
+            #[cfg(target_arch = "x86_64")]
+            Self::Pext => std::is_x86_feature_detected!("bmi2"),
+            #[cfg(not(target_arch = "x86_64"))]
+            Self::Pext => false,
+        }
+    }
+
+    /// The fastest method supported by the CPU running the engine.
+    pub fn detect() -> Self {
+        if Self::Pext.is_supported() {
+            Self::Pext
+        } else {
+            Self::Magic
+        }
+    }
+
+    /// The method used by [`get_bishop_moves`] and [`get_rook_moves`].
+    #[inline]
+    pub fn get_selected() -> Self {
+        #[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+        return Self::Pext;
+        #[cfg(all(target_arch = "x86_64", not(target_feature = "bmi2")))]
+        return SLIDER_ATTACK_LOOKUPS.0;
+        #[cfg(not(target_arch = "x86_64"))]
+        return Self::Magic;
+    }
+
+    fn get_bishop_moves_function(self) -> SliderMovesFunction {
+        assert!(self.is_supported(), "{self} is not supported by the CPU");
+        match self {
+            Self::Magic => get_bishop_moves_non_bmi,
+            #[cfg(target_arch = "x86_64")]
+            // SAFETY: The CPU supports BMI2, checked above.
+            Self::Pext => |square, blockers| unsafe { get_bishop_moves_bmi(square, blockers) },
+            #[cfg(not(target_arch = "x86_64"))]
+            Self::Pext => unreachable!(),
+        }
+    }
+
+    fn get_rook_moves_function(self) -> SliderMovesFunction {
+        assert!(self.is_supported(), "{self} is not supported by the CPU");
+        match self {
+            Self::Magic => get_rook_moves_non_bmi,
+            #[cfg(target_arch = "x86_64")]
+            // SAFETY: The CPU supports BMI2, checked above.
+            Self::Pext => |square, blockers| unsafe { get_rook_moves_bmi(square, blockers) },
+            #[cfg(not(target_arch = "x86_64"))]
+            Self::Pext => unreachable!(),
+        }
+    }
+
+    /// Get the moves for a bishop using this method, panics if the CPU does not support it.
+    pub fn get_bishop_moves(self, square: Square, blockers: BitBoard) -> BitBoard {
+        self.get_bishop_moves_function()(square, blockers)
+    }
+
+    /// Get the moves for a rook using this method, panics if the CPU does not support it.
+    pub fn get_rook_moves(self, square: Square, blockers: BitBoard) -> BitBoard {
+        self.get_rook_moves_function()(square, blockers)
+    }
+}
+
+impl fmt::Display for SliderAttackMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Magic => write!(f, "Magic Bitboards"),
+            Self::Pext => write!(f, "PEXT"),
+        }
+    }
+}
+
 /// Get the moves for a bishop on a particular square, given blockers blocking my movement.
 fn get_bishop_moves_non_bmi(square: Square, blockers: BitBoard) -> BitBoard {
     let magic: Magic =
@@ -20,18 +123,20 @@ fn get_bishop_moves_non_bmi(square: Square, blockers: BitBoard) -> BitBoard {
 }
 
 /// Get the moves for a bishop on a particular square, given blockers blocking my movement.
-#[cfg(target_feature = "bmi2")]
-fn get_bishop_moves_bmi(square: Square, blockers: BitBoard) -> BitBoard {
+///
+/// # Safety
+///
+/// The CPU must support BMI2.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "bmi2")]
+unsafe fn get_bishop_moves_bmi(square: Square, blockers: BitBoard) -> BitBoard {
     let bmi2_magic = *get_item_unchecked!(const BISHOP_AND_ROOK_BMI_MASKS[0], square.to_index());
-    let index = unsafe { _pext_u64(blockers.into_inner(), bmi2_magic.blockers_mask.into_inner()) }
-        as usize
+    let index = _pext_u64(blockers.into_inner(), bmi2_magic.blockers_mask.into_inner()) as usize
         + bmi2_magic.offset;
-    let result = unsafe {
-        _pdep_u64(
-            *BMI_MOVES.get_unchecked(index) as u64,
-            square.get_bishop_rays_bb().into_inner(),
-        )
-    };
+    let result = _pdep_u64(
+        *BMI_MOVES.get_unchecked(index) as u64,
+        square.get_bishop_rays_bb().into_inner(),
+    );
     BitBoard::new(result)
 }
 
@@ -50,45 +155,45 @@ fn get_rook_moves_non_bmi(square: Square, blockers: BitBoard) -> BitBoard {
 }
 
 /// Get the moves for a rook on a particular square, given blockers blocking my movement.
-#[cfg(target_feature = "bmi2")]
-fn get_rook_moves_bmi(square: Square, blockers: BitBoard) -> BitBoard {
+///
+/// # Safety
+///
+/// The CPU must support BMI2.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "bmi2")]
+unsafe fn get_rook_moves_bmi(square: Square, blockers: BitBoard) -> BitBoard {
     let bmi2_magic = *get_item_unchecked!(const BISHOP_AND_ROOK_BMI_MASKS[1], square.to_index());
-    let index = unsafe { _pext_u64(blockers.into_inner(), bmi2_magic.blockers_mask.into_inner()) }
-        as usize
+    let index = _pext_u64(blockers.into_inner(), bmi2_magic.blockers_mask.into_inner()) as usize
         + bmi2_magic.offset;
-    let result = unsafe {
-        _pdep_u64(
-            *BMI_MOVES.get_unchecked(index) as u64,
-            square.get_rook_rays_bb().into_inner(),
-        )
-    };
+    let result = _pdep_u64(
+        *BMI_MOVES.get_unchecked(index) as u64,
+        square.get_rook_rays_bb().into_inner(),
+    );
     BitBoard::new(result)
 }
 
 /// Get the moves for a bishop on a particular square, given blockers blocking my movement.
 #[inline]
 pub fn get_bishop_moves(square: Square, blockers: BitBoard) -> BitBoard {
-    #[cfg(target_feature = "bmi2")]
-    {
-        get_bishop_moves_bmi(square, blockers)
-    }
-    #[cfg(not(target_feature = "bmi2"))]
-    {
-        get_bishop_moves_non_bmi(square, blockers)
-    }
+    #[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+    // SAFETY: The engine is compiled for CPUs supporting BMI2.
+    return unsafe { get_bishop_moves_bmi(square, blockers) };
+    #[cfg(all(target_arch = "x86_64", not(target_feature = "bmi2")))]
+    return SLIDER_ATTACK_LOOKUPS.1(square, blockers);
+    #[cfg(not(target_arch = "x86_64"))]
+    return get_bishop_moves_non_bmi(square, blockers);
 }
 
 /// Get the moves for a rook on a particular square, given blockers blocking my movement.
 #[inline]
 pub fn get_rook_moves(square: Square, blockers: BitBoard) -> BitBoard {
-    #[cfg(target_feature = "bmi2")]
-    {
-        get_rook_moves_bmi(square, blockers)
-    }
-    #[cfg(not(target_feature = "bmi2"))]
-    {
-        get_rook_moves_non_bmi(square, blockers)
-    }
+    #[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+    // SAFETY: The engine is compiled for CPUs supporting BMI2.
+    return unsafe { get_rook_moves_bmi(square, blockers) };
+    #[cfg(all(target_arch = "x86_64", not(target_feature = "bmi2")))]
+    return SLIDER_ATTACK_LOOKUPS.2(square, blockers);
+    #[cfg(not(target_arch = "x86_64"))]
+    return get_rook_moves_non_bmi(square, blockers);
 }
 
 #[inline]
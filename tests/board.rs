@@ -368,6 +368,124 @@ fn test_legal_capture_move_generator() {
     )
 }
 
+fn check_push_and_pop_restore_position(board: &mut Board, depth: Depth) {
+    if depth == 0 {
+        return;
+    }
+    let position = board.get_position().clone();
+    let fen = board.get_fen();
+    let hash = board.get_hash();
+    for move_ in board.generate_legal_moves() {
+        board.push_unchecked(move_);
+        assert_eq!(board.get_position(), &position.make_move_new(move_));
+        check_push_and_pop_restore_position(board, depth - 1);
+        assert_eq!(board.pop(), move_.into());
+        assert_eq!(board.get_position(), &position);
+        assert_eq!(board.get_fen(), fen);
+        assert_eq!(board.get_hash(), hash);
+        board.get_position().verify_hash().unwrap();
+        assert_eq!(
+            board.get_promoted_pieces_mask(),
+            position.get_promoted_pieces_mask()
+        );
+        assert_eq!(board.get_checkers(), position.get_checkers());
+        assert_eq!(board.pinned(), position.pinned());
+        assert_eq!(
+            board.get_white_material_score(),
+            position.get_white_material_score()
+        );
+        assert_eq!(
+            board.get_black_material_score(),
+            position.get_black_material_score()
+        );
+    }
+}
+
+#[test]
+fn test_board_push_and_pop_restore_position() {
+    for fen in [
+        STARTING_POSITION_FEN,
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    ] {
+        let mut board = Board::from_fen(fen).unwrap();
+        check_push_and_pop_restore_position(&mut board, 3);
+    }
+}
+
+#[test]
+fn test_board_push_and_pop_restore_variant_position() {
+    for (fen, variant) in [
+        (
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+            Variant::Standard,
+        ),
+        ("4k3/1Q~6/8/8/4b3/8/Kpp5/8/ b - - 0 1", Variant::Crazyhouse),
+        (
+            "rnb1kbnr/pppp1ppp/8/4p3/4P2q/5P2/PPPP2PP/RNBQKBNR w KQkq - 1 3 +1+2",
+            Variant::ThreeCheck,
+        ),
+        (
+            "rn2kb1r/1pp1p2p/p2q1pp1/3P4/2P3b1/4PN2/PP3PPP/R2QKB1R b KQkq - 0 1",
+            Variant::Atomic,
+        ),
+        (
+            "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1",
+            Variant::Horde,
+        ),
+    ] {
+        let mut board = Board::from_fen_and_variant(fen, variant).unwrap();
+        check_push_and_pop_restore_position(&mut board, 3);
+    }
+}
+
+#[test]
+fn test_board_variant_given_explicitly() {
+    assert_eq!(Board::new().get_variant(), Variant::Standard);
+    assert_eq!(
+        Board::from_fen(STARTING_POSITION_FEN)
+            .unwrap()
+            .get_variant(),
+        Variant::Standard
+    );
+    let mut board = Board::new_variant(Variant::KingOfTheHill);
+    assert_eq!(board.get_variant(), Variant::KingOfTheHill);
+    board.push_sans("e4 e5").unwrap();
+    board.set_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(board.get_variant(), Variant::KingOfTheHill);
+    board.reset();
+    assert_eq!(board.get_variant(), Variant::KingOfTheHill);
+    assert_eq!(board.get_fen(), Variant::KingOfTheHill.get_starting_fen());
+    board
+        .set_fen_and_variant(STARTING_POSITION_FEN, Variant::Standard)
+        .unwrap();
+    assert_eq!(board.get_variant(), Variant::Standard);
+    let mut engine = Engine::default();
+    engine
+        .set_position(Board::new_variant(Variant::ThreeCheck))
+        .unwrap();
+    assert_eq!(engine.get_board().get_variant(), Variant::ThreeCheck);
+}
+
+#[test]
+fn test_board_stack_after_null_move() {
+    let mut board = Board::default();
+    board.push_sans("e4 e5 Nf3").unwrap();
+    board.push(ValidOrNullMove::NullMove).unwrap();
+    board.push_san("Nc3").unwrap();
+    assert!(board.stack_contains_null_move());
+    assert_eq!(board.get_starting_board_fen(), STARTING_POSITION_FEN);
+    assert_eq!(board.get_num_moves(), 5);
+    board.pop();
+    board.pop();
+    assert_eq!(
+        board.get_fen(),
+        "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2"
+    );
+    assert!(!board.stack_contains_null_move());
+}
+
 // is_capture
 // is_quiet
 // is_zeroing
@@ -0,0 +1,56 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use timecat::*;
+
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_cache_table_resize_frees_replaced_slots() {
+    let transposition_table = TranspositionTable::new(CacheTableSize::Exact(1));
+    transposition_table
+        .set_size(CacheTableSize::Exact(4))
+        .unwrap();
+    transposition_table
+        .set_size(CacheTableSize::Exact(1))
+        .unwrap();
+    let allocated_bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for key in 1..20_000 {
+                    transposition_table.write(key, 5, 0, 10, EntryFlagHash::Exact, None);
+                    transposition_table.read(key, 5, 0);
+                }
+            });
+        }
+        for _ in 0..50 {
+            transposition_table
+                .set_size(CacheTableSize::Exact(4))
+                .unwrap();
+            transposition_table
+                .set_size(CacheTableSize::Exact(1))
+                .unwrap();
+        }
+    });
+    assert_eq!(transposition_table.get_size(), CacheTableSize::Exact(1));
+    assert!(transposition_table.get_memory_usage() <= 2_usize.pow(20));
+    // Leaking the replaced slots would keep around 250 MB allocated.
+    assert!(ALLOCATED_BYTES.load(Ordering::Relaxed) < allocated_bytes + 2_usize.pow(20));
+}
@@ -0,0 +1,145 @@
+use timecat::*;
+
+fn run_cluster_search(addresses: Vec<ClusterAddress>) {
+    let workers = addresses
+        .iter()
+        .map(|address| ClusterWorker::bind(address).unwrap())
+        .collect_vec();
+    let coordinator = ClusterCoordinator::new(
+        workers
+            .iter()
+            .map(|worker| worker.get_local_address().unwrap())
+            .collect(),
+    );
+    let join_handles = workers
+        .into_iter()
+        .map(|worker| std::thread::spawn(move || worker.serve(&mut Engine::default()).unwrap()))
+        .collect_vec();
+    let board = Board::from_fen("6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1").unwrap();
+    let search_info = coordinator.go(&board, &SearchConfig::new_depth(3)).unwrap();
+    assert_eq!(
+        search_info.get_best_move(),
+        Some(Move::from_str("d1d8").unwrap())
+    );
+    assert!(search_info.get_num_nodes_searched().unwrap() > 0);
+    coordinator.quit_workers().unwrap();
+    for join_handle in join_handles {
+        join_handle.join().unwrap();
+    }
+}
+
+#[test]
+fn test_cluster_search_over_tcp() {
+    run_cluster_search(vec!["tcp:127.0.0.1:0".parse().unwrap(); 2]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_cluster_search_over_unix_sockets() {
+    let directory = std::env::temp_dir();
+    let addresses = (0..2)
+        .map(|index| {
+            let path = directory.join(format!(
+                "timecat_cluster_test_{}_{index}.sock",
+                std::process::id()
+            ));
+            ClusterAddress::from_str(&format!("unix:{}", path.display())).unwrap()
+        })
+        .collect_vec();
+    run_cluster_search(addresses);
+}
+
+#[test]
+fn test_cluster_messages() {
+    let mut board = Board::default();
+    board.push_uci("e2e4").unwrap();
+    let config = SearchConfig::from_str("go depth 5 movetime 1000 searchmoves e7e5 c7c5").unwrap();
+    let request = ClusterRequest::from_board(&board, config.clone());
+    assert_eq!(
+        ClusterRequest::from_str(&request.to_string()).unwrap(),
+        request
+    );
+    let board = Board::new_variant(Variant::Crazyhouse);
+    let request = ClusterRequest::from_board(&board, config);
+    let ClusterRequest::Search {
+        variant,
+        chess960,
+        starting_fen,
+        moves,
+        ..
+    } = ClusterRequest::from_str(&request.to_string()).unwrap()
+    else {
+        panic!("Expected a search request");
+    };
+    assert_eq!(variant, Variant::Crazyhouse);
+    assert!(!chess960);
+    assert_eq!(
+        ClusterRequest::generate_board(variant, chess960, &starting_fen, &moves)
+            .unwrap()
+            .get_variant(),
+        Variant::Crazyhouse
+    );
+    assert!(ClusterAddress::from_str("udp:127.0.0.1:7000").is_err());
+    assert_eq!(
+        ClusterAddress::from_str("tcp:[::1]:7000").unwrap(),
+        ClusterAddress::Tcp("[::1]:7000".parse().unwrap())
+    );
+}
+
+#[test]
+fn test_cluster_command_keeps_raw_addresses() {
+    assert_eq!(
+        Parser::parse_command("cluster quit tcp:[::1]:7000,tcp:127.0.0.1:7001").unwrap(),
+        vec![UserCommand::ClusterQuit(vec![
+            "tcp:[::1]:7000".parse().unwrap(),
+            "tcp:127.0.0.1:7001".parse().unwrap(),
+        ])]
+    );
+    assert_eq!(
+        Parser::parse_command("cluster go tcp:[::1]:7000 depth 3").unwrap(),
+        vec![UserCommand::ClusterGo {
+            workers: vec!["tcp:[::1]:7000".parse().unwrap()],
+            config: SearchConfig::new_depth(3),
+        }]
+    );
+    assert!(Parser::parse_command("cluster quit tcp:[::1]").is_err());
+}
+
+#[test]
+fn test_cluster_search_with_worker_process() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::process::{Command, Stdio};
+
+    let mut worker_process = Command::new(env!("CARGO_BIN_EXE_timecat"))
+        .arg("--disable-backtrace")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = worker_process.stdin.take().unwrap();
+    writeln!(stdin, "cluster worker tcp:127.0.0.1:0").unwrap();
+    let mut lines = BufReader::new(worker_process.stdout.take().unwrap()).lines();
+    let address = lines
+        .by_ref()
+        .map(|line| line.unwrap())
+        .find_map(|line| {
+            line.split_whitespace()
+                .find(|word| word.starts_with("tcp:"))
+                .map(|word| word.parse::<ClusterAddress>().unwrap())
+        })
+        .unwrap();
+    let coordinator = ClusterCoordinator::new(vec![address]);
+    // Only checking the king wins at once in Three-check, so the worker has to search the right variant.
+    let board =
+        Board::from_fen_and_variant("4k3/8/8/8/8/8/8/R3K3 w - - 1+3 0 1", Variant::ThreeCheck)
+            .unwrap();
+    let search_info = coordinator.go(&board, &SearchConfig::new_depth(2)).unwrap();
+    assert_eq!(
+        search_info.get_best_move(),
+        Some(Move::from_str("a1a8").unwrap())
+    );
+    coordinator.quit_workers().unwrap();
+    drop(stdin);
+    lines.for_each(drop);
+    assert!(worker_process.wait().unwrap().success());
+}
@@ -5,3 +5,518 @@ fn test_go_infinite() {
     let mut engine = Engine::from_fen("8/3R4/p5kp/P1p3p1/6P1/8/6P1/Q6K w - - 0 47").unwrap();
     let _ = engine.go_verbose(&SearchConfig::new_infinite());
 }
+
+#[test]
+fn test_search_statistics() {
+    let mut engine = Engine::from_fen(STARTING_POSITION_FEN).unwrap();
+    engine.set_opening_book::<PolyglotBookHashMap>(None);
+    assert!(engine.get_search_statistics().is_none());
+    engine.set_collecting_search_statistics(true);
+    let _ = engine.go_quiet(&SearchConfig::new_depth(6));
+    let statistics = engine.get_search_statistics().unwrap();
+    assert!(statistics.get_num_nodes() > 0);
+    assert_eq!(statistics.get_nodes_per_iteration().len(), 6);
+    assert!(statistics.get_num_beta_cutoffs() >= statistics.get_num_first_move_beta_cutoffs());
+    assert!(statistics.get_num_tt_probes() >= statistics.get_num_tt_hits());
+    assert!(statistics.get_num_tt_hits() >= statistics.get_num_tt_usable_hits());
+    let json: serde_json::Value = serde_json::from_str(&statistics.to_json()).unwrap();
+    assert_eq!(json["nodes"], statistics.get_num_nodes());
+    assert_eq!(
+        statistics.get_beta_cutoff_rate(),
+        Some(statistics.get_num_beta_cutoffs() as f64 / statistics.get_num_nodes() as f64)
+    );
+    assert!(json["beta_cutoff_rate"].is_number());
+    assert_eq!(
+        Parser::parse_command("stats json").unwrap(),
+        vec![UserCommand::PrintSearchStatistics { json: true }]
+    );
+    let _ = engine.go_quiet(&SearchConfig::new_nodes(20_000));
+    let statistics = engine.get_search_statistics().unwrap();
+    // The iteration stopped by the node limit is not counted as completed.
+    assert!(
+        statistics.get_nodes_per_iteration().iter().sum::<usize>() < statistics.get_num_nodes()
+    );
+}
+
+#[test]
+fn test_search_tree_dump() {
+    let mut engine = Engine::from_fen(STARTING_POSITION_FEN).unwrap();
+    engine.set_opening_book::<PolyglotBookHashMap>(None);
+    let moves_filter = vec![Move::from_str("e2e4").unwrap()];
+    engine.set_search_tree_config(Some(
+        SearchTreeConfig::default()
+            .set_max_ply(2)
+            .set_max_nodes(500)
+            .set_moves_filter(moves_filter.clone()),
+    ));
+    let _ = engine.go_quiet(&SearchConfig::new_depth(4));
+    let search_tree = engine.get_search_tree().unwrap();
+    assert!(!search_tree.is_empty() && search_tree.len() <= 500);
+    let nodes = search_tree.get_nodes();
+    assert!(nodes[0].get_parent().is_none() && nodes[0].get_move().is_none());
+    for node in nodes {
+        assert!(node.get_ply() <= 2);
+        if node.get_ply() == 1 {
+            assert_eq!(node.get_move(), Some(moves_filter[0].into()));
+        }
+    }
+    assert!(search_tree.to_dot().starts_with("digraph"));
+    let json: serde_json::Value = serde_json::from_str(&search_tree.to_json()).unwrap();
+    assert_eq!(json["nodes"].as_array().unwrap().len(), search_tree.len());
+    assert_eq!(
+        Parser::parse_command("searchtree C:\\trees\\a,b.json depth 4 maxply 2 moves e2e4")
+            .unwrap(),
+        vec![UserCommand::DumpSearchTree {
+            depth: 4,
+            path: "C:\\trees\\a,b.json".to_string(),
+            config: SearchTreeConfig::default()
+                .set_max_ply(2)
+                .set_moves_filter(moves_filter),
+        }]
+    );
+}
+
+#[test]
+fn test_search_state_kept_across_continued_positions() {
+    let mut engine = Engine::default();
+    engine.set_opening_book::<PolyglotBookHashMap>(None);
+    let mut board = Board::default();
+    board.push_uci_moves("e2e4 e7e5").unwrap();
+    engine.set_position(board.clone()).unwrap();
+    let _ = engine.go_quiet(&SearchConfig::new_depth(5));
+    let num_cells_filled = engine.get_transposition_table().get_num_cells_filled();
+    assert!(num_cells_filled > 0);
+    board.push_uci_moves("g1f3 b8c6").unwrap();
+    engine.set_position(board.clone()).unwrap();
+    assert_eq!(
+        engine.get_transposition_table().get_num_cells_filled(),
+        num_cells_filled
+    );
+    assert_eq!(engine.get_board().get_all_stack_moves().len(), 4);
+    let _ = engine.go_quiet(&SearchConfig::new_depth(5));
+    assert!(engine.get_transposition_table().get_num_cells_filled() >= num_cells_filled);
+    engine.set_position(Board::default()).unwrap();
+    assert_eq!(engine.get_transposition_table().get_num_cells_filled(), 0);
+}
+
+#[test]
+fn test_fixed_depth_search() {
+    for (fen, best_move) in [
+        (
+            "r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4",
+            "h5f7",
+        ),
+        (
+            "rnb1k1nr/pppp1ppp/8/2b1p3/4P2q/2N2N2/PPPP1PPP/R1BQKB1R b KQkq - 4 4",
+            "h4f2",
+        ),
+        (
+            "rnb1kbnr/pppp1ppp/8/4p3/4P2q/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+            "f3h4",
+        ),
+        ("6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1", "d1d8"),
+    ] {
+        let [first_num_nodes, second_num_nodes] = [(); 2].map(|_| {
+            let mut engine = Engine::from_fen(fen).unwrap();
+            engine.set_opening_book::<PolyglotBookHashMap>(None);
+            engine.set_num_threads(NonZeroUsize::MIN);
+            let search_info = engine.go_quiet(&SearchConfig::new_depth(5));
+            assert_eq!(
+                search_info.get_best_move(),
+                Some(Move::from_str(best_move).unwrap())
+            );
+            search_info.get_num_nodes_searched().unwrap()
+        });
+        assert_eq!(first_num_nodes, second_num_nodes);
+    }
+}
+
+#[test]
+fn test_search_iterations() {
+    let mut engine =
+        Engine::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    engine.set_opening_book::<PolyglotBookHashMap>(None);
+    let search_info = engine.go_quiet(&SearchConfig::new_depth(6));
+    let iterations = search_info.get_iterations();
+    assert_eq!(
+        iterations
+            .iter()
+            .map(|iteration| iteration.get_depth())
+            .collect_vec(),
+        (1..=6).collect_vec()
+    );
+    for (previous, next) in iterations.iter().tuple_windows() {
+        assert!(previous.get_num_nodes_searched() <= next.get_num_nodes_searched());
+        assert!(previous.get_time_elapsed() <= next.get_time_elapsed());
+    }
+    for iteration in iterations {
+        assert_eq!(iteration.get_bound(), EntryFlagHash::Exact);
+        assert_eq!(
+            iteration.get_best_move(),
+            iteration.get_pv().first().copied()
+        );
+        for re_search in iteration.get_aspiration_re_searches() {
+            let (alpha, beta) = re_search.get_window();
+            assert!(re_search.get_score() <= alpha || re_search.get_score() >= beta);
+        }
+    }
+    let last_iteration = iterations.last().unwrap();
+    assert_eq!(last_iteration.get_best_move(), search_info.get_best_move());
+    assert_eq!(Some(last_iteration.get_score()), search_info.get_score());
+}
+
+#[derive(Clone, Default)]
+struct StopOnAspirationFailure(SearchController);
+
+impl<P: PositionEvaluation> SearchControl<Searcher<P>> for StopOnAspirationFailure {
+    fn get_move_overhead(&self) -> Duration {
+        SearchControl::<Searcher<P>>::get_move_overhead(&self.0)
+    }
+
+    fn set_move_overhead(&mut self, duration: Duration) {
+        SearchControl::<Searcher<P>>::set_move_overhead(&mut self.0, duration)
+    }
+
+    fn reset_variables(&mut self) {
+        SearchControl::<Searcher<P>>::reset_variables(&mut self.0)
+    }
+
+    fn stop_search_at_root_node(&mut self, searcher: &mut Searcher<P>) -> bool {
+        searcher.is_outside_aspiration_window() || self.0.stop_search_at_root_node(searcher)
+    }
+
+    fn stop_search_at_every_node(&mut self, searcher: &mut Searcher<P>) -> bool {
+        self.0.stop_search_at_every_node(searcher)
+    }
+
+    fn on_receiving_search_config(&mut self, config: &SearchConfig, searcher: &mut Searcher<P>) {
+        self.0.on_receiving_search_config(config, searcher)
+    }
+
+    fn on_each_search_completion(&mut self, searcher: &mut Searcher<P>) {
+        self.0.on_each_search_completion(searcher)
+    }
+}
+
+#[test]
+fn test_search_iteration_aspiration_bounds() {
+    for (fen, bound) in [
+        (
+            "r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4",
+            EntryFlagHash::Beta,
+        ),
+        (
+            "rnb1k1nr/pppp1ppp/8/2b1p3/4P2q/2N2N2/PPPP1PPP/R1BQKB1R b KQkq - 4 4",
+            EntryFlagHash::Alpha,
+        ),
+        (STARTING_POSITION_FEN, EntryFlagHash::Alpha),
+    ] {
+        let mut engine = CustomEngine::new(
+            Board::from_fen(fen).unwrap(),
+            TranspositionTable::default(),
+            StopOnAspirationFailure::default(),
+            Evaluator::default(),
+        );
+        engine.set_opening_book::<PolyglotBookHashMap>(None);
+        engine.set_num_threads(NonZeroUsize::MIN);
+        let search_info = engine.go_quiet(&SearchConfig::new_depth(10));
+        let (last_iteration, previous_iterations) =
+            search_info.get_iterations().split_last().unwrap();
+        assert!(previous_iterations
+            .iter()
+            .all(|iteration| iteration.get_bound() == EntryFlagHash::Exact));
+        assert_eq!(last_iteration.get_bound(), bound);
+        let previous_score = previous_iterations.last().unwrap().get_score();
+        match bound {
+            EntryFlagHash::Beta => assert!(last_iteration.get_score() > previous_score),
+            _ => assert!(last_iteration.get_score() < previous_score),
+        }
+    }
+}
+
+#[test]
+fn test_experience_learning() {
+    let path = std::env::temp_dir().join(format!("timecat_experience_{}.exp", std::process::id()));
+    let path_string = path.display().to_string();
+    let mut engine = Engine::default();
+    engine.set_opening_book::<PolyglotBookHashMap>(None);
+    engine.set_experience_file(&path_string).unwrap();
+    engine.set_using_experience(true).unwrap();
+    let search_info = engine.go_quiet(&SearchConfig::new_depth(EXPERIENCE_MIN_DEPTH));
+    let hash = engine.get_board().get_hash();
+    let entry = Experience::load(&path).unwrap().get(hash).unwrap();
+    assert_eq!(Some(entry.get_best_move()), search_info.get_best_move());
+    assert_eq!(entry.get_depth(), EXPERIENCE_MIN_DEPTH);
+    engine.on_game_end(GameResult::Win(White));
+    let experience = Experience::load(&path).unwrap();
+    assert_eq!(
+        experience.get(hash).unwrap().get_score(),
+        entry.get_score() + EXPERIENCE_RESULT_ADJUSTMENT
+    );
+    let mut engine = Engine::default();
+    engine.set_opening_book::<PolyglotBookHashMap>(None);
+    engine.set_experience_file(&path_string).unwrap();
+    engine.set_using_experience(true).unwrap();
+    let _ = engine.go_quiet(&SearchConfig::new_depth(1));
+    assert_eq!(
+        engine.get_transposition_table().read_best_move(hash),
+        Some(entry.get_best_move())
+    );
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_experience_shared_by_concurrent_engines() {
+    let path = std::env::temp_dir().join(format!(
+        "timecat_experience_concurrent_{}.exp",
+        std::process::id()
+    ));
+    let path_string = path.display().to_string();
+    let fens = [
+        STARTING_POSITION_FEN,
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1",
+        "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2",
+        "rnbqkbnr/pppppppp/8/8/3P4/8/PPP1PPPP/RNBQKBNR b KQkq - 0 1",
+    ];
+    let join_handles = fens.map(|fen| {
+        let path_string = path_string.clone();
+        std::thread::spawn(move || {
+            let mut engine = Engine::from_fen(fen).unwrap();
+            engine.set_opening_book::<PolyglotBookHashMap>(None);
+            engine.set_experience_file(&path_string).unwrap();
+            engine.set_using_experience(true).unwrap();
+            engine.go_quiet(&SearchConfig::new_depth(EXPERIENCE_MIN_DEPTH));
+            engine.get_board().get_hash()
+        })
+    });
+    let hashes = join_handles.map(|join_handle| join_handle.join().unwrap());
+    let experience = Experience::load(&path).unwrap();
+    assert_eq!(experience.len(), fens.len());
+    assert!(hashes.iter().all(|&hash| experience.get(hash).is_some()));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_game_end_reported_once() {
+    let path = std::env::temp_dir().join(format!(
+        "timecat_experience_game_end_{}.exp",
+        std::process::id()
+    ));
+    let path_string = path.display().to_string();
+    let mut engine = Engine::default();
+    engine.set_opening_book::<PolyglotBookHashMap>(None);
+    engine.set_experience_file(&path_string).unwrap();
+    engine.set_using_experience(true).unwrap();
+    let _ = engine.go_quiet(&SearchConfig::new_depth(EXPERIENCE_MIN_DEPTH));
+    let hash = engine.get_board().get_hash();
+    let score = Experience::load(&path)
+        .unwrap()
+        .get(hash)
+        .unwrap()
+        .get_score();
+    let get_stored_score = || {
+        Experience::load(&path)
+            .unwrap()
+            .get(hash)
+            .unwrap()
+            .get_score()
+    };
+    let mut board = Board::default();
+    board.push_uci_moves("f2f3 e7e5 g2g4").unwrap();
+    engine.set_position(board.clone()).unwrap();
+    assert_eq!(get_stored_score(), score);
+    board.push_uci_moves("d8h4").unwrap();
+    engine.set_position(board.clone()).unwrap();
+    assert_eq!(get_stored_score(), score - EXPERIENCE_RESULT_ADJUSTMENT);
+    engine.set_position(board).unwrap();
+    let _ = engine.go_quiet(&SearchConfig::new_depth(1));
+    engine.on_game_end(GameResult::Win(Black));
+    assert_eq!(get_stored_score(), score - EXPERIENCE_RESULT_ADJUSTMENT);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_engine_pool_shares_transposition_table() {
+    let pool = EnginePool::<Evaluator>::new(MemoryManager::new(9), Evaluator::default()).unwrap();
+    assert_eq!(
+        pool.get_transposition_table().get_size(),
+        CacheTableSize::Exact(4)
+    );
+    assert!(pool.get_memory_usage() <= 9 << 20);
+    let mut engine_1: Engine = pool.create_engine(Board::default());
+    let mut engine_2: Engine = pool.create_engine(Board::default());
+    engine_1.set_opening_book::<PolyglotBookHashMap>(None);
+    engine_2.set_opening_book::<PolyglotBookHashMap>(None);
+    assert!(std::ptr::eq(
+        engine_1.get_transposition_table(),
+        engine_2.get_transposition_table()
+    ));
+    assert!(engine_1.is_sharing_transposition_table());
+    let generation = pool.get_transposition_table().get_generation();
+    let search_info = engine_1.go_quiet(&SearchConfig::new_depth(5));
+    assert_eq!(pool.get_transposition_table().get_generation(), generation);
+    let hash = engine_1.get_board().get_hash();
+    assert_eq!(
+        engine_2.get_transposition_table().read_best_move(hash),
+        search_info.get_best_move()
+    );
+    let num_cells_filled = pool.get_transposition_table().get_num_cells_filled();
+    engine_2
+        .set_position(Board::from_fen("8/8/8/4k3/8/8/3QK3/8 w - - 0 1").unwrap())
+        .unwrap();
+    assert_eq!(
+        pool.get_transposition_table().get_num_cells_filled(),
+        num_cells_filled
+    );
+    engine_1.clear_hash();
+    assert_eq!(
+        pool.get_transposition_table().get_num_cells_filled(),
+        num_cells_filled
+    );
+    assert_eq!(
+        engine_1.set_transposition_table_size(CacheTableSize::Exact(8)),
+        Err(TimecatError::SharedTablesNotModifiable)
+    );
+    assert_eq!(
+        MemoryManager::new(18).apply(&engine_1),
+        Err(TimecatError::SharedTablesNotModifiable)
+    );
+    pool.set_memory_budget(MemoryManager::new(18)).unwrap();
+    assert_eq!(pool.get_memory_budget(), MemoryManager::new(18));
+    assert_eq!(
+        engine_1.get_transposition_table().get_size(),
+        CacheTableSize::Exact(8)
+    );
+    pool.new_game();
+    assert_eq!(
+        pool.get_transposition_table().get_generation(),
+        generation.wrapping_add(1)
+    );
+    drop(engine_2);
+    assert!(engine_1.is_sharing_transposition_table());
+    drop(pool);
+    assert!(engine_1.is_sharing_transposition_table());
+    let engine_1_clone = engine_1.clone();
+    assert!(!engine_1_clone.is_sharing_transposition_table());
+    assert!(!std::ptr::eq(
+        engine_1.get_transposition_table(),
+        engine_1_clone.get_transposition_table()
+    ));
+    let mut engine_3 = Engine::default();
+    assert!(!engine_3.is_sharing_transposition_table());
+    let engine_4 = engine_3.share_transposition_table();
+    assert!(engine_3.is_sharing_transposition_table());
+    assert!(engine_4.is_sharing_transposition_table());
+    assert!(std::ptr::eq(
+        engine_3.get_transposition_table(),
+        engine_4.get_transposition_table()
+    ));
+}
+
+#[test]
+fn test_hash_file_paths_kept_raw() {
+    assert_eq!(
+        Parser::parse_command("save_hash C:\\hashes\\a,b.hash").unwrap(),
+        vec![UserCommand::SaveHash("C:\\hashes\\a,b.hash".to_string())]
+    );
+    assert_eq!(
+        Parser::parse_command("load_hash  /tmp/my hashes/a:b.hash && isready").unwrap(),
+        vec![
+            UserCommand::LoadHash("/tmp/my hashes/a:b.hash".to_string()),
+            UserCommand::IsReady
+        ]
+    );
+    assert!(Parser::parse_command("load_hash ").is_err());
+}
+
+#[test]
+fn test_memory_manager_splits_budget() {
+    let memory_manager = MemoryManager::new(90).set_ratios(6, 2, 1);
+    assert_eq!(
+        memory_manager.get_transposition_table_size(),
+        CacheTableSize::Exact(60)
+    );
+    assert_eq!(
+        memory_manager.get_evaluation_cache_size(),
+        CacheTableSize::Exact(20)
+    );
+    assert_eq!(
+        memory_manager.get_pawn_hash_size(),
+        CacheTableSize::Exact(10)
+    );
+    let engine = Engine::default();
+    MemoryManager::new(18).apply(&engine).unwrap();
+    assert_eq!(
+        engine.get_transposition_table().get_size(),
+        CacheTableSize::Exact(8)
+    );
+    let memory_usage = engine.get_memory_usage();
+    assert!(memory_usage <= 18 << 20);
+    assert_eq!(
+        MemoryManager::new(usize::MAX >> 20)
+            .set_ratios(1, usize::MAX >> 20, 0)
+            .apply(&engine),
+        Err(TimecatError::MemoryAllocationFailed {
+            size: CacheTableSize::Exact(usize::MAX >> 20)
+        })
+    );
+    assert_eq!(engine.get_memory_usage(), memory_usage);
+    assert_eq!(
+        engine.get_transposition_table().get_size(),
+        CacheTableSize::Exact(8)
+    );
+    let evaluator = EvaluatorNonNNUE::default();
+    evaluator
+        .set_cache_sizes(CacheTableSize::Exact(2), CacheTableSize::Exact(1))
+        .unwrap();
+    let memory_usage = evaluator.get_memory_usage();
+    assert!(evaluator
+        .set_cache_sizes(
+            CacheTableSize::Exact(0),
+            CacheTableSize::Exact(usize::MAX >> 20)
+        )
+        .is_err());
+    assert_eq!(evaluator.get_memory_usage(), memory_usage);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_resume_from_checkpoint() {
+    let path = std::env::temp_dir().join(format!("timecat_checkpoint_{}.json", std::process::id()));
+    let path_string = path.display().to_string();
+    let mut engine =
+        Engine::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+            .unwrap();
+    engine.set_checkpoint_file(Some(&path_string)).unwrap();
+    engine.set_checkpoint_interval(Duration::ZERO);
+    let search_info = engine.go_quiet(&SearchConfig::new_depth(5));
+    let mut resumed_engine = Engine::default();
+    resumed_engine.resume_from_checkpoint(&path_string).unwrap();
+    assert_eq!(
+        resumed_engine.get_board().get_fen(),
+        engine.get_board().get_fen()
+    );
+    let resumed_search_info = resumed_engine.go_quiet(&SearchConfig::new_depth(7));
+    let iterations = resumed_search_info.get_iterations();
+    assert_eq!(
+        iterations
+            .iter()
+            .map(|iteration| iteration.get_depth())
+            .collect_vec(),
+        (1..=7).collect_vec()
+    );
+    assert_eq!(&iterations[..5], search_info.get_iterations());
+    assert!(iterations[5].get_num_nodes_searched() > iterations[4].get_num_nodes_searched());
+    std::fs::remove_file(Checkpoint::get_hash_file_path(&path)).unwrap();
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+#[cfg(not(feature = "serde"))]
+fn test_checkpoint_requires_serde() {
+    let mut engine = Engine::default();
+    assert!(engine
+        .set_checkpoint_file(Some("timecat.checkpoint"))
+        .is_err());
+    assert!(engine.resume_from_checkpoint("timecat.checkpoint").is_err());
+}
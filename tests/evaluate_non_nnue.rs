@@ -0,0 +1,76 @@
+use timecat::*;
+
+const FENS: [&str; 4] = [
+    STARTING_POSITION_FEN,
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "8/1P4p1/2P5/3k1PP1/8/8/p2K3p/8 w - - 0 1",
+];
+
+#[test]
+fn test_pawn_hash_table() {
+    let pawn_hash_table = PawnHashTable::new(CacheTableSize::Exact(1));
+    for fen in FENS {
+        let position = BoardPosition::from_fen(fen).unwrap();
+        let pawn_structure = PawnStructure::new(&position);
+        for color in [White, Black] {
+            let passed_pawns = position
+                .get_colored_piece_mask(Pawn, color)
+                .filter(|&square| position.is_passed_pawn(square))
+                .fold(BitBoard::EMPTY, |mask, square| mask ^ square.to_bitboard());
+            assert_eq!(pawn_structure.get_passed_pawns(color), passed_pawns);
+        }
+        assert_eq!(pawn_hash_table.probe(&position), pawn_structure);
+        assert_eq!(pawn_hash_table.probe(&position), pawn_structure);
+    }
+    assert_eq!(pawn_hash_table.get_num_cells_filled(), FENS.len());
+}
+
+#[test]
+fn test_evaluation_cache_shared_between_clones() {
+    let position = BoardPosition::from_fen(FENS[1]).unwrap();
+    let mut evaluator = EvaluatorNonNNUE::default();
+    evaluator.set_using_pawn_structure(true);
+    let mut cloned_evaluator = evaluator.clone();
+    let score = evaluator.evaluate(&position);
+    assert_eq!(
+        cloned_evaluator
+            .get_pawn_hash_table()
+            .get_num_cells_filled(),
+        1
+    );
+    assert_eq!(cloned_evaluator.evaluate(&position), score);
+    evaluator.clear();
+    assert!(cloned_evaluator.get_pawn_hash_table().is_empty());
+}
+
+#[test]
+fn test_pawn_structure_evaluation_is_opt_in() {
+    let mut evaluator = EvaluatorNonNNUE::default();
+    assert!(!evaluator.is_using_pawn_structure());
+    for fen in FENS {
+        let position = BoardPosition::from_fen(fen).unwrap();
+        let alpha = position.get_material_score_abs() as f64 / INITIAL_MATERIAL_SCORE_ABS as f64;
+        let psqt_score: Score = position
+            .iter()
+            .map(|(piece, square)| {
+                let psqt_score = get_psqt_score(piece, square, alpha);
+                if piece.get_color() == White {
+                    psqt_score
+                } else {
+                    -psqt_score
+                }
+            })
+            .sum();
+        let score = evaluator.evaluate(&position);
+        assert_eq!(score, position.get_material_score() + psqt_score);
+        assert_eq!(EvaluatorNonNNUE::slow_evaluate(&position), score);
+        evaluator.set_using_pawn_structure(true);
+        assert_eq!(
+            evaluator.evaluate(&position),
+            score + PawnStructure::new(&position).get_score()
+        );
+        evaluator.set_using_pawn_structure(false);
+    }
+    assert!(evaluator.get_pawn_hash_table().get_num_cells_filled() > 0);
+}
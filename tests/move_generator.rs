@@ -191,6 +191,212 @@ generate_move_generator_functions!(
     104644508,
 );
 
+generate_move_generator_functions!(
+    move_generator_perft_chess960_1,
+    "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+    5,
+    8146062,
+);
+generate_move_generator_functions!(
+    move_generator_perft_chess960_2,
+    "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+    5,
+    16253601,
+);
+generate_move_generator_functions!(
+    move_generator_perft_chess960_3,
+    "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+    5,
+    6417013,
+);
+generate_move_generator_functions!(
+    move_generator_perft_chess960_4,
+    "qbbnnrkr/2pp2pp/p7/1p2pp2/8/P3PP2/1PPP1KPP/QBBNNR1R w hf - 0 9",
+    5,
+    9183776,
+);
+generate_move_generator_functions!(
+    move_generator_perft_chess960_5,
+    "1nbbnrkr/p1p1ppp1/3p4/1p3P1p/3Pq2P/8/PPP1P1P1/QNBBNRKR w HFhf - 0 9",
+    5,
+    34030312,
+);
+generate_move_generator_functions!(
+    move_generator_perft_chess960_6,
+    "qnbnr1kr/ppp1b1pp/4p3/3p1p2/8/2NPP3/PPP1BPPP/QNB1R1KR w HEhe - 1 9",
+    5,
+    24851983,
+);
+
+generate_move_generator_functions!(
+    move_generator_perft_crazyhouse_1,
+    "2k5/8/8/8/8/8/8/4K3[QRBNPqrbnp] w - - 0 1",
+    2,
+    75353,
+);
+generate_move_generator_functions!(
+    move_generator_perft_crazyhouse_2,
+    "4k3/1Q~6/8/8/4b3/8/Kpp5/8/ b - - 0 1",
+    4,
+    132758,
+);
+generate_move_generator_functions!(
+    move_generator_perft_crazyhouse_3,
+    "r1b1k2r/ppp2ppp/2n5/3np3/1b1P4/2N2N2/PPP2PPP/R1BQKB1R[PPqp] w - - 0 8",
+    3,
+    336707,
+);
+generate_move_generator_functions!(
+    move_generator_perft_crazyhouse_4,
+    "8/8/8/3k4/8/8/2K5/4r3[Nn] w - - 0 1",
+    3,
+    63947,
+);
+
+#[test]
+fn move_generator_perft_atomic() {
+    for (fen, depth, expected_result) in [
+        (STARTING_POSITION_FEN, 4, 197326),
+        (
+            "rn2kb1r/1pp1p2p/p2q1pp1/3P4/2P3b1/4PN2/PP3PPP/R2QKB1R b KQkq - 0 1",
+            3,
+            45237,
+        ),
+        (
+            "rn1qkb1r/p5pp/2p5/3p4/N3P3/5P2/PPP4P/R1BQK3 w Qkq - 0 1",
+            3,
+            23353,
+        ),
+        ("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", 3, 14295),
+        ("8/8/8/1k6/3Pp3/8/8/4KQ2 b - d3 0 1", 3, 584),
+        // Chess960 castling next to the opponent king, with captures exploding pieces around the kings.
+        ("8/8/8/8/8/8/2k5/rR4KR w KQ - 0 1", 3, 4364),
+        ("r3k1rR/5K2/8/8/8/8/8/8 b kq - 0 1", 3, 6753),
+        ("Rr2k1rR/3K4/3p4/8/8/8/7P/8 w kq - 0 1", 3, 10631),
+    ] {
+        let position = BoardPosition::try_from(
+            BoardPositionBuilder::from_str(fen)
+                .unwrap()
+                .variant(Variant::Atomic),
+        )
+        .unwrap();
+        let result = MoveGenerator::perft_test(&position, depth);
+        assert_eq!(
+            result, expected_result,
+            "Expected result {expected_result} but got {result} in position {fen}"
+        );
+    }
+}
+
+#[test]
+fn move_generator_perft_antichess() {
+    for (fen, depth, expected_result) in [
+        (
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1",
+            4,
+            153299,
+        ),
+        (
+            "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w - c6 0 2",
+            3,
+            10080,
+        ),
+        ("8/1P6/8/8/8/8/6p1/8 w - - 0 1", 4, 2112),
+        ("3k4/8/8/8/2pP4/8/8/2K5 b - d3 0 1", 5, 720),
+    ] {
+        let position = BoardPosition::try_from(
+            BoardPositionBuilder::from_str(fen)
+                .unwrap()
+                .variant(Variant::Antichess),
+        )
+        .unwrap();
+        let result = MoveGenerator::perft_test(&position, depth);
+        assert_eq!(
+            result, expected_result,
+            "Expected result {expected_result} but got {result} in position {fen}"
+        );
+    }
+}
+
+#[test]
+fn move_generator_perft_horde() {
+    for (fen, depth, expected_result) in [
+        (
+            "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1",
+            4,
+            23310,
+        ),
+        (
+            "rnbqkbnr/6p1/2p1Pp1P/P1PPPP2/Pp4PP/1p2PPPP/1P2PPPP/PPPPPPPP b kq a3 0 1",
+            3,
+            14657,
+        ),
+        (
+            "4k3/pp4q1/3P2p1/8/P3PP2/PPP2r2/PPP5/PPPP4 b - - 0 1",
+            4,
+            56539,
+        ),
+        ("4k3/8/8/3p4/8/8/P1P5/P1P5 w - - 0 1", 5, 4307),
+    ] {
+        let position = BoardPosition::try_from(
+            BoardPositionBuilder::from_str(fen)
+                .unwrap()
+                .variant(Variant::Horde),
+        )
+        .unwrap();
+        let result = MoveGenerator::perft_test(&position, depth);
+        assert_eq!(
+            result, expected_result,
+            "Expected result {expected_result} but got {result} in position {fen}"
+        );
+    }
+}
+
+#[test]
+fn move_generator_perft_racing_kings() {
+    for (fen, depth, expected_result) in [
+        ("8/8/8/8/8/8/krbnNBRK/qrbnNBRQ w - - 0 1", 4, 296242),
+        ("8/8/8/8/8/6K1/krbnNBR1/qrbnNBRQ b - - 1 1", 3, 11289),
+        ("8/5K1k/8/8/8/8/8/8 w - - 0 1", 3, 43),
+        ("8/8/3K4/8/1k6/8/8/q7 w - - 0 1", 4, 12366),
+    ] {
+        let position = BoardPosition::try_from(
+            BoardPositionBuilder::from_str(fen)
+                .unwrap()
+                .variant(Variant::RacingKings),
+        )
+        .unwrap();
+        let result = MoveGenerator::perft_test(&position, depth);
+        assert_eq!(
+            result, expected_result,
+            "Expected result {expected_result} but got {result} in position {fen}"
+        );
+    }
+}
+
+#[test]
+fn move_generator_perft_chess960_starting_positions() {
+    let standard_position =
+        BoardPosition::try_from(BoardPositionBuilder::from_chess960_index(518).unwrap()).unwrap();
+    assert!(standard_position.is_chess960());
+    assert_eq!(standard_position.get_fen(), STARTING_POSITION_FEN);
+    assert_eq!(MoveGenerator::perft_test(&standard_position, 4), 197281);
+
+    for (white_index, black_index) in [(0, 0), (959, 959), (0, 959), (357, 104)] {
+        let position = BoardPosition::try_from(
+            BoardPositionBuilder::from_double_chess960_indices(white_index, black_index).unwrap(),
+        )
+        .unwrap();
+        let reparsed_position = BoardPosition::from_str(&position.get_shredder_fen()).unwrap();
+        assert_eq!(reparsed_position.get_fen(), position.get_fen());
+        assert_eq!(
+            MoveGenerator::perft_test(&reparsed_position, 4),
+            MoveGenerator::perft_test(&position, 4),
+        );
+    }
+    assert!(BoardPositionBuilder::from_chess960_index(960).is_err());
+}
+
 #[test]
 fn move_generator_issue_15() {
     let position = BoardPositionBuilder::from_str(
@@ -246,3 +452,135 @@ fn test_masked_move_generator() {
         expected.into_iter().collect()
     );
 }
+
+fn check_masked_generation(position: &BoardPosition, depth: usize) {
+    let legal_moves = position.generate_legal_moves().collect::<HashSet<_>>();
+    let targets = position.opponent_occupied();
+    let from_masks = position
+        .self_occupied()
+        .into_iter()
+        .map(Square::to_bitboard)
+        .chain([position.self_occupied(), BB_ALL]);
+    for from_mask in from_masks {
+        for to_mask in [targets, !targets, BB_ALL] {
+            let expected = legal_moves
+                .iter()
+                .copied()
+                .filter(|move_| {
+                    (if move_.is_drop() {
+                        from_mask == BB_ALL
+                    } else {
+                        from_mask.contains(move_.get_source())
+                    }) && to_mask.contains(move_.get_dest())
+                })
+                .collect::<HashSet<_>>();
+            assert_eq!(
+                position
+                    .generate_masked_legal_moves(from_mask, to_mask)
+                    .collect::<HashSet<_>>(),
+                expected,
+                "Masked moves from {from_mask:?} to {to_mask:?} differ in position {}",
+                position.get_fen()
+            );
+        }
+    }
+    for source in position.self_occupied() {
+        for dest in ALL_SQUARES {
+            for promotion in [None, Some(Queen), Some(Knight)] {
+                let move_ = Move::new_unchecked(source, dest, promotion);
+                assert_eq!(
+                    position.is_legal(&move_),
+                    legal_moves.contains(&move_),
+                    "Legality of {move_} is wrong in position {}",
+                    position.get_fen()
+                );
+            }
+        }
+    }
+    if depth > 0 {
+        for &move_ in &legal_moves {
+            check_masked_generation(&position.make_move_new(move_), depth - 1);
+        }
+    }
+}
+
+#[test]
+fn test_masked_move_generator_matches_full_generation() {
+    for fen in [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1",
+        "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        "4k3/1Q~6/8/8/4b3/8/Kpp5/8/ b - - 0 1",
+    ] {
+        check_masked_generation(&BoardPosition::from_str(fen).unwrap(), 2);
+    }
+}
+
+fn get_slider_moves_by_walking(
+    square: Square,
+    blockers: BitBoard,
+    directions: [fn(Square) -> Option<Square>; 4],
+) -> BitBoard {
+    let mut moves = BitBoard::EMPTY;
+    for direction in directions {
+        let mut current_square = square;
+        while let Some(next_square) = direction(current_square) {
+            moves |= next_square.to_bitboard();
+            if blockers.contains(next_square) {
+                break;
+            }
+            current_square = next_square;
+        }
+    }
+    moves
+}
+
+#[test]
+fn test_each_slider_attack_method_matches_walking_the_rays() {
+    let blockers_list = (0..64u64)
+        .map(|i| BitBoard::new(i.wrapping_mul(0x9E3779B97F4A7C15) ^ (i << 32)))
+        .chain([BitBoard::EMPTY, BB_ALL])
+        .collect_vec();
+    for method in [SliderAttackMethod::Magic, SliderAttackMethod::Pext] {
+        if !method.is_supported() {
+            continue;
+        }
+        for (&square, &blockers) in ALL_SQUARES.iter().cartesian_product(&blockers_list) {
+            assert_eq!(
+                method.get_bishop_moves(square, blockers),
+                get_slider_moves_by_walking(
+                    square,
+                    blockers,
+                    [
+                        Square::up_left,
+                        Square::up_right,
+                        Square::down_left,
+                        Square::down_right
+                    ],
+                ),
+                "{method} bishop moves from {square} with blockers {blockers:?}",
+            );
+            assert_eq!(
+                method.get_rook_moves(square, blockers),
+                get_slider_moves_by_walking(
+                    square,
+                    blockers,
+                    [Square::up, Square::down, Square::left, Square::right],
+                ),
+                "{method} rook moves from {square} with blockers {blockers:?}",
+            );
+        }
+    }
+    let selected_method = SliderAttackMethod::get_selected();
+    assert!(selected_method.is_supported());
+    for (&square, &blockers) in ALL_SQUARES.iter().cartesian_product(&blockers_list) {
+        assert_eq!(
+            get_bishop_moves(square, blockers),
+            selected_method.get_bishop_moves(square, blockers),
+        );
+        assert_eq!(
+            get_rook_moves(square, blockers),
+            selected_method.get_rook_moves(square, blockers),
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use timecat::*;
+
+const PGN: &str = r#"[Event "Casual \"Game\""]
+[Site "Berlin GER"]
+[Date "1852.??.??"]
+[Round "?"]
+[White "Adolf Anderssen"]
+[Black "Jean Dufresne"]
+[Result "1-0"]
+[Annotator "Someone"]
+
+{The Evergreen Game} 1. e4 {[%clk 0:05:00] [%eval 0.3] Best by test} e5 $1 2. Nf3!? Nc6
+3. Bc4 (3. Bb5 a6 (3... Nf6 {Berlin}) 4. Ba4) 3... Bc5 ; rest of line comment
+4.b4 Bxb4 5. c3 Ba5 6. d4 exd4 7. O-O d3 8. Qb3 Qf6 9. e5 Qg6 10. Re1 Nge7 11. Ba3 b5
+12. Qxb5 Rb8 13. Qa4 Bb6 14. Nbd2 Bb7 15. Ne4 Qf5 16. Bxd3 Qh5 17. Nf6+ gxf6
+18. exf6 Rg8 19. Rad1 Qxf3 20. Rxe7+ Nxe7 21. Qxd7+ Kxd7 22. Bf5+ Ke8
+23. Bd7+ Kf8 24. Bxe7# 1-0
+
+[Event "Broken"]
+
+1. e4 e5 2. Nf3 ) Nc6 1/2-1/2
+
+% escaped line
+[Event "Setup"]
+[SetUp "1"]
+[FEN "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"]
+
+1. e4 Kd7 2. Kd2 *
+
+[Event "Crazy"]
+[Variant "Crazyhouse"]
+
+1. e4 d5 2. exd5 Qxd5 3. Nc3 Qd8 4. P@d5 *
+"#;
+
+#[test]
+fn test_pgn_reader() {
+    let games = PgnReader::new(PGN.as_bytes()).collect_vec();
+    assert_eq!(games.len(), 4);
+
+    let game = games[0].as_ref().unwrap();
+    assert_eq!(game.get_event(), Some("Casual \"Game\""));
+    assert_eq!(game.get_white(), Some("Adolf Anderssen"));
+    assert_eq!(game.get_tag("Annotator"), Some("Someone"));
+    assert_eq!(game.get_comments(), ["The Evergreen Game"]);
+    assert_eq!(game.get_result(), GameResult::Win(White));
+
+    let moves = game.get_moves();
+    assert_eq!(moves.len(), 47);
+    assert_eq!(moves[0].get_comments(), ["Best by test"]);
+    assert_eq!(moves[0].get_clock(), Some(Duration::from_secs(300)));
+    assert_eq!(
+        moves[0].get_evaluation(),
+        Some(PgnEvaluation::Centipawns(30))
+    );
+    assert_eq!(moves[1].get_nags(), [1]);
+    assert_eq!(moves[2].get_nags(), [5]);
+    assert_eq!(moves[5].get_comments(), ["rest of line comment"]);
+    let variation = &moves[4].get_variations()[0];
+    assert_eq!(
+        variation.iter().map(PgnMove::get_san).collect_vec(),
+        ["Bb5", "a6", "Ba4"]
+    );
+    assert_eq!(
+        variation[1].get_variations()[0][0].get_comments(),
+        ["Berlin"]
+    );
+
+    let board = game.to_board().unwrap();
+    assert_eq!(board.result(), GameResult::Win(White));
+    assert_eq!(board.get_num_moves(), 47);
+
+    // The broken game is reported with its position, and the reading goes on with the next one.
+    assert_eq!(
+        games[1],
+        Err(TimecatError::InvalidPgn {
+            line: 19,
+            column: 17,
+            err_msg: "no variation to close".to_string(),
+        })
+    );
+
+    let game = games[2].as_ref().unwrap();
+    assert_eq!(game.get_result(), GameResult::InProgress);
+    assert_eq!(
+        game.to_board().unwrap().get_fen(),
+        "8/3k4/8/8/4P3/8/3K4/8 b - - 2 2"
+    );
+
+    let board = games[3].as_ref().unwrap().to_board().unwrap();
+    assert_eq!(board.get_variant(), Variant::Crazyhouse);
+    assert_eq!(board.get_num_moves(), 7);
+}
+
+#[test]
+fn test_pgn_errors() {
+    for (pgn, line, column) in [
+        ("[Event \"Unclosed]\n\n1. e4 *", 1, 1),
+        ("1. e4 {unclosed comment *", 1, 7),
+        ("1. e4 (1. d4 *", 1, 7),
+        ("1. e4 & e5 *", 1, 7),
+    ] {
+        match PgnGame::from_str(pgn) {
+            Err(TimecatError::InvalidPgn {
+                line: error_line,
+                column: error_column,
+                ..
+            }) => assert_eq!((error_line, error_column), (line, column), "{pgn}"),
+            result => panic!("Expected an error for {pgn} but got {result:?}"),
+        }
+    }
+
+    // Illegal moves are reported when the game is replayed.
+    let game = PgnGame::from_str("1. e4 e4 0-1").unwrap();
+    assert_eq!(
+        game.to_board().unwrap_err(),
+        TimecatError::InvalidPgn {
+            line: 1,
+            column: 7,
+            err_msg: "cannot play the move e4".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_pgn_round_trip() {
+    let mut board = Board::from_pgn(PGN).unwrap();
+    assert_eq!(
+        Board::from_pgn(&board.get_pgn()).unwrap().get_fen(),
+        board.get_fen()
+    );
+
+    board
+        .set_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1")
+        .unwrap();
+    board.push_sans("O-O O-O-O Rf7").unwrap();
+    assert_eq!(
+        Board::from_pgn(&board.get_pgn()).unwrap().get_fen(),
+        board.get_fen()
+    );
+
+    let board =
+        Board::from_pgn("[Variant \"Crazyhouse\"]\n\n1. e4 d5 2. exd5 Qxd5 3. Nc3 Qd8 4. @d5 *")
+            .unwrap();
+    let pgn = board.get_pgn();
+    assert!(!pgn.contains("[FEN "), "{pgn}");
+    assert!(pgn.ends_with("4. P@d5"), "{pgn}");
+    let round_trip_board = Board::from_pgn(&pgn).unwrap();
+    assert_eq!(round_trip_board.get_variant(), Variant::Crazyhouse);
+    assert_eq!(round_trip_board.get_fen(), board.get_fen());
+}
@@ -35,3 +35,375 @@ fn position_is_sane() {
         );
     }
 }
+
+#[test]
+fn test_incremental_hash_matches_hash_from_scratch() {
+    GLOBAL_TIMECAT_STATE.set_hash_verification(true);
+    for (fen, expected_count) in [
+        (STARTING_POSITION_FEN, 8902),
+        (
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            97862,
+        ),
+        ("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 2812),
+        (
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            9467,
+        ),
+    ] {
+        let mut board = Board::from_fen(fen).unwrap();
+        board.get_position().verify_hash().unwrap();
+        assert_eq!(board.perft(3), expected_count);
+    }
+    let mut board = Board::default();
+    board
+        .push_sans("e4 d5 exd5 c6 dxc6 Nf6 cxb7 e5 bxa8=Q")
+        .unwrap();
+    board.push(ValidOrNullMove::NullMove).unwrap();
+    board.get_position().verify_hash().unwrap();
+    GLOBAL_TIMECAT_STATE.set_hash_verification(false);
+}
+
+#[test]
+fn test_chess960_castling_fen_formats() {
+    let position = BoardPosition::from_str("5k1r/8/8/8/8/8/8/RK2R2R w EA - 0 1").unwrap();
+    assert!(position.is_chess960());
+    assert_eq!(position.get_fen(), "5k1r/8/8/8/8/8/8/RK2R2R w EQ - 0 1");
+    assert_eq!(
+        position.get_shredder_fen(),
+        "5k1r/8/8/8/8/8/8/RK2R2R w EA - 0 1"
+    );
+    assert_eq!(
+        BoardPosition::from_str(&position.get_fen())
+            .unwrap()
+            .get_shredder_fen(),
+        position.get_shredder_fen()
+    );
+
+    let castling_move = Move::new(B1, E1, None).unwrap();
+    assert!(position.is_castling(castling_move));
+    assert_eq!(castling_move.san(&position).unwrap(), "O-O+");
+    let mut board = Board::from_fen(&position.get_fen()).unwrap();
+    board.push_uci("b1e1").unwrap();
+    assert_eq!(board.get_fen(), "5k1r/8/8/8/8/8/8/R4RKR b - - 1 1");
+    board.get_position().verify_hash().unwrap();
+    board.pop();
+    assert_eq!(board.get_shredder_fen(), position.get_shredder_fen());
+}
+
+#[test]
+fn test_standard_castling_from_polyglot_move() {
+    let position =
+        BoardPosition::from_str("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQK2R w KQkq - 0 1").unwrap();
+    assert!(!position.is_chess960());
+    let king_takes_rook = Move::new(E1, H1, None).unwrap();
+    let castling_move = position.convert_king_takes_rook_castling(king_takes_rook);
+    assert_eq!(castling_move, Move::new(E1, G1, None).unwrap());
+    assert!(position.is_castling(castling_move));
+    assert!(position.is_legal(&castling_move));
+    assert_eq!(
+        position.make_move_new(castling_move).get_fen(),
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQ1RK1 b kq - 1 1"
+    );
+}
+
+#[test]
+fn test_three_check_counters() {
+    let mut board =
+        Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 3+3 0 1").unwrap();
+    assert_eq!(board.get_variant(), Variant::ThreeCheck);
+    assert_eq!(board.get_hash(), Board::default().get_hash());
+    board.push_sans("e4 e5 Bc4 Nf6 Bxf7+").unwrap();
+    assert_eq!(board.get_remaining_checks(White), 2);
+    assert_eq!(
+        board.get_fen(),
+        "rnbqkb1r/pppp1Bpp/5n2/4p3/4P3/8/PPPP1PPP/RNBQK1NR b KQkq - 2+3 0 3"
+    );
+    board.pop();
+    assert_eq!(board.get_remaining_checks(White), 3);
+
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/4K2R b K - 0 1 +2+0").unwrap();
+    assert_eq!(board.get_remaining_checks(White), 1);
+    assert_eq!(board.get_remaining_checks(Black), 3);
+    assert!(Board::from_fen("4k3/8/8/8/8/8/8/4K2R b K - 4+3 0 1").is_err());
+}
+
+#[test]
+fn test_three_check_game_over() {
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 1+3 0 1").unwrap();
+    assert!(!board.is_game_over());
+    board.push_san("Ra8+").unwrap();
+    assert_eq!(board.status(), BoardStatus::ThreeCheck);
+    assert_eq!(board.result(), GameResult::Win(White));
+    assert_eq!(board.generate_legal_moves().len(), 0);
+    assert!(!Board::from_fen("4k3/8/8/8/8/8/8/4KN2 w - - 1+1 0 1")
+        .unwrap()
+        .is_insufficient_material());
+}
+
+#[test]
+fn test_king_of_the_hill_game_over() {
+    let position = BoardPosition::try_from(
+        BoardPositionBuilder::from_str("4k3/8/8/8/8/4K3/8/8 w - - 0 1")
+            .unwrap()
+            .variant(Variant::KingOfTheHill),
+    )
+    .unwrap();
+    assert_eq!(
+        position.get_hash(),
+        BoardPosition::from_str("4k3/8/8/8/8/4K3/8/8 w - - 0 1")
+            .unwrap()
+            .get_hash()
+    );
+    assert!(!position.is_insufficient_material());
+    let mut board = Board::from(position);
+    board.push_uci("e3d4").unwrap();
+    assert_eq!(board.status(), BoardStatus::KingOfTheHill);
+    assert_eq!(board.result(), GameResult::Win(White));
+    assert!(board.is_game_over());
+}
+
+#[test]
+fn test_variant_parsing() {
+    for variant in ALL_VARIANTS {
+        assert_eq!(variant.get_uci_name().parse::<Variant>().unwrap(), variant);
+    }
+    assert!("bughouse".parse::<Variant>().is_err());
+}
+
+#[test]
+fn test_crazyhouse_drops_and_pockets() {
+    let mut board =
+        Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1").unwrap();
+    assert_eq!(board.get_variant(), Variant::Crazyhouse);
+    assert_eq!(board.get_hash(), Board::default().get_hash());
+    board.push_sans("e4 d5 exd5 Qxd5 Nc3 Qxg2 Bxg2").unwrap();
+    assert_eq!(board.get_pocket_count(White, Queen), 1);
+    assert_eq!(board.get_pocket_count(Black, Pawn), 2);
+    assert_eq!(
+        board.get_fen(),
+        "rnb1kbnr/ppp1pppp/8/8/8/2N5/PPPP1PBP/R1BQK1NR[QPpp] b KQkq - 0 4"
+    );
+
+    let drop_move = Move::from_str("P@e2").unwrap();
+    assert!(drop_move.is_drop());
+    assert_eq!(drop_move.get_drop(), Some(Pawn));
+    assert_eq!(drop_move.to_string(), "P@e2");
+    assert!(board.is_legal(&drop_move));
+    assert!(!board.is_legal(&Move::from_str("N@e2").unwrap()));
+    assert!(!board.is_legal(&Move::from_str("P@e1").unwrap()));
+    assert_eq!(drop_move.san(&board).unwrap(), "P@e2");
+
+    let fen = board.get_fen();
+    board.push_san("@e2").unwrap();
+    assert_eq!(board.get_pocket_count(Black, Pawn), 1);
+    assert_eq!(
+        board.get_hash(),
+        Board::from_fen(&board.get_fen()).unwrap().get_hash()
+    );
+    board.pop();
+    assert_eq!(board.get_fen(), fen);
+}
+
+#[test]
+fn test_crazyhouse_promoted_pieces() {
+    let mut board = Board::from_fen("4k3/1Q~6/8/8/4b3/8/Kpp5/8/ b - - 0 1").unwrap();
+    assert_eq!(board.get_fen(), "4k3/1Q~6/8/8/4b3/8/Kpp5/8[] b - - 0 1");
+    board.push_san("Bxb7").unwrap();
+    assert_eq!(board.get_pocket_count(Black, Pawn), 1);
+    assert_eq!(board.get_pocket_count(Black, Queen), 0);
+    board.push_san("Kb3").unwrap();
+    board.push_san("c1=Q").unwrap();
+    assert!(board.get_promoted_pieces_mask().contains(C1));
+    assert_eq!(board.get_fen(), "4k3/1b6/8/8/8/1K6/1p6/2q~5[p] w - - 0 3");
+}
+
+fn atomic_board(fen: &str) -> Board {
+    Board::from(
+        BoardPosition::try_from(
+            BoardPositionBuilder::from_str(fen)
+                .unwrap()
+                .variant(Variant::Atomic),
+        )
+        .unwrap(),
+    )
+}
+
+#[test]
+fn test_atomic_explosions() {
+    let mut board = atomic_board("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+    board.push_san("Rxa8").unwrap();
+    assert_eq!(board.get_fen(), "4k2r/8/8/8/8/8/8/4K2R b Kk - 0 1");
+    board.pop();
+    assert_eq!(board.get_fen(), "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+
+    let mut board = atomic_board("4k3/2pp4/8/8/8/8/8/3RK3 w - - 0 1");
+    let hash = board.get_hash();
+    board.push_san("Rxd7").unwrap();
+    assert_eq!(board.get_fen(), "8/2p5/8/8/8/8/8/4K3 b - - 0 1");
+    assert_eq!(board.status(), BoardStatus::KingExploded);
+    assert_eq!(board.result(), GameResult::Win(White));
+    assert!(board.is_game_over());
+    board.pop();
+    assert_eq!(board.get_hash(), hash);
+}
+
+#[test]
+fn test_atomic_king_rules() {
+    // The king cannot capture.
+    let board = atomic_board("4k3/8/8/8/8/8/4r3/4K3 w - - 0 1");
+    assert!(board.is_check());
+    assert!(!board.is_legal(&Move::from_str("e1e2").unwrap()));
+    assert_eq!(board.generate_legal_moves().len(), 2);
+
+    // Touching kings cannot be checked.
+    let board = atomic_board("8/8/8/8/3k4/3K4/8/3r4 w - - 0 1");
+    assert!(!board.is_check());
+    assert!(!board.is_legal(&Move::from_str("d3d2").unwrap()));
+    assert!(board.is_legal(&Move::from_str("d3e3").unwrap()));
+
+    assert!(atomic_board("4k3/8/8/8/8/8/8/4K3 w - - 0 1").is_insufficient_material());
+    assert!(!atomic_board("4k3/8/8/8/8/8/8/3QK3 w - - 0 1").is_insufficient_material());
+}
+
+fn antichess_board(fen: &str) -> Board {
+    Board::from(
+        BoardPosition::try_from(
+            BoardPositionBuilder::from_str(fen)
+                .unwrap()
+                .variant(Variant::Antichess),
+        )
+        .unwrap(),
+    )
+}
+
+#[test]
+fn test_antichess_rules() {
+    // Captures are compulsory.
+    let board = antichess_board("rnbqkbnr/pppp1ppp/8/4p3/3P4/8/PPP1PPPP/RNBQKBNR w - - 0 2");
+    assert_eq!(
+        board.generate_legal_moves().collect_vec(),
+        [Move::from_str("d4e5").unwrap()]
+    );
+
+    // There is no castling and the king can be captured.
+    let board = antichess_board("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+    assert_eq!(board.get_fen(), "r3k2r/8/8/8/8/8/8/R3K2R w - - 0 1");
+    let mut board = antichess_board("8/8/8/8/8/8/4k3/4K3 w - - 0 1");
+    board.push_san("Kxe2").unwrap();
+    assert_eq!(board.result(), GameResult::Win(Black));
+
+    // Pawns can promote to a king.
+    let mut board = antichess_board("8/1P6/8/8/8/8/6p1/8 w - - 0 1");
+    assert_eq!(board.generate_legal_moves().len(), 5);
+    board.push_uci("b7b8k").unwrap();
+    assert_eq!(board.get_fen(), "1K6/8/8/8/8/8/6p1/8 b - - 0 1");
+
+    // Being stalemated wins the game.
+    let board = antichess_board("8/8/8/8/8/p7/P7/8 w - - 0 1");
+    assert_eq!(board.status(), BoardStatus::Stalemate);
+    assert_eq!(board.result(), GameResult::Win(White));
+
+    assert!(antichess_board("8/8/8/8/8/8/8/Bb6 w - - 0 1").is_insufficient_material());
+    assert!(!antichess_board("8/8/8/8/8/8/8/B1b5 w - - 0 1").is_insufficient_material());
+}
+
+fn horde_board(fen: &str) -> Board {
+    Board::from(
+        BoardPosition::try_from(
+            BoardPositionBuilder::from_str(fen)
+                .unwrap()
+                .variant(Variant::Horde),
+        )
+        .unwrap(),
+    )
+}
+
+#[test]
+fn test_horde_rules() {
+    assert_eq!(
+        horde_board(Variant::Horde.get_starting_fen())
+            .generate_legal_moves()
+            .len(),
+        8
+    );
+
+    // Pawns on the first rank can move two squares, without allowing en passant.
+    let mut board = horde_board("4k3/8/8/8/8/8/8/P7 w - - 0 1");
+    assert_eq!(board.generate_legal_moves().len(), 2);
+    board.push_uci("a1a3").unwrap();
+    assert_eq!(board.get_fen(), "4k3/8/8/8/8/P7/8/8 b - - 0 1");
+
+    // Black wins by capturing all the white pieces.
+    let mut board = horde_board("4k3/8/8/8/8/8/8/3r3P b - - 0 1");
+    board.push_san("Rxh1").unwrap();
+    assert_eq!(board.status(), BoardStatus::AllPiecesCaptured);
+    assert_eq!(board.result(), GameResult::Win(Black));
+
+    // Black still needs its king.
+    assert!(BoardPosition::try_from(
+        BoardPositionBuilder::from_str("8/8/8/8/8/8/8/P7 w - - 0 1")
+            .unwrap()
+            .variant(Variant::Horde),
+    )
+    .is_err());
+}
+
+fn racing_kings_board(fen: &str) -> Board {
+    Board::from(
+        BoardPosition::try_from(
+            BoardPositionBuilder::from_str(fen)
+                .unwrap()
+                .variant(Variant::RacingKings),
+        )
+        .unwrap(),
+    )
+}
+
+#[test]
+fn test_racing_kings_rules() {
+    // Giving check is not allowed.
+    let board = racing_kings_board("8/k7/8/8/8/8/1R6/7K w - - 0 1");
+    let moves = board.generate_legal_moves().collect_vec();
+    assert!(!moves.contains(&Move::from_str("b2a2").unwrap()));
+    assert!(!moves.contains(&Move::from_str("b2b7").unwrap()));
+    assert!(moves.contains(&Move::from_str("b2b8").unwrap()));
+    for fen in [
+        "8/k7/8/8/8/8/R7/7K b - - 0 1",
+        "8/k7/8/8/8/8/P7/7K w - - 0 1",
+    ] {
+        assert!(BoardPosition::try_from(
+            BoardPositionBuilder::from_str(fen)
+                .unwrap()
+                .variant(Variant::RacingKings),
+        )
+        .is_err());
+    }
+
+    // Black reaching the eighth rank first wins.
+    let mut board = racing_kings_board("8/k7/8/8/8/8/8/5K2 b - - 0 1");
+    board.push_san("Kb8").unwrap();
+    assert_eq!(board.result(), GameResult::Win(Black));
+
+    // White only wins if black cannot reach the eighth rank right after.
+    let mut board = racing_kings_board("8/5K2/8/8/8/8/k7/8 w - - 0 1");
+    board.push_san("Kf8").unwrap();
+    assert_eq!(board.result(), GameResult::Win(White));
+
+    let mut board = racing_kings_board("8/5K1k/8/8/8/8/8/8 w - - 0 1");
+    board.push_san("Ke8").unwrap();
+    assert_eq!(board.status(), BoardStatus::Ongoing);
+    let mut drawn_board = board.clone();
+    drawn_board.push_san("Kh8").unwrap();
+    assert_eq!(drawn_board.status(), BoardStatus::RacingKings(None));
+    assert_eq!(drawn_board.result(), GameResult::Draw);
+    board.push_san("Kh6").unwrap();
+    assert_eq!(board.result(), GameResult::Win(White));
+
+    // The black king cannot escape to the eighth rank along the line of a rook checking it, which
+    // only happens after an unchecked move.
+    let position = racing_kings_board("K7/5Nk1/8/2B5/8/8/8/5R2 w - - 0 1")
+        .get_position()
+        .make_move_new(Move::from_str("f1g1").unwrap());
+    assert_eq!(position.status(), BoardStatus::RacingKings(Some(White)));
+}
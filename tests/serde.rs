@@ -55,4 +55,19 @@ mod serde_tests {
         assert_eq!(data, de_data);
         Ok(())
     }
+
+    #[test]
+    fn search_info_iterations_test() -> std::result::Result<(), Box<dyn Error>> {
+        let mut engine = Engine::default();
+        engine.set_opening_book::<PolyglotBookHashMap>(None);
+        let search_info = engine.go_quiet(&SearchConfig::new_depth(4));
+        let json = serde_json::to_value(&search_info)?;
+        assert_eq!(
+            json["iterations"]
+                .as_array()
+                .map(|iterations| iterations.len()),
+            Some(search_info.get_iterations().len())
+        );
+        test_serde(search_info.get_iterations().to_vec())
+    }
 }
@@ -0,0 +1,64 @@
+use timecat::*;
+
+const FENS: [&str; 4] = [
+    STARTING_POSITION_FEN,
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+];
+
+fn pick_all_moves(board: &Board, move_sorter: &mut MoveSorter, tt_move: Option<Move>) -> Vec<Move> {
+    let mut move_picker = MovePicker::new(move_sorter, board, 0, tt_move, None);
+    let mut moves = Vec::new();
+    while let Some(move_) = move_picker.next_move(board, move_sorter) {
+        moves.push(move_);
+    }
+    assert_eq!(move_picker.get_stage(), MovePickerStage::Done);
+    moves
+}
+
+#[test]
+fn test_move_picker_yields_all_legal_moves_once() {
+    for fen in FENS {
+        let board = Board::from_fen(fen).unwrap();
+        let mut move_sorter = MoveSorter::new();
+        let legal_moves = board.generate_legal_moves().collect_vec();
+        let quiet_move = legal_moves
+            .iter()
+            .copied()
+            .find(|&move_| !board.is_capture(move_) && move_.get_promotion().is_none())
+            .unwrap();
+        move_sorter.update_killer_moves(quiet_move, 0);
+        let tt_move = legal_moves.last().copied();
+        let moves = pick_all_moves(&board, &mut move_sorter, tt_move);
+        assert_eq!(moves.first().copied(), tt_move);
+        assert_eq!(moves.len(), legal_moves.len());
+        assert!(moves.iter().all_unique());
+        assert!(legal_moves.iter().all(|move_| moves.contains(move_)));
+        let illegal_tt_move = Move::new(A1, A5, None).ok();
+        assert!(illegal_tt_move.is_some_and(|move_| !legal_moves.contains(&move_)));
+        assert_eq!(
+            pick_all_moves(&board, &mut move_sorter, illegal_tt_move).len(),
+            legal_moves.len()
+        );
+    }
+}
+
+#[test]
+fn test_move_picker_stage_order() {
+    let board = Board::from_fen(FENS[1]).unwrap();
+    let mut move_sorter = MoveSorter::new();
+    let moves = pick_all_moves(&board, &mut move_sorter, None);
+    let first_quiet_index = moves
+        .iter()
+        .position(|&move_| !board.is_capture(move_))
+        .unwrap();
+    let winning_capture = board.parse_uci("e2a6").unwrap();
+    assert!(
+        moves
+            .iter()
+            .position(|&move_| move_ == winning_capture)
+            .unwrap()
+            < first_quiet_index
+    );
+}
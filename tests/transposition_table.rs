@@ -0,0 +1,264 @@
+use timecat::*;
+
+fn get_same_bucket_keys(transposition_table: &TranspositionTable, num_keys: u64) -> Vec<u64> {
+    let num_buckets = (transposition_table.len() / transposition_table.get_bucket_size()) as u64;
+    (0..num_keys).map(|i| 1 + i * num_buckets).collect()
+}
+
+fn is_stored(transposition_table: &TranspositionTable, key: u64) -> bool {
+    transposition_table.read(key, 0, 0).0.is_some()
+}
+
+#[test]
+fn test_transposition_table_bucket() {
+    let transposition_table = TranspositionTable::new(CacheTableSize::Exact(1));
+    assert_eq!(transposition_table.get_bucket_size(), TT_BUCKET_SIZE);
+    let keys = get_same_bucket_keys(&transposition_table, TT_BUCKET_SIZE as u64);
+    for (depth, &key) in keys.iter().enumerate() {
+        transposition_table.write(key, depth as Depth + 1, 0, 10, EntryFlagHash::Exact, None);
+    }
+    assert!(keys.iter().all(|&key| is_stored(&transposition_table, key)));
+    assert_eq!(transposition_table.get_num_cells_filled(), TT_BUCKET_SIZE);
+}
+
+#[test]
+fn test_transposition_table_aging() {
+    let transposition_table = TranspositionTable::new(CacheTableSize::Exact(1));
+    let keys = get_same_bucket_keys(&transposition_table, 4);
+    transposition_table.write(keys[0], 10, 0, 10, EntryFlagHash::Exact, None);
+    assert!(transposition_table.get_hash_full() > 0.0);
+    transposition_table.increment_generation();
+    assert_eq!(transposition_table.get_hash_full(), 0.0);
+    transposition_table.write(keys[1], 3, 0, 10, EntryFlagHash::Exact, None);
+    transposition_table.write(keys[2], 3, 0, 10, EntryFlagHash::Exact, None);
+    transposition_table.write(keys[3], 1, 0, 10, EntryFlagHash::Exact, None);
+    assert!(!is_stored(&transposition_table, keys[0]));
+    assert!(keys[1..]
+        .iter()
+        .all(|&key| is_stored(&transposition_table, key)));
+    assert!(transposition_table.get_hash_full() > 0.0);
+}
+
+#[test]
+fn test_transposition_table_keeps_deep_entries_of_older_generations() {
+    let transposition_table = TranspositionTable::new(CacheTableSize::Exact(1));
+    transposition_table.write(1, 20, 0, 10, EntryFlagHash::Exact, None);
+    transposition_table.increment_generation();
+    transposition_table.write(1, 5, 0, 20, EntryFlagHash::Exact, None);
+    assert_eq!(
+        transposition_table.read(1, 20, 0).0,
+        Some((10, EntryFlagHash::Exact))
+    );
+    for _ in 0..3 {
+        transposition_table.increment_generation();
+    }
+    transposition_table.write(1, 5, 0, 20, EntryFlagHash::Exact, None);
+    assert_eq!(transposition_table.read(1, 20, 0).0, None);
+    assert_eq!(
+        transposition_table.read(1, 5, 0).0,
+        Some((20, EntryFlagHash::Exact))
+    );
+}
+
+#[test]
+fn test_transposition_table_ignores_illegal_best_move() {
+    let transposition_table = TranspositionTable::new(CacheTableSize::Exact(1));
+    let position = BoardPosition::default();
+    let hash = position.get_hash();
+    transposition_table.write(
+        hash,
+        1,
+        0,
+        0,
+        EntryFlagHash::Exact,
+        Move::from_str("e2e5").ok(),
+    );
+    assert!(transposition_table.read_best_move(hash).is_some());
+    assert_eq!(transposition_table.read_legal_best_move(&position), None);
+    let best_move = Move::from_str("e2e4").unwrap();
+    transposition_table.write(hash, 2, 0, 0, EntryFlagHash::Exact, Some(best_move));
+    assert_eq!(
+        transposition_table.read_legal_best_move(&position),
+        Some(best_move)
+    );
+}
+
+#[test]
+fn test_transposition_table_entry_packing() {
+    let transposition_table = TranspositionTable::new(CacheTableSize::Exact(1));
+    let best_move = Move::from_str("e7e8q").unwrap();
+    transposition_table.write(42, 7, 0, -123, EntryFlagHash::Beta, Some(best_move));
+    assert_eq!(
+        transposition_table.read(42, 7, 0),
+        (Some((-123, EntryFlagHash::Beta)), Some(best_move))
+    );
+    assert_eq!(transposition_table.read(42, 8, 0), (None, Some(best_move)));
+    transposition_table
+        .set_size(CacheTableSize::Exact(2))
+        .unwrap();
+    assert_eq!(transposition_table.read_best_move(42), Some(best_move));
+    transposition_table.clear_best_moves();
+    assert_eq!(transposition_table.read_best_move(42), None);
+    transposition_table.clear();
+    assert_eq!(transposition_table.read(42, 0, 0), (None, None));
+}
+
+#[test]
+fn test_cache_table_concurrent_access() {
+    let cache_table = std::sync::Arc::new(CacheTable::<Score>::new(CacheTableSize::Exact(1)));
+    let join_handles = (0..8_u64)
+        .map(|thread_id| {
+            let cache_table = cache_table.clone();
+            std::thread::spawn(move || {
+                for i in 0..100_000_u64 {
+                    let key = (i * 8 + thread_id).wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+                    cache_table.add(key, (key % 1000) as Score);
+                    let other_key =
+                        ((i + 1) * 8 + thread_id).wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+                    if let Some(score) = cache_table.get(other_key) {
+                        assert_eq!(score, (other_key % 1000) as Score);
+                    }
+                }
+            })
+        })
+        .collect_vec();
+    for join_handle in join_handles {
+        join_handle.join().unwrap();
+    }
+    assert!(cache_table.get_num_cells_filled() > 0);
+}
+
+fn get_hash_file_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("timecat_{name}_{}.hash", std::process::id()))
+}
+
+#[test]
+fn test_transposition_table_save_and_load() {
+    let path = get_hash_file_path("save_and_load");
+    let transposition_table = TranspositionTable::new(CacheTableSize::Exact(1));
+    let keys = get_same_bucket_keys(&transposition_table, TT_BUCKET_SIZE as u64);
+    for (depth, &key) in keys.iter().enumerate() {
+        transposition_table.write(key, depth as Depth + 1, 0, 10, EntryFlagHash::Exact, None);
+    }
+    transposition_table.increment_generation();
+    transposition_table.save_to_file(&path).unwrap();
+    let loaded_table = TranspositionTable::new(CacheTableSize::Exact(2));
+    loaded_table.load_from_file(&path).unwrap();
+    assert_eq!(loaded_table.get_size(), CacheTableSize::Exact(1));
+    assert_eq!(
+        loaded_table.get_generation(),
+        transposition_table.get_generation()
+    );
+    assert_eq!(loaded_table.get_num_cells_filled(), TT_BUCKET_SIZE);
+    for &key in &keys {
+        assert_eq!(
+            loaded_table.read(key, 0, 0),
+            transposition_table.read(key, 0, 0)
+        );
+    }
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_transposition_table_rejects_invalid_hash_file() {
+    let path = get_hash_file_path("invalid");
+    let transposition_table = TranspositionTable::new(CacheTableSize::Exact(1));
+    transposition_table.write(1, 5, 0, 10, EntryFlagHash::Exact, None);
+    transposition_table.save_to_file(&path).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    for index in [0, 8, 100] {
+        let mut corrupted_bytes = bytes.clone();
+        corrupted_bytes[index] ^= 1;
+        std::fs::write(&path, corrupted_bytes).unwrap();
+        let loaded_table = TranspositionTable::new(CacheTableSize::Exact(2));
+        loaded_table.write(2, 3, 0, 20, EntryFlagHash::Exact, None);
+        assert!(loaded_table.load_from_file(&path).is_err());
+        assert_eq!(loaded_table.get_size(), CacheTableSize::Exact(2));
+        assert_eq!(loaded_table.get_num_cells_filled(), 1);
+        assert!(loaded_table.read(2, 0, 0).0.is_some());
+    }
+    std::fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+    assert!(transposition_table.load_from_file(&path).is_err());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_transposition_table_rejects_incompatible_layout() {
+    let path = get_hash_file_path("incompatible_layout");
+    let transposition_table = TranspositionTable::new(CacheTableSize::Exact(1));
+    transposition_table.write(1, 5, 0, 10, EntryFlagHash::Exact, None);
+    transposition_table.save_to_file(&path).unwrap();
+    let mut bytes = std::fs::read(&path).unwrap();
+    let layout_version = TT_ENTRY_LAYOUT_VERSION as u64 + 1;
+    bytes[8..16].copy_from_slice(&layout_version.to_le_bytes());
+    std::fs::write(&path, bytes).unwrap();
+    let loaded_table = TranspositionTable::new(CacheTableSize::Exact(2));
+    assert!(matches!(
+        loaded_table.load_from_file(&path),
+        Err(TimecatError::IncompatibleHashFileLayout { layout_version: version, .. }) if version == layout_version
+    ));
+    assert_eq!(loaded_table.get_size(), CacheTableSize::Exact(2));
+    assert!(loaded_table.is_empty());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_cache_table_resize_failure_keeps_table() {
+    let transposition_table = TranspositionTable::new(CacheTableSize::Exact(1));
+    transposition_table.write(42, 7, 0, 10, EntryFlagHash::Exact, None);
+    let memory_usage = transposition_table.get_memory_usage();
+    assert!(transposition_table
+        .set_size(CacheTableSize::Exact(usize::MAX >> 20))
+        .is_err());
+    assert_eq!(transposition_table.get_size(), CacheTableSize::Exact(1));
+    assert_eq!(transposition_table.get_memory_usage(), memory_usage);
+    assert!(transposition_table.read(42, 7, 0).0.is_some());
+}
+
+#[test]
+fn test_transposition_table_write_best_move() {
+    let transposition_table = TranspositionTable::new(CacheTableSize::Exact(1));
+    let keys = get_same_bucket_keys(&transposition_table, 2);
+    let best_move = Move::from_str("e2e4").unwrap();
+    transposition_table.write_best_move(keys[0], best_move);
+    assert_eq!(
+        transposition_table.read(keys[0], 0, 0),
+        (None, Some(best_move))
+    );
+    transposition_table.write(keys[1], 10, 0, 25, EntryFlagHash::Beta, None);
+    transposition_table.write_best_move(keys[1], best_move);
+    assert_eq!(
+        transposition_table.read(keys[1], 0, 0),
+        (Some((25, EntryFlagHash::Beta)), Some(best_move))
+    );
+}
+
+#[test]
+fn test_transposition_table_resize_while_iterating() {
+    let transposition_table = TranspositionTable::new(CacheTableSize::Exact(1));
+    transposition_table.write(1, 10, 0, 10, EntryFlagHash::Exact, None);
+    let mut entries = transposition_table.iter();
+    assert!(entries.next().is_some());
+    transposition_table
+        .set_size(CacheTableSize::Exact(2))
+        .unwrap();
+    assert_eq!(entries.count() + 1, transposition_table.len());
+    assert!(is_stored(&transposition_table, 1));
+}
+
+#[test]
+fn test_fractional_depth_round_trip() {
+    for depth in 0..=Depth::MAX {
+        let fractional_depth = plies_to_fractional_depth(depth);
+        assert_eq!(fractional_depth_to_plies(fractional_depth), depth);
+        assert_eq!(
+            fractional_depth_to_plies(fractional_depth + ONE_PLY - 1),
+            depth
+        );
+    }
+    let transposition_table = TranspositionTable::new(CacheTableSize::Exact(1));
+    let depth = fractional_depth_to_plies(plies_to_fractional_depth(7) + ONE_PLY / 2);
+    transposition_table.write(1, depth, 0, 10, EntryFlagHash::Exact, None);
+    assert!(transposition_table.read(1, 7, 0).0.is_some());
+    assert!(transposition_table.read(1, 8, 0).0.is_none());
+}